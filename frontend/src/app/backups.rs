@@ -0,0 +1,216 @@
+use super::{convert_epoch_to_local_time, format_size, Backups, WebUI, WebUiMessage};
+
+impl WebUI {
+    pub(super) fn view_backups(&self, ctx: &egui::Context, backups: &Backups) {
+        let mut open = true;
+
+        egui::Window::new("Backups")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let btn = egui::Button::new("💾 Back up this folder");
+                    let has_cwd = self.browser().cwd.is_some();
+                    let rsp = ui.add_enabled(self.backup_busy == false && has_cwd, btn);
+                    if rsp.clicked() {
+                        if let Some(cwd) = &self.browser().cwd {
+                            self.tx.send(WebUiMessage::ActBackup(cwd.clone())).unwrap();
+                        }
+                    }
+
+                    if self.backup_busy {
+                        ui.spinner();
+                    }
+                });
+
+                if let Some(id) = &backups.confirm {
+                    ui.separator();
+                    ui.label(format!(
+                        "Restore {}? The current content of the folder is backed up first.",
+                        id
+                    ));
+                    ui.horizontal(|ui| {
+                        let btn = egui::Button::new("Restore");
+                        if ui.add_enabled(self.backup_busy == false, btn).clicked() {
+                            self.tx
+                                .send(WebUiMessage::ActRestoreBackup(id.clone()))
+                                .unwrap();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.tx.send(WebUiMessage::SetBackupConfirm(None)).unwrap();
+                        }
+                    });
+                }
+
+                ui.separator();
+
+                let list = match &backups.snapshots {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.spinner();
+                        return;
+                    }
+                };
+
+                if list.snapshots.is_empty() {
+                    ui.label("No backups yet.");
+                    return;
+                }
+
+                // Group snapshots by folder, keeping the newest first.
+                let mut sources: Vec<&str> = Vec::new();
+                for snapshot in list.snapshots.iter() {
+                    if sources.contains(&snapshot.source.as_str()) == false {
+                        sources.push(snapshot.source.as_str());
+                    }
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for source in sources {
+                        let btn = egui::Button::new(format!("📁 {}", source));
+                        if ui.add(btn).on_hover_text("Open folder").clicked() {
+                            self.cd(ctx, source);
+                        }
+
+                        egui::Grid::new(source)
+                            .num_columns(5)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for snapshot in list.snapshots.iter() {
+                                    if snapshot.source != source {
+                                        continue;
+                                    }
+
+                                    ui.label(convert_epoch_to_local_time(snapshot.created));
+                                    ui.label(format!("{} files", snapshot.files));
+                                    ui.label(format_size(snapshot.size));
+
+                                    let btn = egui::Button::new("♻ Restore");
+                                    if ui.add_enabled(self.backup_busy == false, btn).clicked() {
+                                        self.tx
+                                            .send(WebUiMessage::SetBackupConfirm(Some(
+                                                snapshot.id.clone(),
+                                            )))
+                                            .unwrap();
+                                    }
+
+                                    let btn = egui::Button::new("🗑");
+                                    let rsp = ui
+                                        .add_enabled(self.backup_busy == false, btn)
+                                        .on_hover_text("Delete");
+                                    if rsp.clicked() {
+                                        self.tx
+                                            .send(WebUiMessage::ActDeleteBackup(
+                                                snapshot.id.clone(),
+                                            ))
+                                            .unwrap();
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+
+                        ui.add_space(8.0);
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseBackups).unwrap();
+        }
+    }
+
+    /// Fetch all snapshots for the backups view.
+    pub(super) fn fetch_backups(&self, ctx: &egui::Context) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BackupListRequest { path: None },
+            move |rsp: Result<crate::protocol::BackupListResponse, String>| {
+                tx.send(WebUiMessage::SetBackups(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Snapshot a folder.
+    ///
+    /// # Arguments
+    /// + `path`: the folder to back up.
+    pub(super) fn backup(&mut self, ctx: &egui::Context, path: String) {
+        self.backup_busy = true;
+        self.status = Some(format!("Backing up {}", path));
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BackupCreateRequest { path: path },
+            move |rsp: Result<crate::protocol::BackupCreateResponse, String>| {
+                let rsp = rsp.map(|v| match v.skipped.is_empty() {
+                    true => format!(
+                        "Backed up {} files of {}",
+                        v.snapshot.files, v.snapshot.source
+                    ),
+                    false => format!(
+                        "Backed up {} files of {}, skipped symlinks and special files: {}",
+                        v.snapshot.files,
+                        v.snapshot.source,
+                        v.skipped.join(", ")
+                    ),
+                });
+                tx.send(WebUiMessage::FinishBackup(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Restore a snapshot.
+    ///
+    /// # Arguments
+    /// + `id`: the id of the snapshot.
+    pub(super) fn restore_backup(&mut self, ctx: &egui::Context, id: String) {
+        self.backup_busy = true;
+        self.status = Some(format!("Restoring {}", id));
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BackupRestoreRequest { id: id.clone() },
+            move |rsp: Result<crate::protocol::BackupRestoreResponse, String>| {
+                let rsp = rsp.map(|v| match v.skipped.is_empty() {
+                    true => format!("Restored {}", id),
+                    false => format!(
+                        "Restored {}, removed symlinks and special files: {}",
+                        id,
+                        v.skipped.join(", ")
+                    ),
+                });
+                tx.send(WebUiMessage::FinishBackup(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Delete a snapshot.
+    ///
+    /// # Arguments
+    /// + `id`: the id of the snapshot.
+    pub(super) fn delete_backup(&mut self, ctx: &egui::Context, id: String) {
+        self.backup_busy = true;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BackupDeleteRequest { id: id.clone() },
+            move |rsp: Result<crate::protocol::BackupDeleteResponse, String>| {
+                let rsp = rsp.map(|_| format!("Deleted {}", id));
+                tx.send(WebUiMessage::FinishBackup(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+}
@@ -0,0 +1,634 @@
+use super::{
+    convert_epoch_to_local_time, format_size, DiskUsage, Properties, Rename, RomCheck, WebUI,
+    WebUiMessage,
+};
+
+impl WebUI {
+    pub(super) fn view_rename(&self, ctx: &egui::Context, rename: &Rename) {
+        let mut open = true;
+
+        egui::Window::new("Rename")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut name = rename.name.clone();
+                let rsp = ui.text_edit_singleline(&mut name);
+                if name != rename.name {
+                    self.tx.send(WebUiMessage::SetRenameName(name)).unwrap();
+                }
+                let submitted = rsp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+
+                if let Some(error) = &rename.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+
+                ui.horizontal(|ui| {
+                    let btn = egui::Button::new("Rename");
+                    let enabled = rename.busy == false && rename.name.is_empty() == false;
+                    if ui.add_enabled(enabled, btn).clicked() || (enabled && submitted) {
+                        self.tx.send(WebUiMessage::ActSaveRename).unwrap();
+                    }
+                    if rename.busy {
+                        ui.spinner();
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseRename).unwrap();
+        }
+    }
+
+    pub(super) fn view_properties(&self, ctx: &egui::Context, properties: &Properties) {
+        let mut open = true;
+        let item = &properties.item;
+
+        egui::Window::new("Properties")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("properties_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Name");
+                        ui.label(item.f_name.as_str());
+                        ui.end_row();
+
+                        ui.label("Path");
+                        ui.label(item.f_path.as_str());
+                        ui.end_row();
+
+                        ui.label("Type");
+                        ui.label(item.f_type.as_str());
+                        ui.end_row();
+
+                        ui.label("Size");
+                        ui.label(format_size(item.f_size));
+                        ui.end_row();
+
+                        ui.label("Modified");
+                        ui.label(convert_epoch_to_local_time(item.f_modified));
+                        ui.end_row();
+                    });
+
+                let btn = egui::Button::new("🗑 Move to trash");
+                if ui.add_enabled(self.trash_busy == false, btn).clicked() {
+                    self.tx
+                        .send(WebUiMessage::AskTrashPut(vec![item.f_path.clone()]))
+                        .unwrap();
+                }
+
+                if item.f_type == "DIR" {
+                    return;
+                }
+
+                if item.f_executable {
+                    ui.separator();
+                    if ui.button("🚂 Add to Steam").clicked() {
+                        self.tx
+                            .send(WebUiMessage::ActAddShortcut(item.f_path.clone()))
+                            .unwrap();
+                    }
+                }
+
+                ui.separator();
+
+                if properties.checksum_id.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label("Computing checksums…");
+                        if ui.button("Cancel").clicked() {
+                            self.tx.send(WebUiMessage::ActCancelChecksum).unwrap();
+                        }
+                    });
+                    return;
+                }
+
+                match &properties.checksum {
+                    Some(Ok(checksum)) => {
+                        egui::Grid::new("checksum_grid")
+                            .num_columns(3)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                let rows = [
+                                    ("CRC32", &checksum.crc32),
+                                    ("MD5", &checksum.md5),
+                                    ("SHA-1", &checksum.sha1),
+                                    ("SHA-256", &checksum.sha256),
+                                    ("xxh3", &checksum.xxh3),
+                                ];
+                                for (name, value) in rows {
+                                    ui.label(name);
+                                    ui.monospace(value.as_str());
+                                    let rsp = ui.small_button("📋").on_hover_text("Copy");
+                                    if rsp.clicked() {
+                                        ui.output_mut(|o| o.copied_text = value.clone());
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    }
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        if ui.button("Retry").clicked() {
+                            self.tx.send(WebUiMessage::ActChecksum).unwrap();
+                        }
+                    }
+                    None => {
+                        if ui.button("Compute checksums").clicked() {
+                            self.tx.send(WebUiMessage::ActChecksum).unwrap();
+                        }
+                    }
+                }
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseProperties).unwrap();
+        }
+    }
+
+    pub(super) fn view_romcheck(&self, ctx: &egui::Context, romcheck: &RomCheck) {
+        let mut open = true;
+
+        egui::Window::new("ROM verification")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([960.0, 600.0])
+            .show(ctx, |ui| {
+                ui.label(romcheck.path.as_str());
+
+                let report = match &romcheck.report {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Verifying…");
+                        });
+                        return;
+                    }
+                };
+
+                if report.dats.is_empty() {
+                    ui.label("No DAT files found, put No-Intro or Redump DAT files in the DAT folder of the server.");
+                    return;
+                }
+
+                let count = |status| report.entries.iter().filter(|v| v.status == status).count();
+                let fixable = report.entries.iter().filter(|v| v.fix_name.is_some()).count();
+
+                ui.horizontal(|ui| {
+                    let filters = [
+                        (None, format!("All ({})", report.entries.len())),
+                        (
+                            Some(crate::protocol::RomCheckStatus::Matched),
+                            format!("Matched ({})", count(crate::protocol::RomCheckStatus::Matched)),
+                        ),
+                        (
+                            Some(crate::protocol::RomCheckStatus::Rename),
+                            format!("Rename needed ({})", count(crate::protocol::RomCheckStatus::Rename)),
+                        ),
+                        (
+                            Some(crate::protocol::RomCheckStatus::Repack),
+                            format!("Repack needed ({})", count(crate::protocol::RomCheckStatus::Repack)),
+                        ),
+                        (
+                            Some(crate::protocol::RomCheckStatus::Unknown),
+                            format!("Unknown ({})", count(crate::protocol::RomCheckStatus::Unknown)),
+                        ),
+                    ];
+                    for (filter, text) in filters {
+                        if ui.selectable_label(romcheck.filter == filter, text).clicked() {
+                            self.tx.send(WebUiMessage::SetRomCheckFilter(filter)).unwrap();
+                        }
+                    }
+                    ui.label(format!("Missing ({})", report.missing.len()));
+
+                    let btn = egui::Button::new(format!("Rename {} to DAT names", fixable));
+                    if ui.add_enabled(fixable > 0, btn).clicked() {
+                        self.tx.send(WebUiMessage::ActRomFix).unwrap();
+                    }
+                });
+
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("romcheck_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for entry in report.entries.iter() {
+                                if romcheck.filter.is_some() && romcheck.filter != Some(entry.status) {
+                                    continue;
+                                }
+
+                                let status = match entry.status {
+                                    crate::protocol::RomCheckStatus::Matched => "✔",
+                                    crate::protocol::RomCheckStatus::Rename => "✏",
+                                    crate::protocol::RomCheckStatus::Repack => "📦",
+                                    crate::protocol::RomCheckStatus::Unknown => "❓",
+                                };
+                                ui.label(status);
+
+                                let mut name = entry.path.clone();
+                                if let Some(v) = &entry.entry {
+                                    name = format!("{} » {}", name, v);
+                                }
+                                ui.label(name);
+
+                                match (&entry.game, &entry.fix_name) {
+                                    (_, Some(fix_name)) => ui.label(format!("→ {}", fix_name)),
+                                    (Some(game), None) if entry.status == crate::protocol::RomCheckStatus::Repack => {
+                                        ui.label(format!("{} (repack needed)", game))
+                                    }
+                                    (Some(game), None) => ui.label(game.as_str()),
+                                    (None, None) => ui.label(""),
+                                };
+                                ui.end_row();
+                            }
+
+                            if romcheck.filter.is_none() {
+                                for missing in report.missing.iter() {
+                                    ui.label("✖");
+                                    ui.label(missing.rom.as_str());
+                                    ui.label(format!("{} (missing)", missing.game));
+                                    ui.end_row();
+                                }
+                            }
+                        });
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseRomCheck).unwrap();
+        }
+    }
+
+    pub(super) fn view_du(&self, ctx: &egui::Context, du: &DiskUsage) {
+        let mut open = true;
+
+        egui::Window::new("Disk usage")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([960.0, 600.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let parent = std::path::Path::new(&du.path)
+                        .parent()
+                        .map(|v| v.to_string_lossy().to_string());
+                    let btn = egui::Button::new("⬆");
+                    let rsp = ui
+                        .add_enabled(parent.is_some(), btn)
+                        .on_hover_text("Parent folder");
+                    if rsp.clicked() {
+                        self.tx
+                            .send(WebUiMessage::ActDu((parent.unwrap(), false)))
+                            .unwrap();
+                    }
+
+                    if ui.button("🔃").on_hover_text("Scan again").clicked() {
+                        self.tx
+                            .send(WebUiMessage::ActDu((du.path.clone(), true)))
+                            .unwrap();
+                    }
+
+                    let mut apparent = du.apparent;
+                    if ui.checkbox(&mut apparent, "Apparent size").changed() {
+                        self.tx.send(WebUiMessage::SetDuApparent(apparent)).unwrap();
+                    }
+
+                    ui.label(du.path.as_str());
+                });
+
+                ui.separator();
+
+                let usage = match &du.usage {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.spinner();
+                        return;
+                    }
+                };
+
+                let node = match &usage.node {
+                    Some(v) if usage.scanning == false => v,
+                    _ => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label(format!("Scanning… {} entries", usage.scanned));
+                        });
+                        return;
+                    }
+                };
+
+                let size_of = |v: &crate::protocol::DuEntry| {
+                    if du.apparent {
+                        v.apparent
+                    } else {
+                        v.disk
+                    }
+                };
+                let total = size_of(node);
+                ui.label(format!("{} in {} files", format_size(total), node.files));
+
+                let mut children: Vec<&crate::protocol::DuEntry> = usage.children.iter().collect();
+                children.sort_by_key(|v| std::cmp::Reverse(size_of(v)));
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("du_grid")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for child in children {
+                                let fraction = if total > 0 {
+                                    size_of(child) as f32 / total as f32
+                                } else {
+                                    0.0
+                                };
+                                ui.add(
+                                    egui::ProgressBar::new(fraction)
+                                        .desired_width(240.0)
+                                        .text(format_size(size_of(child))),
+                                );
+
+                                if child.is_dir {
+                                    let label = egui::Label::new(format!("📁 {}", child.name))
+                                        .sense(egui::Sense::click());
+                                    let rsp = ui
+                                        .add(label)
+                                        .on_hover_cursor(egui::CursorIcon::PointingHand);
+                                    if rsp.clicked() {
+                                        self.tx
+                                            .send(WebUiMessage::ActDu((child.path.clone(), false)))
+                                            .unwrap();
+                                    }
+                                    ui.label(format!("{} files", child.files));
+                                } else {
+                                    ui.label(format!("📒 {}", child.name));
+                                    ui.label("");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseDu).unwrap();
+        }
+    }
+
+    pub(super) fn view_paste_conflict(&self, ctx: &egui::Context, conflicts: &[String]) {
+        const MAX_SHOWN: usize = 10;
+
+        let mut open = true;
+
+        egui::Window::new("Files already exist")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for name in conflicts.iter().take(MAX_SHOWN) {
+                    ui.label(name.as_str());
+                }
+                if conflicts.len() > MAX_SHOWN {
+                    ui.label(format!("and {} more", conflicts.len() - MAX_SHOWN));
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let policies = [
+                        ("Replace", crate::protocol::ConflictPolicy::Overwrite),
+                        ("Skip", crate::protocol::ConflictPolicy::Skip),
+                        ("Keep both", crate::protocol::ConflictPolicy::Rename),
+                    ];
+                    for (text, policy) in policies {
+                        if ui.button(text).clicked() {
+                            self.tx.send(WebUiMessage::ActPasteWith(policy)).unwrap();
+                        }
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.tx.send(WebUiMessage::ClosePasteConflict).unwrap();
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::ClosePasteConflict).unwrap();
+        }
+    }
+
+    pub(super) fn view_confirm_trash(&self, ctx: &egui::Context, paths: &[String]) {
+        const MAX_SHOWN: usize = 10;
+
+        let mut open = true;
+
+        egui::Window::new("Move to trash?")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for path in paths.iter().take(MAX_SHOWN) {
+                    ui.label(path.as_str());
+                }
+                if paths.len() > MAX_SHOWN {
+                    ui.label(format!("and {} more", paths.len() - MAX_SHOWN));
+                }
+
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    let btn = egui::Button::new("🗑 Move to trash");
+                    if ui.add_enabled(self.trash_busy == false, btn).clicked() {
+                        self.tx
+                            .send(WebUiMessage::ActTrashPut(paths.to_vec()))
+                            .unwrap();
+                    }
+                    if ui.button("Cancel").clicked() {
+                        self.tx.send(WebUiMessage::CloseTrashPut).unwrap();
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseTrashPut).unwrap();
+        }
+    }
+
+    /// Submit the rename dialog.
+    pub(super) fn save_rename(&mut self, ctx: &egui::Context) {
+        let rename = match &mut self.rename {
+            Some(v) => v,
+            None => return,
+        };
+        rename.busy = true;
+        rename.error = None;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::RenameRequest {
+                path: rename.path.clone(),
+                name: rename.name.clone(),
+            },
+            move |rsp: Result<crate::protocol::RenameResponse, String>| {
+                tx.send(WebUiMessage::FinishRename(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Compute checksums of the file in properties dialog.
+    pub(super) fn checksum(&mut self, ctx: &egui::Context) {
+        let properties = match &mut self.properties {
+            Some(v) => v,
+            None => return,
+        };
+
+        let id = chrono::Utc::now().timestamp_millis().to_string();
+        properties.checksum_id = Some(id.clone());
+        properties.checksum = None;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::ChecksumRequest {
+                id: Some(id.clone()),
+                path: properties.item.f_path.clone(),
+            },
+            move |rsp: Result<crate::protocol::ChecksumResponse, String>| {
+                tx.send(WebUiMessage::SetChecksum((id, rsp))).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Cancel the in-flight checksum request, if any.
+    pub(super) fn cancel_checksum(&mut self) {
+        let properties = match &mut self.properties {
+            Some(v) => v,
+            None => return,
+        };
+
+        let id = match properties.checksum_id.take() {
+            Some(v) => v,
+            None => return,
+        };
+
+        self.client.post(
+            crate::protocol::ChecksumCancelRequest { id: id },
+            |_rsp: Result<crate::protocol::ChecksumCancelResponse, String>| {},
+        );
+    }
+
+    /// Verify ROMs in a directory.
+    ///
+    /// # Arguments
+    /// + `path`: the ROM directory.
+    pub(super) fn romcheck(&mut self, ctx: &egui::Context, path: String) {
+        self.romcheck = Some(RomCheck {
+            path: path.clone(),
+            report: None,
+            filter: None,
+        });
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::RomCheckRequest { path: path },
+            move |rsp: Result<crate::protocol::RomCheckResponse, String>| {
+                tx.send(WebUiMessage::SetRomCheck(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Rename all fixable ROMs in the report, then verify again.
+    pub(super) fn romfix(&self, ctx: &egui::Context) {
+        let (path, report) = match &self.romcheck {
+            Some(RomCheck {
+                path,
+                report: Some(Ok(v)),
+                ..
+            }) => (path.clone(), v),
+            _ => return,
+        };
+
+        let mut renames = Vec::new();
+        for entry in report.entries.iter() {
+            let name = match &entry.fix_name {
+                Some(v) => v.clone(),
+                None => continue,
+            };
+            // Several entries of one archive share the same fix.
+            if renames
+                .iter()
+                .any(|v: &crate::protocol::RomRename| v.path == entry.path)
+            {
+                continue;
+            }
+            renames.push(crate::protocol::RomRename {
+                path: entry.path.clone(),
+                name: name,
+            });
+        }
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::RomFixRequest { renames: renames },
+            move |rsp: Result<crate::protocol::RomFixResponse, String>| {
+                let status = match rsp {
+                    Ok(v) if v.errors.is_empty() => format!("Renamed {} ROMs", v.renamed),
+                    Ok(v) => format!(
+                        "Renamed {} ROMs, {} failed: {}",
+                        v.renamed,
+                        v.errors.len(),
+                        v.errors.join("; ")
+                    ),
+                    Err(e) => format!("Rename failed: {}", e),
+                };
+
+                tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                tx.send(WebUiMessage::ActRomCheck(path)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Request disk usage of the shown directory.
+    ///
+    /// # Arguments
+    /// + `refresh`: whether to drop the cached result and scan again.
+    pub(super) fn poll_du(&self, ctx: &egui::Context, refresh: bool) {
+        let path = match &self.du {
+            Some(v) => v.path.clone(),
+            None => return,
+        };
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::DuRequest {
+                path: path,
+                cross_fs: false,
+                refresh: refresh,
+            },
+            move |rsp: Result<crate::protocol::DuResponse, String>| {
+                tx.send(WebUiMessage::SetDu(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+}
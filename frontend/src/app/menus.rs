@@ -0,0 +1,212 @@
+use super::{ContextMenu, WebUI, WebUiMessage, MENU_ITEMS_ID};
+
+impl WebUI {
+    pub(super) fn view_context_menu(&self, ctx: &egui::Context, menu: &ContextMenu) {
+        let rsp = egui::Area::new("file_context_menu")
+            .order(egui::Order::Foreground)
+            .fixed_pos(menu.pos)
+            .constrain(true)
+            .show(ctx, |ui| {
+                egui::Frame::menu(ui.style()).show(ui, |ui| {
+                    ui.with_layout(egui::Layout::top_down_justified(egui::Align::LEFT), |ui| {
+                        match &menu.item {
+                            Some(item) => self.view_file_menu(ctx, ui, item),
+                            None => self.view_empty_space_menu(ctx, ui),
+                        }
+                    });
+                });
+            });
+
+        // Close the menu on a press outside, or after an entry is clicked or
+        // activated.
+        let rect = rsp.response.rect;
+        let (pressed_outside, escape) = ctx.input(|i| {
+            let outside = i
+                .pointer
+                .interact_pos()
+                .map(|v| rect.contains(v) == false)
+                .unwrap_or(false);
+            (
+                i.pointer.any_pressed() && outside,
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        let clicked = ctx.input(|i| i.pointer.any_click()) && pressed_outside == false;
+        if pressed_outside || escape || clicked || menu.activate {
+            self.tx.send(WebUiMessage::CloseContextMenu).unwrap();
+        }
+    }
+
+    /// Entries of the context menu of a file row.
+    fn view_file_menu(
+        &self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        item: &crate::protocol::ReaddirResponseItem,
+    ) {
+        let is_dir = item.f_type == "DIR";
+        let mut idx = 0;
+
+        // Actions on several files apply to the whole selection.
+        let selected = &self.browser().selected;
+        let paths: Vec<String> = match selected.contains(&item.f_path) {
+            true => selected.iter().cloned().collect(),
+            false => vec![item.f_path.clone()],
+        };
+
+        if is_dir {
+            if self.menu_item(ui, &mut idx, "📂 Open", true) {
+                self.cd(ctx, item.f_path.as_str());
+            }
+            if self.menu_item(ui, &mut idx, "📑 Open in new tab", true) {
+                self.tx
+                    .send(WebUiMessage::ActNewTab(item.f_path.clone()))
+                    .unwrap();
+            }
+            match self.bookmarks.iter().any(|v| v.path == item.f_path) {
+                true => {
+                    if self.menu_item(ui, &mut idx, "✖ Remove bookmark", true) {
+                        self.tx
+                            .send(WebUiMessage::ActRemoveBookmark(item.f_path.clone()))
+                            .unwrap();
+                    }
+                }
+                false => {
+                    if self.menu_item(ui, &mut idx, "🔖 Bookmark", true) {
+                        self.tx
+                            .send(WebUiMessage::ActAddBookmark(item.f_path.clone()))
+                            .unwrap();
+                    }
+                }
+            }
+        } else if self.menu_item(ui, &mut idx, "⬇ Download", true) {
+            self.tx
+                .send(WebUiMessage::ActDownload(item.f_path.clone()))
+                .unwrap();
+        }
+
+        ui.separator();
+
+        if self.menu_item(ui, &mut idx, "✂ Cut", true) {
+            self.tx
+                .send(WebUiMessage::ActClipboard(crate::protocol::PasteMode::Move))
+                .unwrap();
+        }
+        if self.menu_item(ui, &mut idx, "📄 Copy", true) {
+            self.tx
+                .send(WebUiMessage::ActClipboard(crate::protocol::PasteMode::Copy))
+                .unwrap();
+        }
+        if self.menu_item(ui, &mut idx, "📋 Copy path", true) {
+            self.tx
+                .send(WebUiMessage::ActCopyPath(item.f_path.clone()))
+                .unwrap();
+        }
+        if self.menu_item(ui, &mut idx, "✏ Rename", true) {
+            self.tx
+                .send(WebUiMessage::ActRename(item.f_path.clone()))
+                .unwrap();
+        }
+
+        let text = match paths.len() {
+            1 => "🗑 Move to trash".to_string(),
+            n => format!("🗑 Move {} files to trash", n),
+        };
+        if self.menu_item(ui, &mut idx, text, self.trash_busy == false) {
+            self.tx.send(WebUiMessage::AskTrashPut(paths)).unwrap();
+        }
+
+        // Open with the tools of this app, depending on the file kind.
+        if is_dir {
+            ui.separator();
+            if self.menu_item(ui, &mut idx, "📊 Disk usage", true) {
+                self.tx
+                    .send(WebUiMessage::ActDu((item.f_path.clone(), false)))
+                    .unwrap();
+            }
+            if self.menu_item(ui, &mut idx, "🎮 Verify ROMs", true) {
+                self.tx
+                    .send(WebUiMessage::ActRomCheck(item.f_path.clone()))
+                    .unwrap();
+            }
+            if self.menu_item(ui, &mut idx, "💾 Back up", true) {
+                self.tx
+                    .send(WebUiMessage::ActBackup(item.f_path.clone()))
+                    .unwrap();
+            }
+        } else if item.f_executable {
+            ui.separator();
+            if self.menu_item(ui, &mut idx, "🚂 Add to Steam", true) {
+                self.tx
+                    .send(WebUiMessage::ActAddShortcut(item.f_path.clone()))
+                    .unwrap();
+            }
+        }
+
+        ui.separator();
+
+        if self.menu_item(ui, &mut idx, "ℹ Properties", true) {
+            self.tx
+                .send(WebUiMessage::ActProperties(item.clone()))
+                .unwrap();
+        }
+    }
+
+    /// Entries of the context menu of the empty space below the file rows.
+    fn view_empty_space_menu(&self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        let mut idx = 0;
+
+        if self.menu_item(ui, &mut idx, "📋 Paste", self.clipboard.is_some()) {
+            self.tx.send(WebUiMessage::ActPaste).unwrap();
+        }
+        if self.menu_item(ui, &mut idx, "☑ Select all", true) {
+            self.tx.send(WebUiMessage::ActSelectAll).unwrap();
+        }
+        if self.menu_item(ui, &mut idx, "🔃 Refresh", true) {
+            self.refresh(ctx);
+        }
+
+        if let Some(cwd) = &self.browser().cwd {
+            ui.separator();
+            if self.menu_item(ui, &mut idx, "📋 Copy path", true) {
+                self.tx
+                    .send(WebUiMessage::ActCopyPath(cwd.clone()))
+                    .unwrap();
+            }
+            if self.menu_item(ui, &mut idx, "📊 Disk usage", true) {
+                self.tx
+                    .send(WebUiMessage::ActDu((cwd.clone(), false)))
+                    .unwrap();
+            }
+        }
+    }
+
+    /// An entry of the context menu, which the gamepad and keyboard cursor
+    /// highlights and activates.
+    ///
+    /// # Arguments
+    /// + `idx`: the index of the entry, incremented for the next entry.
+    /// + `enabled`: whether the entry can be clicked.
+    fn menu_item(
+        &self,
+        ui: &mut egui::Ui,
+        idx: &mut usize,
+        text: impl Into<egui::WidgetText>,
+        enabled: bool,
+    ) -> bool {
+        let menu = match &self.context_menu {
+            Some(v) => v,
+            None => return false,
+        };
+        let focused = self.show_cursor && menu.cursor == *idx;
+
+        *idx += 1;
+        let count = *idx;
+        ui.ctx()
+            .data_mut(|d| d.insert_temp(egui::Id::new(MENU_ITEMS_ID), count));
+
+        let btn = egui::Button::new(text).selected(focused);
+        let rsp = ui.add_enabled(enabled, btn);
+        return rsp.clicked() || (focused && enabled && menu.activate);
+    }
+}
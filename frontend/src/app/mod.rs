@@ -0,0 +1,2301 @@
+mod backups;
+mod dialogs;
+mod menus;
+mod panes;
+mod settings;
+mod sidebar;
+mod steam;
+mod toolbar;
+mod trash;
+
+#[derive(Debug, Clone)]
+pub struct WebUiConfig {
+    pub host: String,
+}
+
+/// The id of the location text field of the path bar.
+const LOCATION_ID: &str = "location";
+
+/// The id of the scroll offset of the file table.
+const SCROLL_OFFSET_ID: &str = "file_table_scroll_offset";
+
+/// The id of a scroll offset the file table restores once.
+const RESTORE_SCROLL_ID: &str = "file_table_restore_scroll";
+
+/// The maximum number of directories kept in the navigation history.
+const MAX_HISTORY: usize = 100;
+
+/// The id of the number of entries of the context menu.
+const MENU_ITEMS_ID: &str = "context_menu_items";
+
+/// The storage key of the user settings.
+const SETTINGS_KEY: &str = "settings";
+
+/// The storage key of the tabs of each pane.
+const TABS_KEY: &str = "tabs";
+
+/// Images larger than this show an icon instead of a thumbnail.
+const MAX_THUMBNAIL_BYTES: u64 = 4 * 1024 * 1024;
+
+/// Extensions of ROMs and disc images of emulated systems.
+const ROM_EXTENSIONS: &[&str] = &[
+    "nes", "fds", "sfc", "smc", "gb", "gbc", "gba", "nds", "3ds", "cia", "n64", "z64", "v64",
+    "gcm", "rvz", "wbfs", "wad", "nsp", "xci", "md", "gen", "smd", "sms", "gg", "32x", "pce",
+    "ngp", "ngc", "ws", "wsc", "a26", "a78", "lnx", "iso", "cue", "chd", "cso", "pbp", "gdi",
+    "cdi",
+];
+
+/// Extensions of archives.
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "7z", "rar", "tar", "gz", "tgz", "xz", "bz2", "zst"];
+
+/// XDG places listed in the side panel, with their labels.
+const PLACES: [(crate::protocol::DirsRequestKind, &str); 7] = [
+    (crate::protocol::DirsRequestKind::HomeDir, "🏠 Home"),
+    (crate::protocol::DirsRequestKind::DesktopDir, "🖥 Desktop"),
+    (
+        crate::protocol::DirsRequestKind::DocumentDir,
+        "📄 Documents",
+    ),
+    (
+        crate::protocol::DirsRequestKind::DownloadDir,
+        "📥 Downloads",
+    ),
+    (crate::protocol::DirsRequestKind::AudioDir, "🎵 Music"),
+    (crate::protocol::DirsRequestKind::PictureDir, "🖼 Pictures"),
+    (crate::protocol::DirsRequestKind::VideoDir, "🎞 Videos"),
+];
+
+/// How often gamepads are polled while connected.
+const GAMEPAD_POLL_MILLIS: u64 = 50;
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+enum WebUiMessage {
+    /// Set font.
+    ActFont((String, Vec<u8>)),
+    /// Set the home directory and refresh.
+    ActCWD(String),
+    /// Set the home directory.
+    SetHomeDir(String),
+    /// Set current working directory of a browser, by its id.
+    SetCWD((usize, String)),
+    /// Set file list of a browser, by its id.
+    SetFileList((usize, crate::protocol::ReaddirResponse)),
+    /// Open a directory in a new tab of the active pane.
+    ActNewTab(String),
+    /// Show a tab of the active pane, by its index.
+    ActSelectTab(usize),
+    /// Close a tab of the active pane, by its index.
+    ActCloseTab(usize),
+    /// Show or hide the settings panel.
+    ShowSettings(bool),
+    /// Change the user settings.
+    SetSettings(Settings),
+    /// Wait for the next key or button to bind to an action, or stop waiting
+    /// with `None`.
+    ActRebind(Option<crate::input::Action>),
+    /// Bind a key or button to an action.
+    SetBinding((crate::input::Input, crate::input::Action)),
+    /// Show or hide the second pane.
+    ActSplit(bool),
+    /// Show files as a list or a grid.
+    SetViewMode(ViewMode),
+    /// Show thumbnails of images in the grid.
+    SetShowThumbnails(bool),
+    /// Fetch the thumbnail of an image in the grid.
+    ActFileThumbnail(String),
+    /// Set the thumbnail of an image in the grid, by its path.
+    SetFileThumbnail((String, Result<Vec<u8>, String>)),
+    /// Make a pane the target of file actions, by its index.
+    ActFocusPane(usize),
+    /// Copy or move the selection into the directory of the other pane.
+    ActPasteToPane(crate::protocol::PasteMode),
+    /// Move the gamepad and keyboard cursor, or act on its entry.
+    ActNavigate(crate::input::Action),
+    /// Go back in the navigation history.
+    ActBack,
+    /// Go forward in the navigation history.
+    ActForward,
+    /// Go to the parent directory.
+    ActUp,
+    /// Switch the path bar between breadcrumbs and an editable location.
+    ShowLocation(bool),
+    /// Edit the location, and complete it.
+    SetLocation(String),
+    /// Set completions of a location.
+    SetCompletions((String, Result<crate::protocol::CompleteResponse, String>)),
+    /// Fetch subdirectories of a directory for the path bar.
+    ActSubdirs(String),
+    /// Set subdirectories of a directory.
+    SetSubdirs((String, Result<crate::protocol::CompleteResponse, String>)),
+    /// Select files of current working directory on a click.
+    ActSelect((String, Selection)),
+    /// Select all files of current working directory.
+    ActSelectAll,
+    /// Invert the selection of current working directory.
+    ActSelectInvert,
+    /// Clear the selection.
+    ActSelectNone,
+    /// Open the context menu of a file, or of the empty space, at a position.
+    ShowContextMenu((Option<crate::protocol::ReaddirResponseItem>, egui::Pos2)),
+    /// Close the context menu.
+    CloseContextMenu,
+    /// Download a file.
+    ActDownload(String),
+    /// Copy a path to the system clipboard.
+    ActCopyPath(String),
+    /// Open the rename dialog of a file.
+    ActRename(String),
+    /// Edit the new name in the rename dialog.
+    SetRenameName(String),
+    /// Submit the rename dialog.
+    ActSaveRename,
+    /// Set the result of the rename dialog.
+    FinishRename(Result<crate::protocol::RenameResponse, String>),
+    /// Close the rename dialog.
+    CloseRename,
+    /// Put the selected files into the clipboard, to copy or move them.
+    ActClipboard(crate::protocol::PasteMode),
+    /// Paste the clipboard into current working directory.
+    ActPaste,
+    /// Ask how to paste, with the request and the conflicting names.
+    SetPasteConflict((crate::protocol::PasteRequest, Vec<String>)),
+    /// Paste again with a conflict policy.
+    ActPasteWith(crate::protocol::ConflictPolicy),
+    /// Cancel the paste with conflicts.
+    ClosePasteConflict,
+    /// A paste is finished, with its request.
+    FinishPaste(
+        (
+            crate::protocol::PasteRequest,
+            Result<crate::protocol::PasteResponse, String>,
+        ),
+    ),
+    /// Upload files into current working directory.
+    ActUpload(Vec<crate::http_client::UploadFile>),
+    /// Set status message.
+    SetStatus(String),
+    /// Set progress of the in-flight upload.
+    SetUploadProgress(crate::protocol::UploadProgressResponse),
+    /// The in-flight upload is finished.
+    FinishUpload,
+    /// Open properties dialog of a file.
+    ActProperties(crate::protocol::ReaddirResponseItem),
+    /// Close properties dialog.
+    CloseProperties,
+    /// Compute checksums of the file in properties dialog.
+    ActChecksum,
+    /// Cancel computing checksums.
+    ActCancelChecksum,
+    /// Set checksums of the file in properties dialog, with the request id.
+    SetChecksum((String, Result<crate::protocol::ChecksumResponse, String>)),
+    /// Verify ROMs in a directory.
+    ActRomCheck(String),
+    /// Set ROM verification report.
+    SetRomCheck(Result<crate::protocol::RomCheckResponse, String>),
+    /// Filter ROM verification report by status.
+    SetRomCheckFilter(Option<crate::protocol::RomCheckStatus>),
+    /// Rename all fixable ROMs to their DAT names.
+    ActRomFix,
+    /// Close ROM verification report.
+    CloseRomCheck,
+    /// Show disk usage of a directory, with whether to scan again.
+    ActDu((String, bool)),
+    /// Set disk usage of the shown directory.
+    SetDu(Result<crate::protocol::DuResponse, String>),
+    /// Show apparent sizes instead of sizes on disk.
+    SetDuApparent(bool),
+    /// Close disk usage view.
+    CloseDu,
+    /// Set the path of an XDG place by its index in [PLACES], `None` if
+    /// the place is not configured.
+    SetPlace((usize, Option<String>)),
+    /// Fetch bookmarks.
+    ActBookmarks,
+    /// Set bookmarks.
+    SetBookmarks(Vec<crate::protocol::Bookmark>),
+    /// Bookmark a folder.
+    ActAddBookmark(String),
+    /// Remove the bookmark of a folder.
+    ActRemoveBookmark(String),
+    /// Move the bookmark of a folder to another position.
+    ActMoveBookmark((String, usize)),
+    /// Fetch mounted volumes.
+    ActVolumes,
+    /// Set mounted volumes.
+    SetVolumes(Vec<crate::protocol::Volume>),
+    /// Fetch installed Steam apps.
+    ActSteamApps,
+    /// Set installed Steam apps.
+    SetSteamApps(Result<crate::protocol::SteamAppsResponse, String>),
+    /// Show or hide Steam games view.
+    ShowSteamApps(bool),
+    /// Filter Steam games view by name.
+    SetSteamAppsFilter(String),
+    /// Set non-Steam games.
+    SetSteamShortcuts(Result<crate::protocol::SteamShortcutsResponse, String>),
+    /// Open the "Add to Steam" dialog for an executable.
+    ActAddShortcut(String),
+    /// Edit the "Add to Steam" dialog.
+    SetShortcutForm(crate::protocol::SteamShortcutAddRequest),
+    /// Submit the "Add to Steam" dialog.
+    ActSaveShortcut,
+    /// Set the result of the "Add to Steam" dialog.
+    FinishAddShortcut(Result<crate::protocol::SteamShortcutAddResponse, String>),
+    /// Close the "Add to Steam" dialog.
+    CloseAddShortcut,
+    /// Remove a non-Steam game, by user and app id.
+    ActRemoveShortcut((String, u32)),
+    /// Ask for confirmation before moving files to the trash.
+    AskTrashPut(Vec<String>),
+    /// Cancel moving files to the trash.
+    CloseTrashPut,
+    /// Move files to the trash.
+    ActTrashPut(Vec<String>),
+    /// Open trash view and list trashed files.
+    ActTrash,
+    /// Set trashed files of the trash view.
+    SetTrash(Result<crate::protocol::TrashListResponse, String>),
+    /// Set sizes of trashed directories, by requested paths.
+    SetTrashSizes(
+        (
+            Vec<String>,
+            Result<crate::protocol::TrashSizeResponse, String>,
+        ),
+    ),
+    /// Select or unselect a trashed file.
+    SetTrashSelected((String, bool)),
+    /// Restore the selected trashed files.
+    ActTrashRestore,
+    /// Delete the selected trashed files permanently.
+    ActTrashDelete,
+    /// Ask for confirmation before emptying the trash.
+    SetTrashConfirmEmpty(bool),
+    /// Empty the trash.
+    ActTrashEmpty,
+    /// A trash operation is finished, with the status message.
+    FinishTrash(Result<String, String>),
+    /// Close trash view.
+    CloseTrash,
+    /// Open cleanup wizard and find orphaned folders.
+    ActCleanup,
+    /// Set orphaned folders of the cleanup wizard.
+    SetCleanup(Result<crate::protocol::SteamCleanupResponse, String>),
+    /// Select or unselect an orphaned folder.
+    SetCleanupSelected((String, bool)),
+    /// Replace the selected orphaned folders.
+    SetCleanupSelection(Vec<String>),
+    /// Remove the selected orphaned folders.
+    ActCleanupRemove,
+    /// Set the result of removing orphaned folders.
+    FinishCleanupRemove(Result<crate::protocol::SteamCleanupRemoveResponse, String>),
+    /// Close cleanup wizard.
+    CloseCleanup,
+    /// Open screenshots gallery.
+    ActScreenshots,
+    /// Set screenshots of the gallery.
+    SetScreenshots(Result<crate::protocol::SteamScreenshotsResponse, String>),
+    /// Set a thumbnail, by the path of the image.
+    SetThumbnail((String, Result<Vec<u8>, String>)),
+    /// Select or unselect a screenshot.
+    SetScreenshotSelected((String, bool)),
+    /// Replace the selected screenshots.
+    SetScreenshotSelection(Vec<String>),
+    /// Download the selected screenshots as zip.
+    ActDownloadScreenshots,
+    /// Set the downloaded zip of screenshots.
+    FinishDownloadScreenshots(Result<Vec<u8>, String>),
+    /// Close screenshots gallery.
+    CloseScreenshots,
+    /// Open Proton prefixes view.
+    ActPrefixes,
+    /// Set Proton prefixes.
+    SetPrefixes(Result<crate::protocol::SteamPrefixesResponse, String>),
+    /// Show locations inside the Proton prefix of an app id.
+    ActPrefix(u32),
+    /// Set locations inside the selected Proton prefix.
+    SetPrefix(Result<crate::protocol::SteamPrefixResponse, String>),
+    /// Close Proton prefixes view.
+    ClosePrefixes,
+    /// Open backups view and fetch all snapshots.
+    ActBackups,
+    /// Set snapshots of the backups view.
+    SetBackups(Result<crate::protocol::BackupListResponse, String>),
+    /// Snapshot a folder.
+    ActBackup(String),
+    /// Ask for confirmation before restoring a snapshot, or cancel with `None`.
+    SetBackupConfirm(Option<String>),
+    /// Restore a snapshot by id.
+    ActRestoreBackup(String),
+    /// Delete a snapshot by id.
+    ActDeleteBackup(String),
+    /// A backup operation is finished, with the status message.
+    FinishBackup(Result<String, String>),
+    /// Close backups view.
+    CloseBackups,
+}
+
+/// How a click changes the selection of the file table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum Selection {
+    /// Select only the clicked file.
+    Only,
+    /// Toggle the clicked file.
+    Toggle,
+    /// Select only the files from the last clicked file to the clicked file.
+    Range,
+    /// Add the files from the last clicked file to the clicked file.
+    AddRange,
+}
+
+/// A directory of the navigation history.
+#[derive(Debug, Clone)]
+struct Visit {
+    /// The absolute path of the directory.
+    path: String,
+    /// The scroll offset of the file table.
+    scroll: f32,
+    /// The selected files.
+    selected: std::collections::BTreeSet<String>,
+}
+
+/// State of a file browser, shown in a pane.
+struct Browser {
+    /// The unique id, which routes listings to the browser.
+    id: usize,
+    /// The current working directory.
+    cwd: Option<String>,
+    /// The listing of the current working directory.
+    filelist: Option<crate::protocol::ReaddirResponse>,
+    /// Directories to go back to, the last one first.
+    back: Vec<Visit>,
+    /// Directories to go forward to, the last one first.
+    forward: Vec<Visit>,
+    /// The visit being restored by going back or forward.
+    restore: Option<Visit>,
+    /// Paths of the selected files.
+    selected: std::collections::BTreeSet<String>,
+    /// The file a range selection starts from.
+    selection_anchor: Option<String>,
+    /// The row under the gamepad and keyboard cursor.
+    cursor: usize,
+}
+
+impl Browser {
+    fn new(id: usize) -> Self {
+        return Browser {
+            id: id,
+            cwd: None,
+            filelist: None,
+            back: Vec::new(),
+            forward: Vec::new(),
+            restore: None,
+            selected: std::collections::BTreeSet::new(),
+            selection_anchor: None,
+            cursor: 0,
+        };
+    }
+
+    /// The id of egui temp data of this browser.
+    fn temp_id(&self, name: &str) -> egui::Id {
+        return egui::Id::new(name).with(self.id);
+    }
+
+    /// The current working directory as a visit of the navigation history.
+    fn visit(&self, ctx: &egui::Context) -> Option<Visit> {
+        let path = self.cwd.clone()?;
+        let scroll = ctx
+            .data(|d| d.get_temp::<f32>(self.temp_id(SCROLL_OFFSET_ID)))
+            .unwrap_or_default();
+
+        return Some(Visit {
+            path: path,
+            scroll: scroll,
+            selected: self.selected.clone(),
+        });
+    }
+}
+
+/// The tabs of a pane, each with its own browser.
+struct Tabs {
+    /// The browsers of the tabs, in order.
+    browsers: Vec<Browser>,
+    /// The index of the shown tab.
+    current: usize,
+}
+
+impl Tabs {
+    /// Tabs of the directories of the last session, or a single empty tab.
+    ///
+    /// # Arguments
+    /// + `saved`: the tabs of the last session.
+    /// + `next_id`: the id of the next browser, incremented for each tab.
+    fn restore(saved: Option<&SavedTabs>, next_id: &mut usize) -> Self {
+        let mut browsers = Vec::new();
+        for path in saved.map(|v| v.paths.clone()).unwrap_or_default() {
+            let mut browser = Browser::new(*next_id);
+            browser.cwd = Some(path);
+            browsers.push(browser);
+            *next_id += 1;
+        }
+
+        if browsers.is_empty() {
+            browsers.push(Browser::new(*next_id));
+            *next_id += 1;
+        }
+
+        let current = saved.map(|v| v.current).unwrap_or_default();
+        return Tabs {
+            current: current.min(browsers.len() - 1),
+            browsers: browsers,
+        };
+    }
+
+    fn browser(&self) -> &Browser {
+        return &self.browsers[self.current];
+    }
+
+    fn browser_mut(&mut self) -> &mut Browser {
+        return &mut self.browsers[self.current];
+    }
+}
+
+/// The tabs of a pane, saved between sessions.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct SavedTabs {
+    /// The directories of the tabs.
+    paths: Vec<String>,
+    /// The index of the shown tab.
+    current: usize,
+}
+
+/// State of the editable location of the path bar.
+struct Location {
+    /// The path being edited.
+    text: String,
+    /// Directories completing the path.
+    completions: Vec<String>,
+}
+
+/// State of the context menu of the file table.
+struct ContextMenu {
+    /// The file of the clicked row, `None` for the empty space.
+    item: Option<crate::protocol::ReaddirResponseItem>,
+    /// Where the menu opens.
+    pos: egui::Pos2,
+    /// The entry under the gamepad and keyboard cursor.
+    cursor: usize,
+    /// Whether to click the entry under the cursor.
+    activate: bool,
+}
+
+/// User preferences, saved between sessions.
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct Settings {
+    /// Use the dark theme.
+    dark_mode: bool,
+    /// The column files are sorted by.
+    sort_by: SortBy,
+    /// Sort in descending order.
+    sort_descending: bool,
+    /// Show files whose names start with a dot.
+    show_hidden: bool,
+    /// Show files as a list or a grid.
+    view_mode: ViewMode,
+    /// Show thumbnails of images in the grid.
+    show_thumbnails: bool,
+    /// Show two panes side by side.
+    split: bool,
+    /// Show the side panel of places, bookmarks and volumes.
+    show_side_panel: bool,
+    /// Keys and gamepad buttons bound to navigation actions.
+    bindings: crate::input::Bindings,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        return Settings {
+            dark_mode: true,
+            sort_by: SortBy::Name,
+            sort_descending: false,
+            show_hidden: false,
+            view_mode: ViewMode::List,
+            show_thumbnails: true,
+            split: false,
+            show_side_panel: true,
+            bindings: crate::input::Bindings::default(),
+        };
+    }
+}
+
+/// A column files are sorted by. Directories always come first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum SortBy {
+    Name,
+    /// The file extension.
+    Type,
+    Size,
+    Modified,
+}
+
+/// How the files of a directory are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+enum ViewMode {
+    /// A table with a row per file.
+    List,
+    /// A grid of large icons.
+    Grid,
+}
+
+/// A part of the window the gamepad and keyboard cursor moves in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    /// Places, bookmarks, volumes and the trash in the side panel.
+    Places,
+    /// The file table of the active pane.
+    Files,
+}
+
+/// State of the rename dialog.
+struct Rename {
+    /// The absolute path of the file.
+    path: String,
+    /// The new name being edited.
+    name: String,
+    /// Whether the rename is in flight.
+    busy: bool,
+    /// The error of the last attempt.
+    error: Option<String>,
+}
+
+/// Files to paste.
+struct Clipboard {
+    /// The absolute paths of the files.
+    paths: Vec<String>,
+    /// Whether to copy or move the files.
+    mode: crate::protocol::PasteMode,
+}
+
+/// State of the properties dialog.
+struct Properties {
+    /// The file to show.
+    item: crate::protocol::ReaddirResponseItem,
+    /// The id of the in-flight checksum request.
+    checksum_id: Option<String>,
+    /// The result of the last checksum request.
+    checksum: Option<Result<crate::protocol::ChecksumResponse, String>>,
+}
+
+/// State of the ROM verification report.
+struct RomCheck {
+    /// The verified directory.
+    path: String,
+    /// The report, `None` while verifying.
+    report: Option<Result<crate::protocol::RomCheckResponse, String>>,
+    /// Only show entries with this status.
+    filter: Option<crate::protocol::RomCheckStatus>,
+}
+
+/// State of the "Add to Steam" dialog.
+struct AddShortcut {
+    /// The shortcut to add.
+    form: crate::protocol::SteamShortcutAddRequest,
+    /// Whether the request is in flight.
+    busy: bool,
+    /// The error of the last request.
+    error: Option<String>,
+}
+
+/// State of the trash view.
+struct Trash {
+    /// Trashed files, `None` while fetching.
+    items: Option<Result<crate::protocol::TrashListResponse, String>>,
+    /// Paths of the selected trashed files.
+    selected: std::collections::HashSet<String>,
+    /// Sizes of trashed directories, `None` if they could not be scanned.
+    /// Missing while scanning.
+    sizes: std::collections::HashMap<String, Option<u64>>,
+    /// Whether emptying the trash waits for confirmation.
+    confirm_empty: bool,
+}
+
+/// State of the cleanup wizard.
+struct Cleanup {
+    /// Orphaned folders, `None` while scanning.
+    orphans: Option<Result<crate::protocol::SteamCleanupResponse, String>>,
+    /// Paths of the selected folders.
+    selected: std::collections::HashSet<String>,
+    /// Whether the removal is in flight.
+    removing: bool,
+    /// The result of the removal, shown as the last step.
+    result: Option<Result<crate::protocol::SteamCleanupRemoveResponse, String>>,
+}
+
+/// State of the screenshots gallery.
+struct Screenshots {
+    /// All screenshots, `None` while fetching.
+    games: Option<Result<crate::protocol::SteamScreenshotsResponse, String>>,
+    /// Thumbnails by path, `None` while fetching.
+    thumbnails: std::collections::HashMap<String, Option<std::sync::Arc<[u8]>>>,
+    /// Paths of the selected screenshots.
+    selected: std::collections::HashSet<String>,
+    /// Whether the zip download is in flight.
+    downloading: bool,
+}
+
+/// State of the Proton prefixes view.
+struct Prefixes {
+    /// All prefixes, `None` while fetching.
+    prefixes: Option<Result<crate::protocol::SteamPrefixesResponse, String>>,
+    /// The app id of the selected prefix.
+    selected: Option<u32>,
+    /// The locations inside the selected prefix, `None` while fetching.
+    locations: Option<Result<crate::protocol::SteamPrefixResponse, String>>,
+}
+
+/// State of the backups view.
+struct Backups {
+    /// All snapshots, `None` while fetching.
+    snapshots: Option<Result<crate::protocol::BackupListResponse, String>>,
+    /// The id of the snapshot waiting for restore confirmation.
+    confirm: Option<String>,
+}
+
+/// State of the disk usage view.
+struct DiskUsage {
+    /// The shown directory.
+    path: String,
+    /// The last response, `None` until the first one arrives.
+    usage: Option<Result<crate::protocol::DuResponse, String>>,
+    /// Show apparent sizes instead of sizes on disk.
+    apparent: bool,
+    /// The time of the last poll while scanning.
+    polled_at: f64,
+}
+
+pub struct WebUI {
+    inited: bool,
+    homedir: Option<String>,
+    panes: Vec<Tabs>,
+    next_browser_id: usize,
+    active: usize,
+    settings: Settings,
+    settings_open: bool,
+    /// The action waiting for a key or button to bind.
+    rebinding: Option<crate::input::Action>,
+    thumbnails: std::collections::HashMap<String, Option<std::sync::Arc<[u8]>>>,
+    status: Option<String>,
+    gamepad: crate::input::Gamepad,
+    pane: Pane,
+    place_cursor: usize,
+    show_cursor: bool,
+    location: Option<Location>,
+    subdirs: std::collections::HashMap<String, Option<Vec<String>>>,
+    clipboard: Option<Clipboard>,
+    context_menu: Option<ContextMenu>,
+    rename: Option<Rename>,
+    paste_conflict: Option<(crate::protocol::PasteRequest, Vec<String>)>,
+    /// Files waiting for confirmation to be moved to the trash.
+    confirm_trash: Option<Vec<String>>,
+    upload_id: Option<String>,
+    upload_progress: crate::protocol::UploadProgressResponse,
+    upload_polled_at: f64,
+    properties: Option<Properties>,
+    romcheck: Option<RomCheck>,
+    du: Option<DiskUsage>,
+    places: Vec<Option<String>>,
+    bookmarks: Vec<crate::protocol::Bookmark>,
+    volumes: Vec<crate::protocol::Volume>,
+    steam_apps: Option<Result<crate::protocol::SteamAppsResponse, String>>,
+    steam_apps_open: bool,
+    steam_apps_filter: String,
+    steam_shortcuts: Option<Result<crate::protocol::SteamShortcutsResponse, String>>,
+    add_shortcut: Option<AddShortcut>,
+    screenshots: Option<Screenshots>,
+    cleanup: Option<Cleanup>,
+    trash: Option<Trash>,
+    trash_busy: bool,
+    prefixes: Option<Prefixes>,
+    backups: Option<Backups>,
+    backup_busy: bool,
+
+    client: crate::http_client::HttpClient,
+    tx: std::sync::Arc<std::sync::mpsc::Sender<WebUiMessage>>,
+    rx: std::sync::mpsc::Receiver<WebUiMessage>,
+}
+
+impl WebUI {
+    pub fn new(cc: &eframe::CreationContext<'_>, config: WebUiConfig) -> Self {
+        // This is also where you can customize the look and feel of egui using
+        // `cc.egui_ctx.set_visuals` and `cc.egui_ctx.set_fonts`.
+
+        // Decode screenshots and thumbnails.
+        egui_extras::install_image_loaders(&cc.egui_ctx);
+
+        let (tx, rx) = std::sync::mpsc::channel::<WebUiMessage>();
+
+        let client = crate::http_client::new(config.host.as_str());
+
+        // Restore the settings and tabs of the last session.
+        let settings = cc
+            .storage
+            .and_then(|v| eframe::get_value::<Settings>(v, SETTINGS_KEY))
+            .unwrap_or_default();
+        cc.egui_ctx.set_visuals(match settings.dark_mode {
+            true => egui::Visuals::dark(),
+            false => egui::Visuals::light(),
+        });
+        let saved = cc
+            .storage
+            .and_then(|v| eframe::get_value::<Vec<SavedTabs>>(v, TABS_KEY))
+            .unwrap_or_default();
+        let mut next_browser_id = 0;
+        let panes = (0..2)
+            .map(|idx| Tabs::restore(saved.get(idx), &mut next_browser_id))
+            .collect();
+
+        let ui = WebUI {
+            inited: false,
+            homedir: None,
+            panes: panes,
+            next_browser_id: next_browser_id,
+            active: 0,
+            settings: settings,
+            settings_open: false,
+            rebinding: None,
+            thumbnails: std::collections::HashMap::new(),
+            status: None,
+            gamepad: crate::input::Gamepad::default(),
+            pane: Pane::Files,
+            place_cursor: 0,
+            show_cursor: false,
+            location: None,
+            subdirs: std::collections::HashMap::new(),
+            clipboard: None,
+            context_menu: None,
+            rename: None,
+            paste_conflict: None,
+            confirm_trash: None,
+            upload_id: None,
+            upload_progress: Default::default(),
+            upload_polled_at: 0.0,
+            properties: None,
+            romcheck: None,
+            du: None,
+            places: vec![None; PLACES.len()],
+            bookmarks: Vec::new(),
+            volumes: Vec::new(),
+            steam_apps: None,
+            steam_apps_open: false,
+            steam_apps_filter: String::new(),
+            steam_shortcuts: None,
+            add_shortcut: None,
+            screenshots: None,
+            cleanup: None,
+            trash: None,
+            trash_busy: false,
+            prefixes: None,
+            backups: None,
+            backup_busy: false,
+
+            client: client,
+            tx: std::sync::Arc::new(tx),
+            rx: rx,
+        };
+
+        return ui;
+    }
+}
+
+impl eframe::App for WebUI {
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, SETTINGS_KEY, &self.settings);
+
+        let tabs: Vec<SavedTabs> = self
+            .panes
+            .iter()
+            .map(|tabs| SavedTabs {
+                paths: tabs.browsers.iter().filter_map(|v| v.cwd.clone()).collect(),
+                current: tabs.current,
+            })
+            .collect();
+        eframe::set_value(storage, TABS_KEY, &tabs);
+    }
+
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Initialize data.
+        if self.inited == false {
+            self.init(ctx);
+            self.inited = true;
+        }
+
+        // Handle data update.
+        // Due to update is trigger at redraw, you need to call [`egui::Context::request_repaint`] to
+        // ensure the update function is executed in time.
+        while let Ok(msg) = self.rx.try_recv() {
+            self.updated(ctx, msg);
+        }
+
+        // Poll upload progress.
+        if self.upload_id.is_some() {
+            let now = ctx.input(|i| i.time);
+            if now - self.upload_polled_at > 0.5 {
+                self.upload_polled_at = now;
+                self.poll_upload_progress(ctx);
+            }
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
+
+        // Poll disk usage scan.
+        let mut poll_du = false;
+        if let Some(du) = &mut self.du {
+            if let Some(Ok(usage)) = &du.usage {
+                if usage.scanning {
+                    let now = ctx.input(|i| i.time);
+                    if now - du.polled_at > 0.5 {
+                        du.polled_at = now;
+                        poll_du = true;
+                    }
+                    ctx.request_repaint_after(std::time::Duration::from_millis(500));
+                }
+            }
+        }
+        if poll_du {
+            self.poll_du(ctx, false);
+        }
+
+        // Fetch thumbnails of the screenshots gallery.
+        self.fetch_thumbnails(ctx);
+
+        // While rebinding, the next key or button goes to the chosen action,
+        // and no shortcut sees it.
+        if let Some(action) = self.rebinding {
+            let now = ctx.input(|i| i.time);
+            let mut inputs = self.gamepad.poll(now);
+            ctx.request_repaint_after(std::time::Duration::from_millis(GAMEPAD_POLL_MILLIS));
+            inputs.extend(ctx.input(|i| crate::input::key_inputs(&i.events)));
+
+            if let Some(input) = inputs.first() {
+                let msg = match input {
+                    crate::input::Input::Key {
+                        key: egui::Key::Escape,
+                        ..
+                    } => WebUiMessage::ActRebind(None),
+                    _ => WebUiMessage::SetBinding((*input, action)),
+                };
+                self.tx.send(msg).unwrap();
+                ctx.request_repaint();
+            }
+            ctx.input_mut(|i| {
+                i.events
+                    .retain(|e| matches!(e, egui::Event::Key { .. }) == false)
+            });
+        }
+
+        // Clipboard shortcuts, unless a text field has focus. Browsers send
+        // clipboard events instead of key presses.
+        if ctx.memory(|m| m.focus().is_none()) {
+            let (copy, cut, paste) = ctx.input(|i| {
+                let command = i.modifiers.command;
+                let event = |f: fn(&egui::Event) -> bool| i.events.iter().any(f);
+                (
+                    event(|e| matches!(e, egui::Event::Copy))
+                        || (command && i.key_pressed(egui::Key::C)),
+                    event(|e| matches!(e, egui::Event::Cut))
+                        || (command && i.key_pressed(egui::Key::X)),
+                    event(|e| matches!(e, egui::Event::Paste(_)))
+                        || (command && i.key_pressed(egui::Key::V)),
+                )
+            });
+            if copy {
+                self.tx
+                    .send(WebUiMessage::ActClipboard(crate::protocol::PasteMode::Copy))
+                    .unwrap();
+            }
+            if cut {
+                self.tx
+                    .send(WebUiMessage::ActClipboard(crate::protocol::PasteMode::Move))
+                    .unwrap();
+            }
+            if paste {
+                self.tx.send(WebUiMessage::ActPaste).unwrap();
+            }
+            if copy || cut || paste {
+                ctx.request_repaint();
+            }
+        }
+
+        // Gamepad and keyboard navigation, unless a text field has focus.
+        let now = ctx.input(|i| i.time);
+        let mut inputs = self.gamepad.poll(now);
+        if self.gamepad.is_connected() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(GAMEPAD_POLL_MILLIS));
+        }
+        if ctx.memory(|m| m.focus().is_none()) {
+            inputs.extend(ctx.input(|i| crate::input::key_inputs(&i.events)));
+            for action in self.settings.bindings.actions(&inputs) {
+                self.tx.send(WebUiMessage::ActNavigate(action)).unwrap();
+                ctx.request_repaint();
+            }
+        }
+
+        // Mouse back and forward buttons.
+        let (back, forward) = ctx.input(|i| {
+            (
+                i.pointer.button_pressed(egui::PointerButton::Extra1),
+                i.pointer.button_pressed(egui::PointerButton::Extra2),
+            )
+        });
+
+        // Navigation shortcuts, unless a text field has focus.
+        let (back, forward, up) = match ctx.memory(|m| m.focus().is_none()) {
+            true => ctx.input(|i| {
+                let alt = i.modifiers.alt;
+                (
+                    back || (alt && i.key_pressed(egui::Key::ArrowLeft)),
+                    forward || (alt && i.key_pressed(egui::Key::ArrowRight)),
+                    alt && i.key_pressed(egui::Key::ArrowUp),
+                )
+            }),
+            false => (back, forward, false),
+        };
+        if back {
+            self.tx.send(WebUiMessage::ActBack).unwrap();
+        }
+        if forward {
+            self.tx.send(WebUiMessage::ActForward).unwrap();
+        }
+        if up {
+            self.tx.send(WebUiMessage::ActUp).unwrap();
+        }
+        if back || forward || up {
+            ctx.request_repaint();
+        }
+
+        // Selection shortcuts, unless a text field has focus.
+        if ctx.memory(|m| m.focus().is_none()) {
+            let (all, invert, none, delete) = ctx.input(|i| {
+                let command = i.modifiers.command;
+                (
+                    command && i.key_pressed(egui::Key::A),
+                    command && i.key_pressed(egui::Key::I),
+                    i.key_pressed(egui::Key::Escape),
+                    i.key_pressed(egui::Key::Delete),
+                )
+            });
+            if all {
+                self.tx.send(WebUiMessage::ActSelectAll).unwrap();
+            }
+            if invert {
+                self.tx.send(WebUiMessage::ActSelectInvert).unwrap();
+            }
+            let selected = &self.browser().selected;
+            if none && selected.is_empty() == false && self.context_menu.is_none() {
+                self.tx.send(WebUiMessage::ActSelectNone).unwrap();
+            }
+            if delete && selected.is_empty() == false && self.trash_busy == false {
+                let paths = selected.iter().cloned().collect();
+                self.tx.send(WebUiMessage::AskTrashPut(paths)).unwrap();
+            }
+            if all || invert || none || delete {
+                ctx.request_repaint();
+            }
+        }
+
+        // Tab shortcuts, unless a text field has focus.
+        if ctx.memory(|m| m.focus().is_none()) {
+            let (new_tab, close_tab) = ctx.input(|i| {
+                let command = i.modifiers.command;
+                (
+                    command && i.key_pressed(egui::Key::T),
+                    command && i.key_pressed(egui::Key::W),
+                )
+            });
+            if let (true, Some(cwd)) = (new_tab, &self.browser().cwd) {
+                self.tx.send(WebUiMessage::ActNewTab(cwd.clone())).unwrap();
+            }
+            if close_tab {
+                let current = self.panes[self.active].current;
+                self.tx.send(WebUiMessage::ActCloseTab(current)).unwrap();
+            }
+            if new_tab || close_tab {
+                ctx.request_repaint();
+            }
+        }
+
+        // Copy and move between panes, unless a text field has focus.
+        if self.settings.split && ctx.memory(|m| m.focus().is_none()) {
+            let (copy, to_move) =
+                ctx.input(|i| (i.key_pressed(egui::Key::F5), i.key_pressed(egui::Key::F6)));
+            if copy {
+                self.tx
+                    .send(WebUiMessage::ActPasteToPane(
+                        crate::protocol::PasteMode::Copy,
+                    ))
+                    .unwrap();
+            }
+            if to_move {
+                self.tx
+                    .send(WebUiMessage::ActPasteToPane(
+                        crate::protocol::PasteMode::Move,
+                    ))
+                    .unwrap();
+            }
+            if copy || to_move {
+                ctx.request_repaint();
+            }
+        }
+
+        // Update view.
+        self.view(ctx);
+    }
+}
+
+impl WebUI {
+    fn init(&self, ctx: &egui::Context) {
+        // Fetch font.
+        {
+            let font_name = "LXGWWenKai-Regular.ttf";
+            let font_name_path = format!("/assets/{}", font_name);
+
+            let ctx = ctx.clone();
+            let tx = self.tx.clone();
+            self.client.get(font_name_path.as_str(), move |data| {
+                let data = data.unwrap();
+                tx.send(WebUiMessage::ActFont((font_name.to_string(), data)))
+                    .unwrap();
+                ctx.request_repaint();
+            });
+        }
+
+        // Reopen the tabs of the last session.
+        let restored = self.browser().cwd.is_some();
+        self.refresh(ctx);
+
+        // Fetch homedir
+        {
+            let tx = self.tx.clone();
+            let ctx = ctx.clone();
+            self.client.post(
+                crate::protocol::DirsRequest {
+                    kind: crate::protocol::DirsRequestKind::HomeDir,
+                },
+                move |rsp: Result<crate::protocol::DirsResponse, String>| {
+                    let rsp = rsp.unwrap();
+                    let homedir = rsp.path.unwrap();
+
+                    tx.send(WebUiMessage::SetHomeDir(homedir.clone())).unwrap();
+                    if restored == false {
+                        tx.send(WebUiMessage::ActCWD(homedir.clone())).unwrap();
+                    }
+                    ctx.request_repaint();
+                },
+            );
+        }
+
+        // Fetch places, bookmarks and volumes.
+        self.fetch_places(ctx);
+        self.fetch_bookmarks(ctx);
+        self.fetch_volumes(ctx);
+
+        // Fetch Steam apps.
+        self.fetch_steam_apps(ctx);
+    }
+
+    fn updated(&mut self, ctx: &egui::Context, msg: WebUiMessage) {
+        match msg {
+            WebUiMessage::ActFont((name, data)) => {
+                self.install_font(ctx, name, data);
+            }
+            WebUiMessage::ActCWD(path) => {
+                self.cd(ctx, path.as_str());
+            }
+            WebUiMessage::SetHomeDir(path) => {
+                self.homedir = Some(path);
+            }
+            WebUiMessage::SetCWD((id, path)) => {
+                let browser = match self.browser_by_id(id) {
+                    Some(v) => v,
+                    None => return,
+                };
+
+                // Going back or forward moves the history by itself.
+                let restoring = browser.restore.as_ref().map(|v| v.path == path) == Some(true);
+                if restoring == false {
+                    browser.restore = None;
+                    if let Some(visit) = browser.visit(ctx) {
+                        if visit.path != path {
+                            browser.back.push(visit);
+                            if browser.back.len() > MAX_HISTORY {
+                                browser.back.remove(0);
+                            }
+                            browser.forward.clear();
+                        }
+                    }
+                }
+                if browser.cwd.as_ref() != Some(&path) {
+                    browser.cursor = 0;
+                }
+                browser.cwd = Some(path.clone());
+
+                // The second pane starts in the first directory opened.
+                let empty: Vec<usize> = self
+                    .panes
+                    .iter()
+                    .flat_map(|v| v.browsers.iter())
+                    .filter(|v| v.cwd.is_none())
+                    .map(|v| v.id)
+                    .collect();
+                for id in empty {
+                    self.cd_in(ctx, id, path.as_str());
+                }
+            }
+            WebUiMessage::ActNewTab(path) => {
+                let id = self.next_browser_id;
+                self.next_browser_id += 1;
+
+                // Known before listing, so the new tab has no history.
+                let mut browser = Browser::new(id);
+                browser.cwd = Some(path.clone());
+
+                let tabs = &mut self.panes[self.active];
+                tabs.current = (tabs.current + 1).min(tabs.browsers.len());
+                tabs.browsers.insert(tabs.current, browser);
+                self.cd_in(ctx, id, path.as_str());
+            }
+            WebUiMessage::ActSelectTab(idx) => {
+                let tabs = &mut self.panes[self.active];
+                if idx < tabs.browsers.len() {
+                    tabs.current = idx;
+                    self.location = None;
+                }
+            }
+            WebUiMessage::ActCloseTab(idx) => {
+                // The last tab of a pane stays.
+                let tabs = &mut self.panes[self.active];
+                if idx < tabs.browsers.len() && tabs.browsers.len() > 1 {
+                    tabs.browsers.remove(idx);
+                    if tabs.current > idx || tabs.current >= tabs.browsers.len() {
+                        tabs.current -= 1;
+                    }
+                    self.location = None;
+                }
+            }
+            WebUiMessage::SetViewMode(mode) => {
+                self.settings.view_mode = mode;
+            }
+            WebUiMessage::SetShowThumbnails(show) => {
+                self.settings.show_thumbnails = show;
+            }
+            WebUiMessage::ActFileThumbnail(path) => {
+                const MAX_IN_FLIGHT: usize = 8;
+
+                // Over the limit the cell asks again once a fetch finishes.
+                let in_flight = self.thumbnails.values().filter(|v| v.is_none()).count();
+                if in_flight < MAX_IN_FLIGHT && self.thumbnails.contains_key(&path) == false {
+                    self.thumbnails.insert(path.clone(), None);
+                    self.fetch_file_thumbnail(ctx, path);
+                }
+            }
+            WebUiMessage::SetFileThumbnail((path, rsp)) => {
+                match rsp {
+                    Ok(v) => {
+                        self.thumbnails.insert(path, Some(v.into()));
+                    }
+                    Err(e) => {
+                        // Keep the entry, so it is not fetched again.
+                        log::warn!("fetch thumbnail {} failed: {}", path, e);
+                        self.thumbnails.insert(path, Some(Vec::new().into()));
+                    }
+                }
+                // Handle the requests of the cells still waiting.
+                ctx.request_repaint();
+            }
+            WebUiMessage::ShowSettings(open) => {
+                self.settings_open = open;
+                self.rebinding = None;
+            }
+            WebUiMessage::SetSettings(settings) => {
+                let old = std::mem::replace(&mut self.settings, settings);
+                self.apply_settings(ctx, &old);
+            }
+            WebUiMessage::ActRebind(action) => {
+                self.rebinding = action;
+            }
+            WebUiMessage::SetBinding((input, action)) => {
+                self.rebinding = None;
+                self.settings.bindings.bind(input, action);
+            }
+            WebUiMessage::ActSplit(split) => {
+                self.settings.split = split;
+                if split == false {
+                    self.active = 0;
+                }
+            }
+            WebUiMessage::ActFocusPane(idx) => {
+                if idx < self.panes.len() {
+                    self.active = idx;
+                    self.pane = Pane::Files;
+                }
+            }
+            WebUiMessage::ActPasteToPane(mode) => {
+                let dest = self.panes[1 - self.active].browser().cwd.clone();
+                let sources: Vec<String> = self.browser().selected.iter().cloned().collect();
+                if let (true, Some(dest)) =
+                    (self.settings.split && sources.is_empty() == false, dest)
+                {
+                    let req = crate::protocol::PasteRequest {
+                        sources: sources,
+                        dest: dest,
+                        mode: mode,
+                        conflict: crate::protocol::ConflictPolicy::Ask,
+                    };
+                    self.paste(ctx, req);
+                }
+            }
+            WebUiMessage::ActNavigate(action) => {
+                self.navigate(ctx, action);
+            }
+            WebUiMessage::ActBack => {
+                let browser = self.browser_mut();
+                if let Some(visit) = browser.back.pop() {
+                    if let Some(current) = browser.visit(ctx) {
+                        browser.forward.push(current);
+                    }
+                    browser.restore = Some(visit.clone());
+                    self.cd(ctx, visit.path.as_str());
+                }
+            }
+            WebUiMessage::ActForward => {
+                let browser = self.browser_mut();
+                if let Some(visit) = browser.forward.pop() {
+                    if let Some(current) = browser.visit(ctx) {
+                        browser.back.push(current);
+                    }
+                    browser.restore = Some(visit.clone());
+                    self.cd(ctx, visit.path.as_str());
+                }
+            }
+            WebUiMessage::ActUp => {
+                let parent = self
+                    .browser()
+                    .cwd
+                    .as_ref()
+                    .and_then(|v| std::path::Path::new(v).parent())
+                    .map(|v| v.to_string_lossy().to_string());
+                if let Some(parent) = parent {
+                    self.cd(ctx, parent.as_str());
+                }
+            }
+            WebUiMessage::ShowLocation(show) => {
+                if show == false {
+                    self.location = None;
+                } else if let Some(cwd) = &self.browser().cwd {
+                    let text = match cwd.ends_with('/') {
+                        true => cwd.clone(),
+                        false => format!("{}/", cwd),
+                    };
+                    self.location = Some(Location {
+                        text: text.clone(),
+                        completions: Vec::new(),
+                    });
+                    ctx.memory_mut(|m| m.request_focus(egui::Id::new(LOCATION_ID)));
+                    self.complete(ctx, text);
+                }
+            }
+            WebUiMessage::SetLocation(text) => {
+                if let Some(location) = &mut self.location {
+                    location.text = text.clone();
+                    self.complete(ctx, text);
+                }
+            }
+            WebUiMessage::SetCompletions((text, rsp)) => {
+                if let Some(location) = &mut self.location {
+                    // Drop completions of an outdated location.
+                    if location.text == text {
+                        match rsp {
+                            Ok(v) => location.completions = v.dirs,
+                            Err(e) => log::warn!("complete {} failed: {}", text, e),
+                        }
+                    }
+                }
+            }
+            WebUiMessage::ActSubdirs(path) => {
+                if self.subdirs.contains_key(&path) == false {
+                    self.subdirs.insert(path.clone(), None);
+                    self.fetch_subdirs(ctx, path);
+                }
+            }
+            WebUiMessage::SetSubdirs((path, rsp)) => {
+                let dirs = match rsp {
+                    Ok(v) => v.dirs,
+                    Err(e) => {
+                        log::warn!("list subdirectories of {} failed: {}", path, e);
+                        Vec::new()
+                    }
+                };
+                self.subdirs.insert(path, Some(dirs));
+            }
+            WebUiMessage::SetFileList((id, mut filelist)) => {
+                // Directories may have changed.
+                self.subdirs.clear();
+
+                if self.settings.show_hidden == false {
+                    filelist
+                        .entries
+                        .retain(|v| v.f_name.starts_with('.') == false);
+                }
+                sort_entries(&mut filelist.entries, &self.settings);
+
+                let browser = match self.browser_by_id(id) {
+                    Some(v) => v,
+                    None => return,
+                };
+
+                // Keep the selection on refresh, but drop files that are gone.
+                // Restore the selection and scroll position when going back.
+                if let Some(visit) = browser.restore.take() {
+                    browser.selected = visit.selected;
+                    browser.selection_anchor = None;
+                    let restore_id = browser.temp_id(RESTORE_SCROLL_ID);
+                    ctx.data_mut(|d| d.insert_temp(restore_id, visit.scroll));
+                }
+
+                browser
+                    .selected
+                    .retain(|path| filelist.entries.iter().any(|v| &v.f_path == path));
+                browser.cursor = browser.cursor.min(filelist.entries.len().saturating_sub(1));
+                if let Some(anchor) = &browser.selection_anchor {
+                    if filelist.entries.iter().any(|v| &v.f_path == anchor) == false {
+                        browser.selection_anchor = None;
+                    }
+                }
+                browser.filelist = Some(filelist);
+
+                // Forget thumbnails of images no longer listed in any tab.
+                let listed: std::collections::HashSet<&String> = self
+                    .panes
+                    .iter()
+                    .flat_map(|v| v.browsers.iter())
+                    .filter_map(|v| v.filelist.as_ref())
+                    .flat_map(|v| v.entries.iter().map(|v| &v.f_path))
+                    .collect();
+                self.thumbnails.retain(|path, _| {
+                    if listed.contains(path) {
+                        return true;
+                    }
+                    ctx.forget_image(format!("bytes://{}", path).as_str());
+                    return false;
+                });
+            }
+            WebUiMessage::ActSelect((path, selection)) => {
+                let browser = self.browser_mut();
+                match selection {
+                    Selection::Only => {
+                        browser.selected.clear();
+                        browser.selected.insert(path.clone());
+                        browser.selection_anchor = Some(path);
+                    }
+                    Selection::Toggle => {
+                        if browser.selected.remove(&path) == false {
+                            browser.selected.insert(path.clone());
+                        }
+                        browser.selection_anchor = Some(path);
+                    }
+                    Selection::Range | Selection::AddRange => {
+                        if let Some(filelist) = &browser.filelist {
+                            let entries = &filelist.entries;
+                            let end = entries.iter().position(|v| v.f_path == path);
+                            let start = browser
+                                .selection_anchor
+                                .as_ref()
+                                .and_then(|anchor| entries.iter().position(|v| &v.f_path == anchor))
+                                .or(end);
+
+                            if selection == Selection::Range {
+                                browser.selected.clear();
+                            }
+                            if let (Some(start), Some(end)) = (start, end) {
+                                for item in &entries[start.min(end)..=start.max(end)] {
+                                    browser.selected.insert(item.f_path.clone());
+                                }
+                            }
+                            if browser.selection_anchor.is_none() {
+                                browser.selection_anchor = Some(path);
+                            }
+                        }
+                    }
+                }
+            }
+            WebUiMessage::ActSelectAll => {
+                let browser = self.browser_mut();
+                if let Some(filelist) = &browser.filelist {
+                    for item in &filelist.entries {
+                        browser.selected.insert(item.f_path.clone());
+                    }
+                }
+            }
+            WebUiMessage::ActSelectInvert => {
+                let browser = self.browser_mut();
+                if let Some(filelist) = &browser.filelist {
+                    for item in &filelist.entries {
+                        if browser.selected.remove(&item.f_path) == false {
+                            browser.selected.insert(item.f_path.clone());
+                        }
+                    }
+                }
+            }
+            WebUiMessage::ActSelectNone => {
+                let browser = self.browser_mut();
+                browser.selected.clear();
+                browser.selection_anchor = None;
+            }
+            WebUiMessage::ShowContextMenu((item, pos)) => {
+                self.context_menu = Some(ContextMenu {
+                    item: item,
+                    pos: pos,
+                    cursor: 0,
+                    activate: false,
+                });
+            }
+            WebUiMessage::CloseContextMenu => {
+                self.context_menu = None;
+            }
+            WebUiMessage::ActDownload(path) => {
+                self.download(path.as_str());
+            }
+            WebUiMessage::ActCopyPath(path) => {
+                self.status = Some(format!("Copied {}", path));
+                ctx.output_mut(|o| o.copied_text = path);
+            }
+            WebUiMessage::ActRename(path) => {
+                let name = std::path::Path::new(&path)
+                    .file_name()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.rename = Some(Rename {
+                    path: path,
+                    name: name,
+                    busy: false,
+                    error: None,
+                });
+            }
+            WebUiMessage::SetRenameName(name) => {
+                if let Some(rename) = &mut self.rename {
+                    rename.name = name;
+                }
+            }
+            WebUiMessage::ActSaveRename => {
+                self.save_rename(ctx);
+            }
+            WebUiMessage::FinishRename(rsp) => match rsp {
+                Ok(v) => {
+                    self.status = Some(format!("Renamed to {}", v.path));
+                    self.rename = None;
+                    self.refresh(ctx);
+                }
+                Err(e) => {
+                    if let Some(rename) = &mut self.rename {
+                        rename.busy = false;
+                        rename.error = Some(e);
+                    }
+                }
+            },
+            WebUiMessage::CloseRename => {
+                self.rename = None;
+            }
+            WebUiMessage::ActClipboard(mode) => {
+                let paths: Vec<String> = self.browser().selected.iter().cloned().collect();
+                if paths.is_empty() == false {
+                    let verb = match mode {
+                        crate::protocol::PasteMode::Copy => "Copied",
+                        crate::protocol::PasteMode::Move => "Cut",
+                    };
+                    self.status = Some(format!("{} {} files", verb, paths.len()));
+                    self.clipboard = Some(Clipboard {
+                        paths: paths,
+                        mode: mode,
+                    });
+                }
+            }
+            WebUiMessage::ActPaste => {
+                if let (Some(clipboard), Some(cwd)) = (&self.clipboard, &self.browser().cwd) {
+                    let req = crate::protocol::PasteRequest {
+                        sources: clipboard.paths.clone(),
+                        dest: cwd.clone(),
+                        mode: clipboard.mode,
+                        conflict: crate::protocol::ConflictPolicy::Ask,
+                    };
+                    self.paste(ctx, req);
+                }
+            }
+            WebUiMessage::SetPasteConflict(conflict) => {
+                self.paste_conflict = Some(conflict);
+            }
+            WebUiMessage::ActPasteWith(policy) => {
+                if let Some((mut req, _)) = self.paste_conflict.take() {
+                    req.conflict = policy;
+                    self.paste(ctx, req);
+                }
+            }
+            WebUiMessage::ClosePasteConflict => {
+                self.paste_conflict = None;
+            }
+            WebUiMessage::FinishPaste((req, rsp)) => {
+                match rsp {
+                    Ok(v) => {
+                        // Moved files are gone, so they can not be pasted again.
+                        let from_clipboard =
+                            self.clipboard.as_ref().map(|v| &v.paths) == Some(&req.sources);
+                        let moved = req.mode == crate::protocol::PasteMode::Move;
+                        if moved && from_clipboard && v.errors.is_empty() {
+                            self.clipboard = None;
+                        }
+                        self.status = Some(match v.errors.first() {
+                            Some(e) => format!("Pasted {} files, {}", v.done, e),
+                            None => format!("Pasted {} files, skipped {}", v.done, v.skipped),
+                        });
+                    }
+                    Err(e) => self.status = Some(format!("Paste failed: {}", e)),
+                }
+                self.refresh(ctx);
+            }
+            WebUiMessage::ActUpload(files) => {
+                self.upload(ctx, files);
+            }
+            WebUiMessage::SetStatus(status) => {
+                self.status = Some(status);
+            }
+            WebUiMessage::SetUploadProgress(progress) => {
+                self.upload_progress = progress;
+            }
+            WebUiMessage::FinishUpload => {
+                self.upload_id = None;
+                self.upload_progress = Default::default();
+            }
+            WebUiMessage::ActProperties(item) => {
+                self.properties = Some(Properties {
+                    item: item,
+                    checksum_id: None,
+                    checksum: None,
+                });
+            }
+            WebUiMessage::CloseProperties => {
+                self.cancel_checksum();
+                self.properties = None;
+            }
+            WebUiMessage::ActChecksum => {
+                self.checksum(ctx);
+            }
+            WebUiMessage::ActCancelChecksum => {
+                self.cancel_checksum();
+            }
+            WebUiMessage::ActRomCheck(path) => {
+                self.romcheck(ctx, path);
+            }
+            WebUiMessage::SetRomCheck(rsp) => {
+                if let Some(romcheck) = &mut self.romcheck {
+                    romcheck.report = Some(rsp);
+                }
+            }
+            WebUiMessage::SetRomCheckFilter(filter) => {
+                if let Some(romcheck) = &mut self.romcheck {
+                    romcheck.filter = filter;
+                }
+            }
+            WebUiMessage::ActRomFix => {
+                self.romfix(ctx);
+            }
+            WebUiMessage::CloseRomCheck => {
+                self.romcheck = None;
+            }
+            WebUiMessage::ActDu((path, refresh)) => {
+                let apparent = self.du.as_ref().map(|v| v.apparent).unwrap_or(false);
+                self.du = Some(DiskUsage {
+                    path: path,
+                    usage: None,
+                    apparent: apparent,
+                    polled_at: 0.0,
+                });
+                self.poll_du(ctx, refresh);
+            }
+            WebUiMessage::SetDu(rsp) => {
+                if let Some(du) = &mut self.du {
+                    // Drop responses of a directory no longer shown.
+                    let path = rsp.as_ref().map(|v| v.path.clone()).ok();
+                    if path.is_none() || path.as_ref() == Some(&du.path) {
+                        du.usage = Some(rsp);
+                    }
+                }
+            }
+            WebUiMessage::SetDuApparent(apparent) => {
+                if let Some(du) = &mut self.du {
+                    du.apparent = apparent;
+                }
+            }
+            WebUiMessage::CloseDu => {
+                self.du = None;
+            }
+            WebUiMessage::SetPlace((idx, path)) => {
+                if let Some(v) = self.places.get_mut(idx) {
+                    *v = path;
+                }
+            }
+            WebUiMessage::ActBookmarks => {
+                self.fetch_bookmarks(ctx);
+            }
+            WebUiMessage::SetBookmarks(bookmarks) => {
+                self.bookmarks = bookmarks;
+            }
+            WebUiMessage::ActAddBookmark(path) => {
+                self.add_bookmark(ctx, path);
+            }
+            WebUiMessage::ActRemoveBookmark(path) => {
+                self.remove_bookmark(ctx, path);
+            }
+            WebUiMessage::ActMoveBookmark((path, index)) => {
+                self.move_bookmark(ctx, path, index);
+            }
+            WebUiMessage::ActVolumes => {
+                self.fetch_volumes(ctx);
+            }
+            WebUiMessage::SetVolumes(volumes) => {
+                self.volumes = volumes;
+            }
+            WebUiMessage::ActSteamApps => {
+                self.fetch_steam_apps(ctx);
+                self.fetch_steam_shortcuts(ctx);
+            }
+            WebUiMessage::SetSteamApps(rsp) => {
+                self.steam_apps = Some(rsp);
+            }
+            WebUiMessage::ShowSteamApps(open) => {
+                self.steam_apps_open = open;
+            }
+            WebUiMessage::SetSteamAppsFilter(filter) => {
+                self.steam_apps_filter = filter;
+            }
+            WebUiMessage::SetSteamShortcuts(rsp) => {
+                self.steam_shortcuts = Some(rsp);
+            }
+            WebUiMessage::ActAddShortcut(exe) => {
+                let name = std::path::Path::new(&exe)
+                    .file_stem()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                self.add_shortcut = Some(AddShortcut {
+                    form: crate::protocol::SteamShortcutAddRequest {
+                        name: name,
+                        exe: exe,
+                        ..Default::default()
+                    },
+                    busy: false,
+                    error: None,
+                });
+                self.fetch_steam_shortcuts(ctx);
+            }
+            WebUiMessage::SetShortcutForm(form) => {
+                if let Some(add_shortcut) = &mut self.add_shortcut {
+                    add_shortcut.form = form;
+                }
+            }
+            WebUiMessage::ActSaveShortcut => {
+                self.save_shortcut(ctx);
+            }
+            WebUiMessage::FinishAddShortcut(rsp) => match rsp {
+                Ok(v) => {
+                    self.add_shortcut = None;
+                    self.status = Some(format!(
+                        "Added {} to Steam, restart Steam to see it",
+                        v.shortcut.name
+                    ));
+                    self.fetch_steam_shortcuts(ctx);
+                }
+                Err(e) => {
+                    if let Some(add_shortcut) = &mut self.add_shortcut {
+                        add_shortcut.busy = false;
+                        add_shortcut.error = Some(e);
+                    }
+                }
+            },
+            WebUiMessage::CloseAddShortcut => {
+                self.add_shortcut = None;
+            }
+            WebUiMessage::ActRemoveShortcut((user, appid)) => {
+                self.remove_shortcut(ctx, user, appid);
+            }
+            WebUiMessage::AskTrashPut(paths) => {
+                self.confirm_trash = Some(paths);
+            }
+            WebUiMessage::CloseTrashPut => {
+                self.confirm_trash = None;
+            }
+            WebUiMessage::ActTrashPut(paths) => {
+                self.confirm_trash = None;
+                self.properties = None;
+                self.trash_put(ctx, paths);
+            }
+            WebUiMessage::ActTrash => {
+                self.fetch_trash(ctx, true);
+            }
+            WebUiMessage::SetTrash(rsp) => {
+                if let Some(trash) = &mut self.trash {
+                    // Drop selections of files that are gone.
+                    if let Ok(v) = &rsp {
+                        trash
+                            .selected
+                            .retain(|path| v.items.iter().any(|v| &v.path == path));
+                    }
+                    trash.items = Some(rsp);
+                }
+                self.fetch_trash_sizes(ctx);
+            }
+            WebUiMessage::SetTrashSizes((paths, rsp)) => {
+                if let Some(trash) = &mut self.trash {
+                    let sizes = match rsp {
+                        Ok(v) => v.sizes,
+                        Err(_) => std::collections::HashMap::new(),
+                    };
+                    for path in paths {
+                        let size = sizes.get(&path).copied();
+                        trash.sizes.insert(path, size);
+                    }
+                }
+            }
+            WebUiMessage::SetTrashSelected((path, selected)) => {
+                if let Some(trash) = &mut self.trash {
+                    if selected {
+                        trash.selected.insert(path);
+                    } else {
+                        trash.selected.remove(&path);
+                    }
+                }
+            }
+            WebUiMessage::ActTrashRestore => {
+                self.trash_restore(ctx);
+            }
+            WebUiMessage::ActTrashDelete => {
+                let paths = match &self.trash {
+                    Some(v) => v.selected.iter().cloned().collect(),
+                    None => Vec::new(),
+                };
+                self.trash_empty(ctx, Some(paths));
+            }
+            WebUiMessage::SetTrashConfirmEmpty(confirm) => {
+                if let Some(trash) = &mut self.trash {
+                    trash.confirm_empty = confirm;
+                }
+            }
+            WebUiMessage::ActTrashEmpty => {
+                if let Some(trash) = &mut self.trash {
+                    trash.confirm_empty = false;
+                }
+                self.trash_empty(ctx, None);
+            }
+            WebUiMessage::FinishTrash(rsp) => {
+                self.trash_busy = false;
+                match rsp {
+                    Ok(v) => self.status = Some(v),
+                    Err(e) => self.status = Some(format!("Trash failed: {}", e)),
+                }
+                if self.trash.is_some() {
+                    self.fetch_trash(ctx, false);
+                }
+                self.refresh(ctx);
+            }
+            WebUiMessage::CloseTrash => {
+                self.trash = None;
+            }
+            WebUiMessage::ActCleanup => {
+                self.fetch_cleanup(ctx);
+            }
+            WebUiMessage::SetCleanup(rsp) => {
+                if let Some(cleanup) = &mut self.cleanup {
+                    cleanup.orphans = Some(rsp);
+                }
+            }
+            WebUiMessage::SetCleanupSelected((path, selected)) => {
+                if let Some(cleanup) = &mut self.cleanup {
+                    if selected {
+                        cleanup.selected.insert(path);
+                    } else {
+                        cleanup.selected.remove(&path);
+                    }
+                }
+            }
+            WebUiMessage::SetCleanupSelection(paths) => {
+                if let Some(cleanup) = &mut self.cleanup {
+                    cleanup.selected = paths.into_iter().collect();
+                }
+            }
+            WebUiMessage::ActCleanupRemove => {
+                self.cleanup_remove(ctx);
+            }
+            WebUiMessage::FinishCleanupRemove(rsp) => {
+                if let Some(cleanup) = &mut self.cleanup {
+                    cleanup.removing = false;
+                    cleanup.result = Some(rsp);
+                }
+            }
+            WebUiMessage::CloseCleanup => {
+                self.cleanup = None;
+            }
+            WebUiMessage::ActScreenshots => {
+                self.fetch_screenshots(ctx);
+            }
+            WebUiMessage::SetScreenshots(rsp) => {
+                if let Some(screenshots) = &mut self.screenshots {
+                    screenshots.games = Some(rsp);
+                }
+            }
+            WebUiMessage::SetThumbnail((path, rsp)) => {
+                if let Some(screenshots) = &mut self.screenshots {
+                    match rsp {
+                        Ok(v) => {
+                            screenshots.thumbnails.insert(path, Some(v.into()));
+                        }
+                        Err(e) => {
+                            // Keep the entry, so it is not fetched again.
+                            log::warn!("fetch thumbnail {} failed: {}", path, e);
+                            screenshots.thumbnails.insert(path, Some(Vec::new().into()));
+                        }
+                    }
+                }
+            }
+            WebUiMessage::SetScreenshotSelected((path, selected)) => {
+                if let Some(screenshots) = &mut self.screenshots {
+                    if selected {
+                        screenshots.selected.insert(path);
+                    } else {
+                        screenshots.selected.remove(&path);
+                    }
+                }
+            }
+            WebUiMessage::SetScreenshotSelection(paths) => {
+                if let Some(screenshots) = &mut self.screenshots {
+                    screenshots.selected = paths.into_iter().collect();
+                }
+            }
+            WebUiMessage::ActDownloadScreenshots => {
+                self.download_screenshots(ctx);
+            }
+            WebUiMessage::FinishDownloadScreenshots(rsp) => {
+                if let Some(screenshots) = &mut self.screenshots {
+                    screenshots.downloading = false;
+                }
+                match rsp {
+                    Ok(v) => crate::picker::save_file("screenshots.zip", v),
+                    Err(e) => self.status = Some(format!("Download screenshots failed: {}", e)),
+                }
+            }
+            WebUiMessage::CloseScreenshots => {
+                self.screenshots = None;
+            }
+            WebUiMessage::ActPrefixes => {
+                self.fetch_prefixes(ctx);
+            }
+            WebUiMessage::SetPrefixes(rsp) => {
+                if let Some(prefixes) = &mut self.prefixes {
+                    prefixes.prefixes = Some(rsp);
+                }
+            }
+            WebUiMessage::ActPrefix(appid) => {
+                self.fetch_prefix(ctx, appid);
+            }
+            WebUiMessage::SetPrefix(rsp) => {
+                if let Some(prefixes) = &mut self.prefixes {
+                    let appid = rsp.as_ref().map(|v| v.prefix.appid).ok();
+                    if appid.is_none() || appid == prefixes.selected {
+                        prefixes.locations = Some(rsp);
+                    }
+                }
+            }
+            WebUiMessage::ClosePrefixes => {
+                self.prefixes = None;
+            }
+            WebUiMessage::ActBackups => {
+                self.backups = Some(Backups {
+                    snapshots: None,
+                    confirm: None,
+                });
+                self.fetch_backups(ctx);
+            }
+            WebUiMessage::SetBackups(rsp) => {
+                if let Some(backups) = &mut self.backups {
+                    backups.snapshots = Some(rsp);
+                }
+            }
+            WebUiMessage::ActBackup(path) => {
+                self.backup(ctx, path);
+            }
+            WebUiMessage::SetBackupConfirm(id) => {
+                if let Some(backups) = &mut self.backups {
+                    backups.confirm = id;
+                }
+            }
+            WebUiMessage::ActRestoreBackup(id) => {
+                if let Some(backups) = &mut self.backups {
+                    backups.confirm = None;
+                }
+                self.restore_backup(ctx, id);
+            }
+            WebUiMessage::ActDeleteBackup(id) => {
+                self.delete_backup(ctx, id);
+            }
+            WebUiMessage::FinishBackup(rsp) => {
+                self.backup_busy = false;
+                match rsp {
+                    Ok(v) => self.status = Some(v),
+                    Err(e) => self.status = Some(format!("Backup failed: {}", e)),
+                }
+                if self.backups.is_some() {
+                    self.fetch_backups(ctx);
+                }
+                self.refresh(ctx);
+            }
+            WebUiMessage::CloseBackups => {
+                self.backups = None;
+            }
+            WebUiMessage::SetChecksum((id, rsp)) => {
+                // Drop results of cancelled requests.
+                if let Some(properties) = &mut self.properties {
+                    if properties.checksum_id.as_ref() == Some(&id) {
+                        properties.checksum_id = None;
+                        properties.checksum = Some(rsp);
+                    }
+                }
+            }
+        }
+    }
+
+    fn view(&self, ctx: &egui::Context) {
+        egui::TopBottomPanel::top("top_panel").show(ctx, |ui| {
+            self.view_top_panel(ctx, ui);
+        });
+
+        if self.settings.show_side_panel {
+            egui::SidePanel::left("side_panel")
+                .resizable(false)
+                .default_width(200.0)
+                .show(ctx, |ui| {
+                    self.view_side_panel(ctx, ui);
+                });
+        }
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            self.view_body_panel(ctx, ui);
+        });
+
+        egui::TopBottomPanel::bottom("bottem_panel").show(ctx, |ui| {
+            self.view_bottom_panel(ctx, ui);
+        });
+
+        if let Some(properties) = &self.properties {
+            self.view_properties(ctx, properties);
+        }
+
+        if let Some(romcheck) = &self.romcheck {
+            self.view_romcheck(ctx, romcheck);
+        }
+
+        if let Some(du) = &self.du {
+            self.view_du(ctx, du);
+        }
+
+        if self.steam_apps_open {
+            self.view_steam_apps(ctx);
+        }
+
+        if let Some(prefixes) = &self.prefixes {
+            self.view_prefixes(ctx, prefixes);
+        }
+
+        if let Some(add_shortcut) = &self.add_shortcut {
+            self.view_add_shortcut(ctx, add_shortcut);
+        }
+
+        if let Some(screenshots) = &self.screenshots {
+            self.view_screenshots(ctx, screenshots);
+        }
+
+        if let Some(cleanup) = &self.cleanup {
+            self.view_cleanup(ctx, cleanup);
+        }
+
+        if let Some(trash) = &self.trash {
+            self.view_trash(ctx, trash);
+        }
+
+        if let Some((_, conflicts)) = &self.paste_conflict {
+            self.view_paste_conflict(ctx, conflicts);
+        }
+
+        if let Some(paths) = &self.confirm_trash {
+            self.view_confirm_trash(ctx, paths);
+        }
+
+        if let Some(rename) = &self.rename {
+            self.view_rename(ctx, rename);
+        }
+
+        if let Some(menu) = &self.context_menu {
+            self.view_context_menu(ctx, menu);
+        }
+
+        if let Some(backups) = &self.backups {
+            self.view_backups(ctx, backups);
+        }
+
+        if self.settings_open {
+            self.view_settings(ctx);
+        }
+    }
+
+    fn install_font(&self, ctx: &egui::Context, name: String, data: Vec<u8>) {
+        // Install my own font. `.ttf` and `.otf` files supported.
+        let mut fonts = egui::FontDefinitions::default();
+        let font = egui::FontData::from_owned(data);
+        fonts.font_data.insert(name.clone(), font);
+
+        // Put my font first (highest priority) for proportional text
+        fonts
+            .families
+            .entry(egui::FontFamily::Proportional)
+            .or_default()
+            .insert(0, name.clone());
+
+        // Put my font as last fallback for monospace
+        fonts
+            .families
+            .entry(egui::FontFamily::Monospace)
+            .or_default()
+            .push(name.clone());
+
+        // Tell egui to use these fonts.
+        ctx.set_fonts(fonts);
+    }
+
+    /// Download a file of current working directory.
+    ///
+    /// # Arguments
+    /// + `path`: the absolute path of the file.
+    fn download(&mut self, path: &str) {
+        let name = std::path::Path::new(path)
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let url = format!(
+            "/api/download?{}",
+            url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("path", path)
+                .finish()
+        );
+
+        self.client.download(url.as_str(), name.as_str());
+        self.status = Some(format!("Downloading {}", name));
+    }
+
+    /// Paste files, asking how to resolve conflicts if needed.
+    ///
+    /// # Arguments
+    /// + `req`: the paste request.
+    fn paste(&self, ctx: &egui::Context, req: crate::protocol::PasteRequest) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            req.clone(),
+            move |rsp: Result<crate::protocol::PasteResponse, String>| {
+                let ask = req.conflict == crate::protocol::ConflictPolicy::Ask;
+                match rsp {
+                    Ok(v) if ask && v.conflicts.is_empty() == false => {
+                        tx.send(WebUiMessage::SetPasteConflict((req, v.conflicts)))
+                            .unwrap();
+                    }
+                    rsp => {
+                        tx.send(WebUiMessage::FinishPaste((req, rsp))).unwrap();
+                    }
+                }
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Refresh the directories of all tabs.
+    fn refresh(&self, ctx: &egui::Context) {
+        for browser in self.panes.iter().flat_map(|v| v.browsers.iter()) {
+            if let Some(v) = &browser.cwd {
+                self.cd_in(ctx, browser.id, v.as_str());
+            }
+        }
+    }
+
+    /// Upload files into current directory.
+    ///
+    /// # Arguments
+    /// + `files`: files to upload, paths are relative to current directory.
+    fn upload(&mut self, ctx: &egui::Context, files: Vec<crate::http_client::UploadFile>) {
+        let cwd = match &self.browser().cwd {
+            Some(v) => v.clone(),
+            None => return,
+        };
+
+        let id = chrono::Utc::now().timestamp_millis().to_string();
+        self.upload_id = Some(id.clone());
+
+        let dest = cwd.clone();
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client
+            .upload(dest.as_str(), id.as_str(), files, move |rsp| {
+                let status = match rsp {
+                    Ok(v) if v.missing.is_empty() == false => format!(
+                        "Uploaded {} files, {} files missing: {}",
+                        v.files,
+                        v.missing.len(),
+                        v.missing.join(", ")
+                    ),
+                    Ok(v) => format!(
+                        "Uploaded {} files in {} new folders, {}",
+                        v.files,
+                        v.dirs,
+                        format_size(v.bytes)
+                    ),
+                    Err(e) => format!("Upload failed: {}", e),
+                };
+
+                tx.send(WebUiMessage::FinishUpload).unwrap();
+                tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                tx.send(WebUiMessage::ActCWD(cwd)).unwrap();
+                ctx.request_repaint();
+            });
+    }
+
+    /// Fetch progress of the in-flight upload.
+    fn poll_upload_progress(&self, ctx: &egui::Context) {
+        let id = match &self.upload_id {
+            Some(v) => v.clone(),
+            None => return,
+        };
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::UploadProgressRequest { id: id },
+            move |rsp: Result<crate::protocol::UploadProgressResponse, String>| {
+                // The upload may not be started or already finished.
+                if let Ok(rsp) = rsp {
+                    tx.send(WebUiMessage::SetUploadProgress(rsp)).unwrap();
+                    ctx.request_repaint();
+                }
+            },
+        );
+    }
+
+    /// The browser of the shown tab of the active pane.
+    fn browser(&self) -> &Browser {
+        return self.panes[self.active].browser();
+    }
+
+    fn browser_mut(&mut self) -> &mut Browser {
+        return self.panes[self.active].browser_mut();
+    }
+
+    /// The browser of any tab, by its id.
+    fn browser_by_id(&mut self, id: usize) -> Option<&mut Browser> {
+        return self
+            .panes
+            .iter_mut()
+            .flat_map(|v| v.browsers.iter_mut())
+            .find(|v| v.id == id);
+    }
+
+    /// Change current directory of the active pane.
+    ///
+    /// # Arguments
+    /// + `path`: path to change to.
+    fn cd(&self, ctx: &egui::Context, path: &str) {
+        self.cd_in(ctx, self.browser().id, path);
+    }
+
+    /// Change current directory of a browser.
+    ///
+    /// # Arguments
+    /// + `id`: the id of the browser.
+    /// + `path`: path to change to.
+    fn cd_in(&self, ctx: &egui::Context, id: usize, path: &str) {
+        let path = path.to_string();
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::ReaddirRequest { path: path.clone() },
+            move |rsp: Result<crate::protocol::ReaddirResponse, String>| {
+                // A restored tab may point to a removed SD card.
+                let rsp = match rsp {
+                    Ok(v) => v,
+                    Err(e) => {
+                        let status = format!("Open {} failed: {}", path, e);
+                        tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                        ctx.request_repaint();
+                        return;
+                    }
+                };
+                tx.send(WebUiMessage::SetCWD((id, path))).unwrap();
+                tx.send(WebUiMessage::SetFileList((id, rsp))).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+}
+
+/// Sort files by the sort order of the settings, directories first.
+fn sort_entries(entries: &mut [crate::protocol::ReaddirResponseItem], settings: &Settings) {
+    let extension = |v: &crate::protocol::ReaddirResponseItem| {
+        std::path::Path::new(&v.f_name)
+            .extension()
+            .map(|v| v.to_string_lossy().to_lowercase())
+            .unwrap_or_default()
+    };
+
+    entries.sort_by(|a, b| {
+        let name = a.f_name.to_lowercase().cmp(&b.f_name.to_lowercase());
+        let order = match settings.sort_by {
+            SortBy::Name => name,
+            SortBy::Type => extension(a).cmp(&extension(b)).then(name),
+            SortBy::Size => a.f_size.cmp(&b.f_size).then(name),
+            SortBy::Modified => a.f_modified.cmp(&b.f_modified).then(name),
+        };
+        let order = match settings.sort_descending {
+            true => order.reverse(),
+            false => order,
+        };
+        return (b.f_type == "DIR").cmp(&(a.f_type == "DIR")).then(order);
+    });
+}
+
+/// Convert size in bytes into a human-readable format.
+fn format_size(size: u64) -> String {
+    let units = ["B", "KB", "MB", "GB", "TB", "PB", "EB", "ZB", "YB"];
+    let mut size = size as f64;
+    let mut index = 0;
+
+    while size >= 1024.0 && index < units.len() - 1 {
+        size /= 1024.0;
+        index += 1;
+    }
+
+    return format!("{:.2} {}", size, units[index]);
+}
+
+fn convert_epoch_to_local_time(epoch: u64) -> String {
+    // Create a NaiveDateTime from the timestamp
+    let naive_datetime = chrono::NaiveDateTime::from_timestamp_opt(epoch as i64, 0).unwrap();
+
+    // Convert it to UTC DateTime, then to local timezone
+    let datetime_utc: chrono::DateTime<chrono::Utc> =
+        chrono::DateTime::from_naive_utc_and_offset(naive_datetime, chrono::Utc);
+    let datetime_local = datetime_utc.with_timezone(&chrono::Local);
+
+    // Format the datetime to a string in a human-readable format
+    datetime_local.format("%Y-%m-%d %H:%M:%S").to_string()
+}
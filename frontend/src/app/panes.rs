@@ -0,0 +1,855 @@
+use super::{
+    convert_epoch_to_local_time, format_size, Browser, Pane, Selection, SortBy, Tabs, ViewMode,
+    WebUI, WebUiMessage, ARCHIVE_EXTENSIONS, MAX_THUMBNAIL_BYTES, MENU_ITEMS_ID, RESTORE_SCROLL_ID,
+    ROM_EXTENSIONS, SCROLL_OFFSET_ID,
+};
+
+/// The id of a row the file table scrolls to once.
+const SCROLL_TO_ROW_ID: &str = "file_table_scroll_to_row";
+
+/// The id of the position of the cursor row of the file table.
+const CURSOR_POS_ID: &str = "file_table_cursor_pos";
+
+/// Rows the cursor moves by a page.
+const PAGE_ROWS: usize = 10;
+
+/// The id of the number of columns of the file grid.
+const GRID_COLUMNS_ID: &str = "file_grid_columns";
+
+/// The size of a cell of the file grid.
+const GRID_CELL_SIZE: egui::Vec2 = egui::vec2(112.0, 104.0);
+
+impl WebUI {
+    pub(super) fn view_bottom_panel(&self, _ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.with_layout(egui::Layout::bottom_up(egui::Align::LEFT), |ui| {
+            powered_by_egui_and_eframe(ui);
+            egui::warn_if_debug_build(ui);
+
+            if self.upload_id.is_some() {
+                let progress = &self.upload_progress;
+                let fraction = if progress.bytes_total > 0 {
+                    progress.bytes_written as f32 / progress.bytes_total as f32
+                } else {
+                    0.0
+                };
+                let text = format!(
+                    "Uploading {} ({} / {})",
+                    progress.current.clone().unwrap_or_default(),
+                    format_size(progress.bytes_written),
+                    format_size(progress.bytes_total)
+                );
+                ui.add(egui::ProgressBar::new(fraction).text(text));
+            } else if let Some(status) = &self.status {
+                ui.label(status);
+            }
+
+            let browser = self.browser();
+            if let Some(filelist) = &browser.filelist {
+                ui.horizontal(|ui| {
+                    let size: u64 = filelist
+                        .entries
+                        .iter()
+                        .filter(|v| browser.selected.contains(&v.f_path) && v.f_type != "DIR")
+                        .map(|v| v.f_size)
+                        .sum();
+                    ui.label(format!(
+                        "{} of {} selected ({})",
+                        browser.selected.len(),
+                        filelist.entries.len(),
+                        format_size(size)
+                    ));
+
+                    if ui.small_button("All").on_hover_text("Ctrl+A").clicked() {
+                        self.tx.send(WebUiMessage::ActSelectAll).unwrap();
+                    }
+                    if ui.small_button("Invert").on_hover_text("Ctrl+I").clicked() {
+                        self.tx.send(WebUiMessage::ActSelectInvert).unwrap();
+                    }
+
+                    let has_selection = browser.selected.is_empty() == false;
+                    let btn = egui::Button::new("None").small();
+                    let rsp = ui.add_enabled(has_selection, btn).on_hover_text("Esc");
+                    if rsp.clicked() {
+                        self.tx.send(WebUiMessage::ActSelectNone).unwrap();
+                    }
+
+                    let btn = egui::Button::new("🗑").small();
+                    let rsp = ui
+                        .add_enabled(has_selection && self.trash_busy == false, btn)
+                        .on_hover_text("Move to trash (Del)");
+                    if rsp.clicked() {
+                        let paths = browser.selected.iter().cloned().collect();
+                        self.tx.send(WebUiMessage::AskTrashPut(paths)).unwrap();
+                    }
+
+                    if self.settings.split {
+                        ui.separator();
+                        for (text, hover, mode) in [
+                            (
+                                "F5 Copy",
+                                "Copy to the other pane",
+                                crate::protocol::PasteMode::Copy,
+                            ),
+                            (
+                                "F6 Move",
+                                "Move to the other pane",
+                                crate::protocol::PasteMode::Move,
+                            ),
+                        ] {
+                            let btn = egui::Button::new(text).small();
+                            let rsp = ui.add_enabled(has_selection, btn).on_hover_text(hover);
+                            if rsp.clicked() {
+                                self.tx.send(WebUiMessage::ActPasteToPane(mode)).unwrap();
+                            }
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    pub(super) fn view_body_panel(&self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        if self.settings.split == false {
+            self.view_files(ctx, ui, 0);
+            return;
+        }
+
+        ui.columns(2, |columns| {
+            for (idx, ui) in columns.iter_mut().enumerate() {
+                self.view_files(ctx, ui, idx);
+            }
+        });
+    }
+
+    /// The file table of a pane.
+    ///
+    /// # Arguments
+    /// + `idx`: the index of the pane.
+    fn view_files(&self, ctx: &egui::Context, ui: &mut egui::Ui, idx: usize) {
+        let tabs = &self.panes[idx];
+        let browser = tabs.browser();
+        let active = idx == self.active;
+
+        // A press in another pane makes it the target of file actions, before
+        // the click lands.
+        let pressed = ui.input(|i| i.pointer.any_pressed());
+        if active == false && pressed && ui.rect_contains_pointer(ui.max_rect()) {
+            self.tx.send(WebUiMessage::ActFocusPane(idx)).unwrap();
+        }
+
+        self.view_tabs(ui, tabs, active);
+        ui.separator();
+
+        ui.push_id(browser.id, |ui| {
+            match self.settings.view_mode {
+                ViewMode::List => self.view_file_table(ctx, ui, browser, active),
+                ViewMode::Grid => self.view_file_grid(ctx, ui, browser, active),
+            }
+
+            // Files take their own clicks, as they are added first.
+            let rsp = ui.interact(
+                ui.max_rect(),
+                browser.temp_id("file_table_empty_space"),
+                egui::Sense::click(),
+            );
+            if rsp.secondary_clicked() || long_pressed(ui, &rsp) {
+                let pos = rsp.interact_pointer_pos().unwrap_or(rsp.rect.center());
+                self.tx
+                    .send(WebUiMessage::ShowContextMenu((None, pos)))
+                    .unwrap();
+            }
+        });
+    }
+
+    /// The tab bar of a pane.
+    ///
+    /// # Arguments
+    /// + `tabs`: the tabs of the pane.
+    /// + `active`: whether the pane is the target of file actions.
+    fn view_tabs(&self, ui: &mut egui::Ui, tabs: &Tabs, active: bool) {
+        ui.horizontal_wrapped(|ui| {
+            for (idx, browser) in tabs.browsers.iter().enumerate() {
+                let cwd = browser.cwd.clone().unwrap_or_default();
+                let name = std::path::Path::new(&cwd)
+                    .file_name()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .unwrap_or(cwd.clone());
+
+                // The shown tab of the active pane stands out in split view.
+                let current = idx == tabs.current;
+                let mut text = egui::RichText::new(format!("📁 {}", name));
+                if current && active && self.settings.split {
+                    text = text.strong().color(ui.visuals().selection.stroke.color);
+                }
+
+                let rsp = ui.selectable_label(current, text).on_hover_text(cwd);
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActSelectTab(idx)).unwrap();
+                }
+
+                let closable = tabs.browsers.len() > 1;
+                if closable && rsp.middle_clicked() {
+                    self.tx.send(WebUiMessage::ActCloseTab(idx)).unwrap();
+                }
+                if closable {
+                    let btn = egui::Button::new("×").small().frame(false);
+                    let rsp = ui.add(btn).on_hover_text("Close tab (Ctrl+W)");
+                    if rsp.clicked() {
+                        self.tx.send(WebUiMessage::ActCloseTab(idx)).unwrap();
+                    }
+                }
+            }
+
+            let btn = egui::Button::new("➕").small();
+            let rsp = ui.add(btn).on_hover_text("New tab (Ctrl+T)");
+            if let (true, Some(cwd)) = (rsp.clicked(), &tabs.browser().cwd) {
+                self.tx.send(WebUiMessage::ActNewTab(cwd.clone())).unwrap();
+            }
+        });
+    }
+
+    fn view_file_table(
+        &self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        browser: &Browser,
+        active: bool,
+    ) {
+        let mut table = egui_extras::TableBuilder::new(ui);
+
+        // Restore the scroll position once, after going back.
+        let restore_id = browser.temp_id(RESTORE_SCROLL_ID);
+        if let Some(offset) = ctx.data(|d| d.get_temp::<f32>(restore_id)) {
+            ctx.data_mut(|d| d.remove::<f32>(restore_id));
+            table = table.vertical_scroll_offset(offset);
+        }
+
+        // Follow the gamepad and keyboard cursor.
+        let scroll_to_row_id = browser.temp_id(SCROLL_TO_ROW_ID);
+        if let Some(row) = ctx.data(|d| d.get_temp::<usize>(scroll_to_row_id)) {
+            ctx.data_mut(|d| d.remove::<usize>(scroll_to_row_id));
+            table = table.scroll_to_row(row, None);
+        }
+
+        table
+            .striped(true)
+            .resizable(true)
+            .cell_layout(egui::Layout::left_to_right(egui::Align::Center))
+            .column(egui_extras::Column::exact(8.0).resizable(false))
+            .column(egui_extras::Column::remainder().resizable(false))
+            .column(egui_extras::Column::exact(64.0).resizable(false))
+            .column(egui_extras::Column::exact(64.0).resizable(false))
+            .column(egui_extras::Column::exact(128.0).resizable(false))
+            .column(egui_extras::Column::exact(24.0).resizable(false))
+            .header(20.0, |mut header| {
+                header.col(|_ui| {});
+                header.col(|ui| {
+                    self.view_sort_header(ui, "Name", SortBy::Name);
+                });
+                header.col(|ui| {
+                    self.view_sort_header(ui, "Type", SortBy::Type);
+                });
+                header.col(|ui| {
+                    self.view_sort_header(ui, "Size", SortBy::Size);
+                });
+                header.col(|ui| {
+                    self.view_sort_header(ui, "Modified", SortBy::Modified);
+                });
+                header.col(|_ui| {});
+            })
+            .body(|mut body| {
+                let mut size = 0;
+                if let Some(filelist) = &browser.filelist {
+                    size = filelist.entries.len();
+                }
+
+                // Remember the scroll position for the navigation history.
+                let ui = body.ui_mut();
+                let offset = (ui.clip_rect().top() - ui.max_rect().top()).max(0.0);
+                ctx.data_mut(|d| d.insert_temp(browser.temp_id(SCROLL_OFFSET_ID), offset));
+
+                body.rows(20.0, size, |idx, mut row| {
+                    let mut item: Option<crate::protocol::ReaddirResponseItem> = None;
+                    if let Some(filelist) = &browser.filelist {
+                        item = Some(filelist.entries[idx].clone());
+                    }
+
+                    if let Some(item) = item {
+                        let selected = browser.selected.contains(&item.f_path);
+                        let focused = self.show_cursor
+                            && active
+                            && self.pane == Pane::Files
+                            && browser.cursor == idx;
+                        let id = |col: usize| egui::Id::new(("file_row", browser.id, idx, col));
+
+                        row.col(|ui| {
+                            paint_selection(ui, selected, focused);
+                            ui.label(file_icon(&item));
+                            self.interact_row(ui, browser, id(0), &item, None);
+                        });
+                        row.col(|ui| {
+                            paint_selection(ui, selected, focused);
+                            if focused {
+                                let pos = ui.max_rect().left_bottom();
+                                ctx.data_mut(|d| {
+                                    d.insert_temp(browser.temp_id(CURSOR_POS_ID), pos)
+                                });
+                            }
+                            let text = match &item.f_label {
+                                Some(v) => format!("{} ({})", v, item.f_name),
+                                None => item.f_name.clone(),
+                            };
+                            let mut label = egui::Label::new(text).truncate(true);
+
+                            if item.f_type == "DIR" {
+                                label = label.sense(egui::Sense::click());
+                            }
+
+                            let mut rsp = ui.add(label);
+                            if item.f_type == "DIR" {
+                                rsp = rsp.on_hover_cursor(egui::CursorIcon::PointingHand);
+                            }
+
+                            // Modifiers select the directory instead of opening it,
+                            // and a middle-click opens it in a new tab.
+                            let modifiers = ui.input(|i| i.modifiers);
+                            if rsp.clicked() && (modifiers.command || modifiers.shift) == false {
+                                self.cd(ctx, item.f_path.as_str());
+                            } else if rsp.middle_clicked() {
+                                self.tx
+                                    .send(WebUiMessage::ActNewTab(item.f_path.clone()))
+                                    .unwrap();
+                            } else {
+                                self.interact_row(ui, browser, id(1), &item, Some(rsp));
+                            }
+                        });
+                        row.col(|ui| {
+                            paint_selection(ui, selected, focused);
+                            ui.label(item.f_type.clone());
+                            self.interact_row(ui, browser, id(2), &item, None);
+                        });
+                        row.col(|ui| {
+                            paint_selection(ui, selected, focused);
+                            ui.label(format_size(item.f_size));
+                            self.interact_row(ui, browser, id(3), &item, None);
+                        });
+                        row.col(|ui| {
+                            paint_selection(ui, selected, focused);
+                            ui.label(convert_epoch_to_local_time(item.f_modified));
+                            self.interact_row(ui, browser, id(4), &item, None);
+                        });
+                        row.col(|ui| {
+                            paint_selection(ui, selected, focused);
+                            let btn = egui::Button::new("ℹ").small();
+                            let rsp = ui.add(btn).on_hover_text("Properties");
+                            if rsp.clicked() {
+                                self.tx
+                                    .send(WebUiMessage::ActProperties(item.clone()))
+                                    .unwrap();
+                            } else {
+                                self.interact_row(ui, browser, id(5), &item, Some(rsp));
+                            }
+                        });
+                    }
+                });
+            });
+    }
+
+    /// A column header of the file table, which sorts by the column when
+    /// clicked, or reverses the order if already sorted by it.
+    fn view_sort_header(&self, ui: &mut egui::Ui, text: &str, sort_by: SortBy) {
+        let mut text = text.to_string();
+        if self.settings.sort_by == sort_by {
+            let arrow = match self.settings.sort_descending {
+                true => "⏷",
+                false => "⏶",
+            };
+            text = format!("{} {}", text, arrow);
+        }
+
+        let label =
+            egui::Label::new(egui::RichText::new(text).heading()).sense(egui::Sense::click());
+        if ui.add(label).clicked() {
+            let mut settings = self.settings.clone();
+            if settings.sort_by == sort_by {
+                settings.sort_descending = settings.sort_descending == false;
+            } else {
+                settings.sort_by = sort_by;
+                settings.sort_descending = false;
+            }
+            self.tx.send(WebUiMessage::SetSettings(settings)).unwrap();
+        }
+    }
+
+    /// The file grid of a pane, with large icons or thumbnails. Only the
+    /// visible rows are drawn.
+    fn view_file_grid(
+        &self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        browser: &Browser,
+        active: bool,
+    ) {
+        let entries = match &browser.filelist {
+            Some(v) => &v.entries,
+            None => return,
+        };
+
+        let columns = ((ui.available_width() / GRID_CELL_SIZE.x) as usize).max(1);
+        let rows = entries.len().div_ceil(columns);
+        let row_height = GRID_CELL_SIZE.y + ui.spacing().item_spacing.y;
+        ctx.data_mut(|d| d.insert_temp(browser.temp_id(GRID_COLUMNS_ID), columns));
+
+        let mut scroll = egui::ScrollArea::vertical().auto_shrink([false, false]);
+
+        // Restore the scroll position once, after going back.
+        let restore_id = browser.temp_id(RESTORE_SCROLL_ID);
+        if let Some(offset) = ctx.data(|d| d.get_temp::<f32>(restore_id)) {
+            ctx.data_mut(|d| d.remove::<f32>(restore_id));
+            scroll = scroll.vertical_scroll_offset(offset);
+        }
+
+        // Follow the gamepad and keyboard cursor, scrolling as little as
+        // possible.
+        let scroll_to_row_id = browser.temp_id(SCROLL_TO_ROW_ID);
+        if let Some(idx) = ctx.data(|d| d.get_temp::<usize>(scroll_to_row_id)) {
+            ctx.data_mut(|d| d.remove::<usize>(scroll_to_row_id));
+            let offset = ctx
+                .data(|d| d.get_temp::<f32>(browser.temp_id(SCROLL_OFFSET_ID)))
+                .unwrap_or_default();
+            let top = (idx / columns) as f32 * row_height;
+            let bottom = top + row_height;
+            let height = ui.available_height();
+            if top < offset {
+                scroll = scroll.vertical_scroll_offset(top);
+            } else if bottom > offset + height {
+                scroll = scroll.vertical_scroll_offset(bottom - height);
+            }
+        }
+
+        let output = scroll.show_rows(ui, GRID_CELL_SIZE.y, rows, |ui, range| {
+            for row in range {
+                ui.horizontal(|ui| {
+                    ui.spacing_mut().item_spacing.x = 0.0;
+                    for (idx, item) in entries.iter().enumerate().skip(row * columns).take(columns)
+                    {
+                        let (rect, _) =
+                            ui.allocate_exact_size(GRID_CELL_SIZE, egui::Sense::hover());
+                        let layout = egui::Layout::top_down(egui::Align::Center);
+                        let mut ui = ui.child_ui(rect, layout);
+                        self.view_grid_cell(ctx, &mut ui, browser, active, idx, item);
+                    }
+                });
+            }
+        });
+
+        // Remember the scroll position for the navigation history.
+        let offset = output.state.offset.y;
+        ctx.data_mut(|d| d.insert_temp(browser.temp_id(SCROLL_OFFSET_ID), offset));
+    }
+
+    /// A cell of the file grid. A double-click opens the file.
+    ///
+    /// # Arguments
+    /// + `idx`: the index of the file in the directory.
+    fn view_grid_cell(
+        &self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        browser: &Browser,
+        active: bool,
+        idx: usize,
+        item: &crate::protocol::ReaddirResponseItem,
+    ) {
+        let selected = browser.selected.contains(&item.f_path);
+        let focused =
+            self.show_cursor && active && self.pane == Pane::Files && browser.cursor == idx;
+
+        let rect = ui.max_rect().shrink(2.0);
+        if selected {
+            ui.painter()
+                .rect_filled(rect, 4.0, ui.visuals().selection.bg_fill);
+        }
+        if focused {
+            ui.painter()
+                .rect_stroke(rect, 4.0, ui.visuals().selection.stroke);
+            let pos = rect.left_bottom();
+            ctx.data_mut(|d| d.insert_temp(browser.temp_id(CURSOR_POS_ID), pos));
+        }
+
+        // Thumbnails are fetched once the cell is visible.
+        let mut thumbnail = None;
+        if self.settings.show_thumbnails && has_thumbnail(item) {
+            match self.thumbnails.get(&item.f_path) {
+                Some(Some(data)) if data.is_empty() == false => thumbnail = Some(data.clone()),
+                Some(_) => {}
+                None => {
+                    self.tx
+                        .send(WebUiMessage::ActFileThumbnail(item.f_path.clone()))
+                        .unwrap();
+                }
+            }
+        }
+
+        ui.add_space(4.0);
+        let size = egui::vec2(GRID_CELL_SIZE.x - 16.0, 64.0);
+        match thumbnail {
+            Some(data) => {
+                let image = egui::Image::from_bytes(
+                    format!("bytes://{}", item.f_path),
+                    egui::load::Bytes::Shared(data),
+                )
+                .fit_to_exact_size(size);
+                ui.add_sized(size, image);
+            }
+            None => {
+                let icon = egui::RichText::new(file_icon(item)).size(48.0);
+                ui.add_sized(size, egui::Label::new(icon));
+            }
+        }
+
+        let text = match &item.f_label {
+            Some(v) => v.clone(),
+            None => item.f_name.clone(),
+        };
+        ui.add(egui::Label::new(text.as_str()).truncate(true));
+
+        let id = egui::Id::new(("file_cell", browser.id, idx));
+        let rsp = ui
+            .interact(ui.max_rect(), id, egui::Sense::click())
+            .on_hover_text(text);
+        if rsp.double_clicked() {
+            match item.f_type == "DIR" {
+                true => self.cd(ctx, item.f_path.as_str()),
+                false => self
+                    .tx
+                    .send(WebUiMessage::ActProperties(item.clone()))
+                    .unwrap(),
+            }
+        } else if rsp.middle_clicked() && item.f_type == "DIR" {
+            self.tx
+                .send(WebUiMessage::ActNewTab(item.f_path.clone()))
+                .unwrap();
+        }
+        self.interact_row(ui, browser, id, item, Some(rsp));
+    }
+
+    /// Handle clicks on a cell of a file row. A click changes the selection,
+    /// and a right-click or a long-press on a touch screen opens the context
+    /// menu.
+    ///
+    /// # Arguments
+    /// + `browser`: the browser of the file table.
+    /// + `id`: the unique id of the cell.
+    /// + `item`: the file of the row.
+    /// + `inner`: the response of a clickable widget inside the cell, which
+    ///   takes the clicks of the cell.
+    fn interact_row(
+        &self,
+        ui: &mut egui::Ui,
+        browser: &Browser,
+        id: egui::Id,
+        item: &crate::protocol::ReaddirResponseItem,
+        inner: Option<egui::Response>,
+    ) {
+        let mut rsp = ui.interact(ui.max_rect(), id, egui::Sense::click());
+        if let Some(inner) = inner {
+            rsp = rsp.union(inner);
+        }
+
+        if rsp.clicked() {
+            let modifiers = ui.input(|i| i.modifiers);
+            let selection = match (modifiers.command, modifiers.shift) {
+                (false, false) => Selection::Only,
+                (true, false) => Selection::Toggle,
+                (false, true) => Selection::Range,
+                (true, true) => Selection::AddRange,
+            };
+            self.tx
+                .send(WebUiMessage::ActSelect((item.f_path.clone(), selection)))
+                .unwrap();
+            return;
+        }
+
+        if rsp.secondary_clicked() || long_pressed(ui, &rsp) {
+            // A file outside the selection becomes the selection.
+            if browser.selected.contains(&item.f_path) == false {
+                self.tx
+                    .send(WebUiMessage::ActSelect((
+                        item.f_path.clone(),
+                        Selection::Only,
+                    )))
+                    .unwrap();
+            }
+
+            let pos = rsp.interact_pointer_pos().unwrap_or(rsp.rect.center());
+            self.tx
+                .send(WebUiMessage::ShowContextMenu((Some(item.clone()), pos)))
+                .unwrap();
+        }
+    }
+
+    /// Handle a gamepad or keyboard action, in the context menu if open, or
+    /// in the focused pane.
+    pub(super) fn navigate(&mut self, ctx: &egui::Context, action: crate::input::Action) {
+        self.show_cursor = true;
+
+        if let Some(menu) = &mut self.context_menu {
+            let last = ctx
+                .data(|d| d.get_temp::<usize>(egui::Id::new(MENU_ITEMS_ID)))
+                .unwrap_or_default()
+                .saturating_sub(1);
+            match action {
+                crate::input::Action::Up => menu.cursor = menu.cursor.saturating_sub(1),
+                crate::input::Action::Down => menu.cursor = (menu.cursor + 1).min(last),
+                crate::input::Action::First => menu.cursor = 0,
+                crate::input::Action::Last => menu.cursor = last,
+                crate::input::Action::Open => menu.activate = true,
+                crate::input::Action::Parent | crate::input::Action::ContextMenu => {
+                    self.context_menu = None;
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // The side panel, then the file table of each pane.
+        let stops = match self.settings.split {
+            true => 1 + self.panes.len(),
+            false => 2,
+        };
+        let stop = match self.pane {
+            Pane::Places => 0,
+            Pane::Files => 1 + self.active,
+        };
+        let stop = match action {
+            crate::input::Action::PreviousPane => Some((stop + stops - 1) % stops),
+            crate::input::Action::NextPane => Some((stop + 1) % stops),
+            _ => None,
+        };
+
+        // The hidden side panel is skipped.
+        let stop = match (stop, self.settings.show_side_panel) {
+            (Some(0), false) if action == crate::input::Action::PreviousPane => Some(stops - 1),
+            (Some(0), false) => Some(1),
+            (v, _) => v,
+        };
+        match stop {
+            Some(0) => self.pane = Pane::Places,
+            Some(v) => {
+                self.pane = Pane::Files;
+                self.active = v - 1;
+            }
+            None => match self.pane {
+                Pane::Places => self.navigate_places(ctx, action),
+                Pane::Files => self.navigate_files(ctx, action),
+            },
+        }
+    }
+
+    /// Handle a gamepad or keyboard action in the file table.
+    fn navigate_files(&mut self, ctx: &egui::Context, action: crate::input::Action) {
+        if action == crate::input::Action::Parent {
+            self.tx.send(WebUiMessage::ActUp).unwrap();
+            return;
+        }
+
+        let browser = self.browser();
+        let entries = match &browser.filelist {
+            Some(v) if v.entries.is_empty() == false => &v.entries,
+            _ => return,
+        };
+        let last = entries.len() - 1;
+        let cursor = browser.cursor.min(last);
+        let item = entries[cursor].clone();
+        let cursor_pos_id = browser.temp_id(CURSOR_POS_ID);
+        let scroll_to_row_id = browser.temp_id(SCROLL_TO_ROW_ID);
+
+        // Up and down move by a row of the grid.
+        let step = match self.settings.view_mode {
+            ViewMode::List => 1,
+            ViewMode::Grid => ctx
+                .data(|d| d.get_temp::<usize>(browser.temp_id(GRID_COLUMNS_ID)))
+                .unwrap_or(1),
+        };
+
+        let moved = match action {
+            crate::input::Action::Up => Some(cursor.saturating_sub(step)),
+            crate::input::Action::Down => Some((cursor + step).min(last)),
+            crate::input::Action::PageUp => Some(cursor.saturating_sub(PAGE_ROWS * step)),
+            crate::input::Action::PageDown => Some((cursor + PAGE_ROWS * step).min(last)),
+            crate::input::Action::First => Some(0),
+            crate::input::Action::Last => Some(last),
+            _ => None,
+        };
+        if let Some(row) = moved {
+            self.browser_mut().cursor = row;
+            ctx.data_mut(|d| d.insert_temp(scroll_to_row_id, row));
+            return;
+        }
+
+        match action {
+            crate::input::Action::Open if item.f_type == "DIR" => {
+                self.cd(ctx, item.f_path.as_str());
+            }
+            crate::input::Action::Open => {
+                self.tx.send(WebUiMessage::ActProperties(item)).unwrap();
+            }
+            crate::input::Action::ContextMenu => {
+                // Same as a right-click on the row.
+                if self.browser().selected.contains(&item.f_path) == false {
+                    self.tx
+                        .send(WebUiMessage::ActSelect((
+                            item.f_path.clone(),
+                            Selection::Only,
+                        )))
+                        .unwrap();
+                }
+                let pos = ctx
+                    .data(|d| d.get_temp::<egui::Pos2>(cursor_pos_id))
+                    .unwrap_or(ctx.screen_rect().center());
+                self.tx
+                    .send(WebUiMessage::ShowContextMenu((Some(item), pos)))
+                    .unwrap();
+            }
+            crate::input::Action::ToggleSelection => {
+                self.tx
+                    .send(WebUiMessage::ActSelect((item.f_path, Selection::Toggle)))
+                    .unwrap();
+            }
+            _ => {}
+        }
+    }
+
+    /// Fetch the thumbnail of an image in the grid, which is the image itself.
+    pub(super) fn fetch_file_thumbnail(&self, ctx: &egui::Context, path: String) {
+        let url = format!(
+            "/api/download?{}",
+            url::form_urlencoded::Serializer::new(String::new())
+                .append_pair("path", path.as_str())
+                .finish()
+        );
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.get(url.as_str(), move |rsp| {
+            tx.send(WebUiMessage::SetFileThumbnail((path, rsp)))
+                .unwrap();
+            ctx.request_repaint();
+        });
+    }
+}
+
+/// Whether a widget is long-pressed on a touch screen. Only true once per
+/// press.
+fn long_pressed(ui: &egui::Ui, rsp: &egui::Response) -> bool {
+    // A shorter press is a click in egui.
+    const LONG_PRESS_SECS: f64 = 0.6;
+
+    if rsp.is_pointer_button_down_on() == false {
+        return false;
+    }
+
+    let (touch, dragging, start, now) = ui.input(|i| {
+        (
+            i.any_touches(),
+            i.pointer.is_decidedly_dragging(),
+            i.pointer.press_start_time(),
+            i.time,
+        )
+    });
+    let start = match start {
+        Some(v) if touch && dragging == false => v,
+        _ => return false,
+    };
+
+    let elapsed = now - start;
+    if elapsed < LONG_PRESS_SECS {
+        ui.ctx()
+            .request_repaint_after(std::time::Duration::from_secs_f64(
+                LONG_PRESS_SECS - elapsed,
+            ));
+        return false;
+    }
+
+    let key = egui::Id::new("long_press");
+    let handled = ui.ctx().data_mut(|d| {
+        let handled = d.get_temp::<f64>(key) == Some(start);
+        d.insert_temp(key, start);
+        handled
+    });
+    return handled == false;
+}
+
+/// Highlight a cell of a selected row, and outline a cell of the row under
+/// the gamepad and keyboard cursor.
+fn paint_selection(ui: &mut egui::Ui, selected: bool, focused: bool) {
+    let rect = ui.max_rect().expand2(ui.spacing().item_spacing * 0.5);
+    if selected {
+        ui.painter()
+            .rect_filled(rect, 0.0, ui.visuals().selection.bg_fill);
+    }
+    if focused {
+        let stroke = ui.visuals().selection.stroke;
+        ui.painter().hline(rect.x_range(), rect.top(), stroke);
+        ui.painter().hline(rect.x_range(), rect.bottom(), stroke);
+    }
+}
+
+/// An icon of a file, by its type.
+fn file_icon(item: &crate::protocol::ReaddirResponseItem) -> &str {
+    if let Some(icon) = &item.f_icon {
+        return icon.as_str();
+    }
+    if item.f_type == "DIR" {
+        return "📁";
+    }
+
+    let ext = std::path::Path::new(&item.f_name)
+        .extension()
+        .map(|v| v.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let mime = item.f_mime.clone().unwrap_or_default();
+
+    if ext == "appimage" {
+        return "🚀";
+    }
+    if ROM_EXTENSIONS.contains(&ext.as_str()) {
+        return "🎮";
+    }
+    if ARCHIVE_EXTENSIONS.contains(&ext.as_str()) {
+        return "📦";
+    }
+    if item.f_executable {
+        return "⚙";
+    }
+
+    return match mime.split('/').next().unwrap_or_default() {
+        "image" => "🖼",
+        "audio" => "🎵",
+        "video" => "🎞",
+        "text" => "📝",
+        _ if mime.ends_with("json") || mime.ends_with("xml") => "📝",
+        _ => "📒",
+    };
+}
+
+/// Whether a file is an image small enough to show as its own thumbnail.
+fn has_thumbnail(item: &crate::protocol::ReaddirResponseItem) -> bool {
+    let decodable = matches!(item.f_mime.as_deref(), Some("image/png" | "image/jpeg"));
+    return decodable && item.f_size <= MAX_THUMBNAIL_BYTES;
+}
+
+fn powered_by_egui_and_eframe(ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.spacing_mut().item_spacing.x = 0.0;
+        ui.label("Powered by ");
+        ui.hyperlink_to("egui", "https://github.com/emilk/egui");
+        ui.label(" and ");
+        ui.hyperlink_to(
+            "eframe",
+            "https://github.com/emilk/egui/tree/master/crates/eframe",
+        );
+        ui.label(".");
+    });
+}
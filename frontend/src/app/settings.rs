@@ -0,0 +1,162 @@
+use super::{sort_entries, Pane, Settings, SortBy, ViewMode, WebUI, WebUiMessage};
+
+impl WebUI {
+    pub(super) fn view_settings(&self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut settings = self.settings.clone();
+
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                egui::Grid::new("settings_grid")
+                    .num_columns(2)
+                    .spacing([16.0, 8.0])
+                    .show(ui, |ui| {
+                        ui.label("Theme");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut settings.dark_mode, true, "🌙 Dark");
+                            ui.radio_value(&mut settings.dark_mode, false, "☀ Light");
+                        });
+                        ui.end_row();
+
+                        ui.label("Sort by");
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_source("settings_sort_by")
+                                .selected_text(format!("{:?}", settings.sort_by))
+                                .show_ui(ui, |ui| {
+                                    for sort_by in
+                                        [SortBy::Name, SortBy::Type, SortBy::Size, SortBy::Modified]
+                                    {
+                                        let text = format!("{:?}", sort_by);
+                                        ui.selectable_value(&mut settings.sort_by, sort_by, text);
+                                    }
+                                });
+                            ui.checkbox(&mut settings.sort_descending, "Descending");
+                        });
+                        ui.end_row();
+
+                        ui.label("Files");
+                        ui.checkbox(&mut settings.show_hidden, "Show hidden files");
+                        ui.end_row();
+
+                        ui.label("View");
+                        ui.horizontal(|ui| {
+                            ui.radio_value(&mut settings.view_mode, ViewMode::List, "☰ List");
+                            ui.radio_value(&mut settings.view_mode, ViewMode::Grid, "⊞ Grid");
+                            ui.checkbox(&mut settings.show_thumbnails, "Thumbnails");
+                        });
+                        ui.end_row();
+
+                        ui.label("Layout");
+                        ui.horizontal(|ui| {
+                            ui.checkbox(&mut settings.split, "Two panes");
+                            ui.checkbox(&mut settings.show_side_panel, "Side panel");
+                        });
+                        ui.end_row();
+                    });
+
+                ui.separator();
+
+                ui.collapsing("Keys and gamepad buttons", |ui| {
+                    egui::Grid::new("settings_bindings_grid")
+                        .num_columns(2)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for action in crate::input::ACTIONS {
+                                ui.label(format!("{:?}", action));
+                                ui.horizontal(|ui| {
+                                    let mut remove = None;
+                                    let bindings = settings.bindings.bindings.iter().enumerate();
+                                    for (idx, (input, _)) in
+                                        bindings.filter(|(_, (_, v))| *v == action)
+                                    {
+                                        let btn =
+                                            egui::Button::new(input_label(ctx, input)).small();
+                                        if ui.add(btn).on_hover_text("Remove").clicked() {
+                                            remove = Some(idx);
+                                        }
+                                    }
+                                    if let Some(idx) = remove {
+                                        settings.bindings.bindings.remove(idx);
+                                    }
+
+                                    if self.rebinding == Some(action) {
+                                        ui.label("Press a key or button, Esc to cancel");
+                                    } else {
+                                        let btn = egui::Button::new("➕").small();
+                                        let rsp = ui.add(btn).on_hover_text("Add a key or button");
+                                        if rsp.clicked() {
+                                            self.tx
+                                                .send(WebUiMessage::ActRebind(Some(action)))
+                                                .unwrap();
+                                        }
+                                    }
+                                });
+                                ui.end_row();
+                            }
+                        });
+                    if ui.button("Reset bindings").clicked() {
+                        settings.bindings = crate::input::Bindings::default();
+                    }
+                });
+
+                ui.separator();
+
+                if ui.button("Reset all settings").clicked() {
+                    settings = Settings::default();
+                }
+            });
+
+        if settings != self.settings {
+            self.tx.send(WebUiMessage::SetSettings(settings)).unwrap();
+        }
+        if open == false {
+            self.tx.send(WebUiMessage::ShowSettings(false)).unwrap();
+        }
+    }
+
+    /// Apply changed settings.
+    ///
+    /// # Arguments
+    /// + `old`: the settings before the change.
+    pub(super) fn apply_settings(&mut self, ctx: &egui::Context, old: &Settings) {
+        if self.settings.dark_mode != old.dark_mode {
+            ctx.set_visuals(match self.settings.dark_mode {
+                true => egui::Visuals::dark(),
+                false => egui::Visuals::light(),
+            });
+        }
+
+        if self.settings.split == false {
+            self.active = 0;
+        }
+        if self.settings.show_side_panel == false && self.pane == Pane::Places {
+            self.pane = Pane::Files;
+        }
+
+        // Hidden files are dropped from listings, so list again.
+        if self.settings.show_hidden != old.show_hidden {
+            self.refresh(ctx);
+        } else if (self.settings.sort_by, self.settings.sort_descending)
+            != (old.sort_by, old.sort_descending)
+        {
+            for browser in self.panes.iter_mut().flat_map(|v| v.browsers.iter_mut()) {
+                if let Some(filelist) = &mut browser.filelist {
+                    sort_entries(&mut filelist.entries, &self.settings);
+                }
+            }
+        }
+    }
+}
+
+/// The text of a key with its modifiers, or of a gamepad button.
+fn input_label(ctx: &egui::Context, input: &crate::input::Input) -> String {
+    return match input {
+        crate::input::Input::Key { key, modifiers } => {
+            ctx.format_shortcut(&egui::KeyboardShortcut::new(*modifiers, *key))
+        }
+        crate::input::Input::Button(button) => format!("🎮 {:?}", button),
+    };
+}
@@ -0,0 +1,295 @@
+use super::{format_size, Pane, WebUI, WebUiMessage, PLACES};
+
+impl WebUI {
+    pub(super) fn view_side_panel(&self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        // The index of each entry for the gamepad and keyboard cursor, in the
+        // order of [WebUI::place_paths].
+        let mut idx = 0;
+        let mut focused = || {
+            let ret = self.show_cursor && self.pane == Pane::Places && self.place_cursor == idx;
+            idx += 1;
+            return ret;
+        };
+
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            ui.heading("Places");
+
+            for ((_, label), path) in PLACES.iter().zip(self.places.iter()) {
+                if let Some(path) = path {
+                    self.view_place(ctx, ui, label, path, focused())
+                        .on_hover_text(path);
+                }
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.heading("Bookmarks");
+                let cwd = self.browser().cwd.clone();
+                let btn = egui::Button::new("➕").small();
+                let rsp = ui
+                    .add_enabled(cwd.is_some(), btn)
+                    .on_hover_text("Bookmark this folder");
+                if rsp.clicked() {
+                    self.tx
+                        .send(WebUiMessage::ActAddBookmark(cwd.unwrap()))
+                        .unwrap();
+                }
+            });
+
+            if self.bookmarks.is_empty() {
+                ui.weak("No bookmarks");
+            }
+
+            let last = self.bookmarks.len().saturating_sub(1);
+            for (pos, bookmark) in self.bookmarks.iter().enumerate() {
+                let text = format!("🔖 {}", bookmark.name);
+                let rsp = self
+                    .view_place(ctx, ui, text.as_str(), &bookmark.path, focused())
+                    .on_hover_text(bookmark.path.as_str());
+                rsp.context_menu(|ui| {
+                    let path = bookmark.path.clone();
+                    if ui
+                        .add_enabled(pos > 0, egui::Button::new("⏶ Move up"))
+                        .clicked()
+                    {
+                        self.tx
+                            .send(WebUiMessage::ActMoveBookmark((path.clone(), pos - 1)))
+                            .unwrap();
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(pos < last, egui::Button::new("⏷ Move down"))
+                        .clicked()
+                    {
+                        self.tx
+                            .send(WebUiMessage::ActMoveBookmark((path.clone(), pos + 1)))
+                            .unwrap();
+                        ui.close_menu();
+                    }
+                    if ui.button("✖ Remove bookmark").clicked() {
+                        self.tx
+                            .send(WebUiMessage::ActRemoveBookmark(path.clone()))
+                            .unwrap();
+                        ui.close_menu();
+                    }
+                });
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.heading("Volumes");
+                if ui.small_button("🔃").on_hover_text("Refresh").clicked() {
+                    self.tx.send(WebUiMessage::ActVolumes).unwrap();
+                }
+            });
+
+            for volume in self.volumes.iter() {
+                let icon = if volume.removable { "💾" } else { "🖴" };
+                let mut text = format!("{} {}", icon, volume.label);
+                if volume.read_only {
+                    text = format!("{} 🔒", text);
+                }
+
+                self.view_place(ctx, ui, text.as_str(), &volume.mount_point, focused())
+                    .on_hover_text(format!(
+                        "{}\n{} ({})",
+                        volume.mount_point, volume.device, volume.fs_type
+                    ));
+
+                let fraction = if volume.total > 0 {
+                    volume.used as f32 / volume.total as f32
+                } else {
+                    0.0
+                };
+                ui.add(egui::ProgressBar::new(fraction).text(format!(
+                    "{} free of {}",
+                    format_size(volume.free),
+                    format_size(volume.total)
+                )));
+                ui.add_space(4.0);
+            }
+
+            ui.separator();
+
+            let rsp = ui.selectable_label(self.trash.is_some(), "🗑 Trash");
+            if focused() {
+                paint_cursor(ui, rsp.rect);
+            }
+            if rsp.clicked() {
+                self.tx.send(WebUiMessage::ActTrash).unwrap();
+            }
+        });
+    }
+
+    /// An entry of the side panel, which opens its folder when clicked.
+    /// Callers add the hover text.
+    ///
+    /// # Arguments
+    /// + `focused`: whether the gamepad and keyboard cursor is on the entry.
+    fn view_place(
+        &self,
+        ctx: &egui::Context,
+        ui: &mut egui::Ui,
+        text: &str,
+        path: &String,
+        focused: bool,
+    ) -> egui::Response {
+        let selected = self.browser().cwd.as_ref() == Some(path);
+        let rsp = ui.selectable_label(selected, text);
+        if focused {
+            paint_cursor(ui, rsp.rect);
+        }
+        if rsp.clicked() {
+            self.cd(ctx, path.as_str());
+        }
+        return rsp;
+    }
+
+    /// Fetch the paths of the XDG places in [PLACES].
+    pub(super) fn fetch_places(&self, ctx: &egui::Context) {
+        for (idx, (kind, _)) in PLACES.iter().enumerate() {
+            let ctx = ctx.clone();
+            let tx = self.tx.clone();
+            self.client.post(
+                crate::protocol::DirsRequest { kind: kind.clone() },
+                move |rsp: Result<crate::protocol::DirsResponse, String>| match rsp {
+                    Ok(v) => {
+                        tx.send(WebUiMessage::SetPlace((idx, v.path))).unwrap();
+                        ctx.request_repaint();
+                    }
+                    Err(e) => log::warn!("fetch place failed: {}", e),
+                },
+            );
+        }
+    }
+
+    /// Fetch bookmarks.
+    pub(super) fn fetch_bookmarks(&self, ctx: &egui::Context) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BookmarkListRequest {},
+            move |rsp: Result<crate::protocol::BookmarkListResponse, String>| match rsp {
+                Ok(v) => {
+                    tx.send(WebUiMessage::SetBookmarks(v.bookmarks)).unwrap();
+                    ctx.request_repaint();
+                }
+                Err(e) => log::warn!("fetch bookmarks failed: {}", e),
+            },
+        );
+    }
+
+    /// Bookmark a folder, named after the folder.
+    pub(super) fn add_bookmark(&self, ctx: &egui::Context, path: String) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BookmarkAddRequest {
+                path: path.clone(),
+                name: None,
+            },
+            move |rsp: Result<crate::protocol::BookmarkAddResponse, String>| {
+                match rsp {
+                    Ok(v) => tx.send(WebUiMessage::SetBookmarks(v.bookmarks)).unwrap(),
+                    Err(e) => {
+                        let status = format!("Bookmark {} failed: {}", path, e);
+                        tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                    }
+                }
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Remove the bookmark of a folder.
+    pub(super) fn remove_bookmark(&self, ctx: &egui::Context, path: String) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BookmarkRemoveRequest { path: path.clone() },
+            move |rsp: Result<crate::protocol::BookmarkRemoveResponse, String>| {
+                match rsp {
+                    Ok(v) => tx.send(WebUiMessage::SetBookmarks(v.bookmarks)).unwrap(),
+                    Err(e) => {
+                        let status = format!("Remove bookmark {} failed: {}", path, e);
+                        tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                    }
+                }
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Move the bookmark of a folder to another position.
+    pub(super) fn move_bookmark(&self, ctx: &egui::Context, path: String, index: usize) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::BookmarkMoveRequest {
+                path: path.clone(),
+                index: index,
+            },
+            move |rsp: Result<crate::protocol::BookmarkMoveResponse, String>| {
+                match rsp {
+                    Ok(v) => tx.send(WebUiMessage::SetBookmarks(v.bookmarks)).unwrap(),
+                    Err(e) => {
+                        let status = format!("Move bookmark {} failed: {}", path, e);
+                        tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                    }
+                }
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Fetch mounted volumes.
+    pub(super) fn fetch_volumes(&self, ctx: &egui::Context) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::VolumesRequest {},
+            move |rsp: Result<crate::protocol::VolumesResponse, String>| match rsp {
+                Ok(v) => {
+                    tx.send(WebUiMessage::SetVolumes(v.volumes)).unwrap();
+                    ctx.request_repaint();
+                }
+                Err(e) => log::warn!("fetch volumes failed: {}", e),
+            },
+        );
+    }
+
+    /// The folders of the side panel entries, in the order they are shown.
+    fn place_paths(&self) -> Vec<String> {
+        let places = self.places.iter().filter_map(|v| v.clone());
+        let bookmarks = self.bookmarks.iter().map(|v| v.path.clone());
+        let volumes = self.volumes.iter().map(|v| v.mount_point.clone());
+        return places.chain(bookmarks).chain(volumes).collect();
+    }
+
+    /// Handle a gamepad or keyboard action in the side panel.
+    pub(super) fn navigate_places(&mut self, ctx: &egui::Context, action: crate::input::Action) {
+        // Places, bookmarks and volumes, then the trash.
+        let paths = self.place_paths();
+        let last = paths.len();
+
+        match action {
+            crate::input::Action::Up => self.place_cursor = self.place_cursor.saturating_sub(1),
+            crate::input::Action::Down => self.place_cursor = (self.place_cursor + 1).min(last),
+            crate::input::Action::First => self.place_cursor = 0,
+            crate::input::Action::Last => self.place_cursor = last,
+            crate::input::Action::Open => match paths.get(self.place_cursor) {
+                Some(path) => self.cd(ctx, path.as_str()),
+                None => self.tx.send(WebUiMessage::ActTrash).unwrap(),
+            },
+            _ => {}
+        }
+    }
+}
+
+/// Outline a widget under the gamepad and keyboard cursor.
+fn paint_cursor(ui: &mut egui::Ui, rect: egui::Rect) {
+    ui.painter()
+        .rect_stroke(rect, 2.0, ui.visuals().selection.stroke);
+}
@@ -0,0 +1,812 @@
+use super::{
+    convert_epoch_to_local_time, format_size, AddShortcut, Cleanup, Prefixes, Screenshots, WebUI,
+    WebUiMessage,
+};
+
+/// Shown while Steam runs, as it overwrites `shortcuts.vdf` when it exits.
+const STEAM_RUNNING_WARNING: &str =
+    "⚠ Steam is running and will undo changes to non-Steam games when it exits, close Steam first";
+
+impl WebUI {
+    pub(super) fn view_steam_apps(&self, ctx: &egui::Context) {
+        let mut open = true;
+
+        egui::Window::new("Steam games")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([960.0, 600.0])
+            .show(ctx, |ui| {
+                let rsp = match &self.steam_apps {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.spinner();
+                        return;
+                    }
+                };
+
+                let root = match &rsp.root {
+                    Some(v) => v,
+                    None => {
+                        ui.label("Steam is not installed.");
+                        return;
+                    }
+                };
+
+                ui.horizontal(|ui| {
+                    let btn = egui::Button::new(format!("📁 {}", root));
+                    if ui.add(btn).on_hover_text("Open Steam folder").clicked() {
+                        self.cd(ctx, root.as_str());
+                    }
+
+                    let btn = egui::Button::new("🧹 Clean up");
+                    let rsp = ui.add(btn).on_hover_text("Find data of uninstalled games");
+                    if rsp.clicked() {
+                        self.tx.send(WebUiMessage::ActCleanup).unwrap();
+                    }
+
+                    let mut filter = self.steam_apps_filter.clone();
+                    let edit = egui::TextEdit::singleline(&mut filter).hint_text("🔍 Filter");
+                    if ui.add(edit).changed() {
+                        self.tx
+                            .send(WebUiMessage::SetSteamAppsFilter(filter))
+                            .unwrap();
+                    }
+                });
+
+                ui.separator();
+
+                let filter = self.steam_apps_filter.to_lowercase();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("steam_apps_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for app in rsp.apps.iter() {
+                                if app.name.to_lowercase().contains(filter.as_str()) == false {
+                                    continue;
+                                }
+
+                                let label =
+                                    egui::Label::new(app.name.as_str()).sense(egui::Sense::click());
+                                let rsp = ui
+                                    .add(label)
+                                    .on_hover_cursor(egui::CursorIcon::PointingHand)
+                                    .on_hover_text(app.install_dir.as_str());
+                                if rsp.clicked() {
+                                    self.cd(ctx, app.install_dir.as_str());
+                                }
+
+                                ui.label(app.appid.to_string());
+                                ui.label(format_size(app.size_on_disk));
+                                ui.label(app.library.as_str());
+                                ui.end_row();
+                            }
+                        });
+
+                    let shortcuts = match &self.steam_shortcuts {
+                        Some(Ok(v)) => v,
+                        _ => return,
+                    };
+                    if shortcuts.shortcuts.is_empty() {
+                        return;
+                    }
+
+                    ui.separator();
+                    ui.heading("Non-Steam games");
+                    if shortcuts.steam_running {
+                        ui.colored_label(ui.visuals().warn_fg_color, STEAM_RUNNING_WARNING);
+                    }
+                    egui::Grid::new("steam_shortcuts_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for shortcut in shortcuts.shortcuts.iter() {
+                                if shortcut.name.to_lowercase().contains(filter.as_str()) == false {
+                                    continue;
+                                }
+
+                                ui.label(shortcut.name.as_str())
+                                    .on_hover_text(shortcut.exe.as_str());
+                                ui.label(shortcut.appid.to_string());
+                                ui.label(shortcut.launch_options.as_str());
+
+                                let btn = egui::Button::new("🗑");
+                                if ui.add(btn).on_hover_text("Remove from Steam").clicked() {
+                                    self.tx
+                                        .send(WebUiMessage::ActRemoveShortcut((
+                                            shortcut.user.clone(),
+                                            shortcut.appid,
+                                        )))
+                                        .unwrap();
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::ShowSteamApps(false)).unwrap();
+        }
+    }
+
+    pub(super) fn view_add_shortcut(&self, ctx: &egui::Context, add_shortcut: &AddShortcut) {
+        let mut open = true;
+        let form = &add_shortcut.form;
+
+        egui::Window::new("Add to Steam")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut new_form = form.clone();
+
+                egui::Grid::new("add_shortcut_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Program");
+                        ui.label(form.exe.as_str());
+                        ui.end_row();
+
+                        ui.label("Name");
+                        ui.text_edit_singleline(&mut new_form.name);
+                        ui.end_row();
+
+                        ui.label("Start in");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut new_form.start_dir)
+                                .hint_text("Folder of the program"),
+                        );
+                        ui.end_row();
+
+                        ui.label("Launch options");
+                        ui.text_edit_singleline(&mut new_form.launch_options);
+                        ui.end_row();
+
+                        // Only ask for the user if there is a choice.
+                        if let Some(Ok(shortcuts)) = &self.steam_shortcuts {
+                            if shortcuts.users.len() > 1 {
+                                ui.label("Steam user");
+                                let text =
+                                    form.user.clone().unwrap_or("Last logged in".to_string());
+                                egui::ComboBox::from_id_source("add_shortcut_user")
+                                    .selected_text(text)
+                                    .show_ui(ui, |ui| {
+                                        ui.selectable_value(
+                                            &mut new_form.user,
+                                            None,
+                                            "Last logged in",
+                                        );
+                                        for user in shortcuts.users.iter() {
+                                            ui.selectable_value(
+                                                &mut new_form.user,
+                                                Some(user.clone()),
+                                                user.as_str(),
+                                            );
+                                        }
+                                    });
+                                ui.end_row();
+                            }
+                        }
+                    });
+
+                if new_form.name != form.name
+                    || new_form.start_dir != form.start_dir
+                    || new_form.launch_options != form.launch_options
+                    || new_form.user != form.user
+                {
+                    self.tx
+                        .send(WebUiMessage::SetShortcutForm(new_form))
+                        .unwrap();
+                }
+
+                if let Some(Ok(shortcuts)) = &self.steam_shortcuts {
+                    if shortcuts.steam_running {
+                        ui.colored_label(ui.visuals().warn_fg_color, STEAM_RUNNING_WARNING);
+                    }
+                }
+
+                if let Some(e) = &add_shortcut.error {
+                    ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                }
+
+                ui.horizontal(|ui| {
+                    let btn = egui::Button::new("🚂 Add to Steam");
+                    if ui.add_enabled(add_shortcut.busy == false, btn).clicked() {
+                        self.tx.send(WebUiMessage::ActSaveShortcut).unwrap();
+                    }
+                    if add_shortcut.busy {
+                        ui.spinner();
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseAddShortcut).unwrap();
+        }
+    }
+
+    pub(super) fn view_cleanup(&self, ctx: &egui::Context, cleanup: &Cleanup) {
+        let mut open = true;
+
+        egui::Window::new("Steam cleanup")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                // Last step: the result of the removal.
+                if let Some(result) = &cleanup.result {
+                    match result {
+                        Ok(v) => {
+                            ui.label(format!("Moved {} folders to the trash.", v.trashed));
+                            for e in v.errors.iter() {
+                                ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                            }
+                        }
+                        Err(e) => {
+                            ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        }
+                    }
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Scan again").clicked() {
+                            self.tx.send(WebUiMessage::ActCleanup).unwrap();
+                        }
+                        if ui.button("Close").clicked() {
+                            self.tx.send(WebUiMessage::CloseCleanup).unwrap();
+                        }
+                    });
+                    return;
+                }
+
+                // First step: scanning.
+                let rsp = match &cleanup.orphans {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Looking for data of uninstalled games…");
+                        });
+                        return;
+                    }
+                };
+
+                if rsp.orphans.is_empty() {
+                    ui.label("Nothing to clean up.");
+                    return;
+                }
+
+                // Second step: review and select.
+                let total: u64 = rsp
+                    .orphans
+                    .iter()
+                    .filter(|v| cleanup.selected.contains(&v.path))
+                    .map(|v| v.size)
+                    .sum();
+
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} of {} selected, {}",
+                        cleanup.selected.len(),
+                        rsp.orphans.len(),
+                        format_size(total)
+                    ));
+
+                    if ui.button("Select all").clicked() {
+                        let paths = rsp.orphans.iter().map(|v| v.path.clone()).collect();
+                        self.tx
+                            .send(WebUiMessage::SetCleanupSelection(paths))
+                            .unwrap();
+                    }
+                    if ui.button("Select none").clicked() {
+                        self.tx
+                            .send(WebUiMessage::SetCleanupSelection(Vec::new()))
+                            .unwrap();
+                    }
+
+                    let btn = egui::Button::new("🗑 Move to trash");
+                    let enabled = cleanup.selected.is_empty() == false && cleanup.removing == false;
+                    if ui.add_enabled(enabled, btn).clicked() {
+                        self.tx.send(WebUiMessage::ActCleanupRemove).unwrap();
+                    }
+                    if cleanup.removing {
+                        ui.spinner();
+                    }
+                });
+
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("cleanup_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for orphan in rsp.orphans.iter() {
+                                let mut selected = cleanup.selected.contains(&orphan.path);
+                                let rsp = ui
+                                    .checkbox(&mut selected, orphan.appid.to_string())
+                                    .on_hover_text(orphan.path.as_str());
+                                if rsp.changed() {
+                                    self.tx
+                                        .send(WebUiMessage::SetCleanupSelected((
+                                            orphan.path.clone(),
+                                            selected,
+                                        )))
+                                        .unwrap();
+                                }
+
+                                ui.label(orphan.kind.as_str());
+                                ui.label(format_size(orphan.size));
+
+                                let btn = egui::Button::new("📁").small();
+                                if ui.add(btn).on_hover_text("Open folder").clicked() {
+                                    self.cd(ctx, orphan.path.as_str());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseCleanup).unwrap();
+        }
+    }
+
+    pub(super) fn view_screenshots(&self, ctx: &egui::Context, screenshots: &Screenshots) {
+        let mut open = true;
+
+        egui::Window::new("Steam screenshots")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([960.0, 600.0])
+            .show(ctx, |ui| {
+                let rsp = match &screenshots.games {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.spinner();
+                        return;
+                    }
+                };
+
+                if rsp.games.is_empty() {
+                    ui.label("No screenshots found.");
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} selected", screenshots.selected.len()));
+
+                    if ui.button("Select all").clicked() {
+                        let paths = rsp
+                            .games
+                            .iter()
+                            .flat_map(|v| v.screenshots.iter().map(|v| v.path.clone()))
+                            .collect();
+                        self.tx
+                            .send(WebUiMessage::SetScreenshotSelection(paths))
+                            .unwrap();
+                    }
+                    if ui.button("Select none").clicked() {
+                        self.tx
+                            .send(WebUiMessage::SetScreenshotSelection(Vec::new()))
+                            .unwrap();
+                    }
+
+                    let btn = egui::Button::new("📥 Download zip");
+                    let enabled = screenshots.selected.is_empty() == false
+                        && screenshots.downloading == false;
+                    if ui.add_enabled(enabled, btn).clicked() {
+                        self.tx.send(WebUiMessage::ActDownloadScreenshots).unwrap();
+                    }
+                    if screenshots.downloading {
+                        ui.spinner();
+                    }
+                });
+
+                ui.separator();
+
+                let size = egui::vec2(160.0, 90.0);
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for game in rsp.games.iter() {
+                        let name = match &game.name {
+                            Some(v) => format!("{} ({})", v, game.screenshots.len()),
+                            None => format!("{} ({})", game.gameid, game.screenshots.len()),
+                        };
+                        ui.heading(name);
+
+                        ui.horizontal_wrapped(|ui| {
+                            for screenshot in game.screenshots.iter() {
+                                let selected = screenshots.selected.contains(&screenshot.path);
+                                let thumbnail =
+                                    screenshot.thumbnail.as_ref().unwrap_or(&screenshot.path);
+
+                                let rsp = match screenshots.thumbnails.get(thumbnail) {
+                                    Some(Some(data)) if data.is_empty() == false => {
+                                        let image = egui::Image::from_bytes(
+                                            format!("bytes://{}", thumbnail),
+                                            egui::load::Bytes::Shared(data.clone()),
+                                        )
+                                        .fit_to_exact_size(size);
+                                        ui.add(egui::ImageButton::new(image).selected(selected))
+                                    }
+                                    _ => ui.add(
+                                        egui::Button::new("📷").min_size(size).selected(selected),
+                                    ),
+                                };
+
+                                let rsp = rsp.on_hover_text(format!(
+                                    "{}\n{}",
+                                    convert_epoch_to_local_time(screenshot.created),
+                                    format_size(screenshot.size)
+                                ));
+                                if rsp.clicked() {
+                                    self.tx
+                                        .send(WebUiMessage::SetScreenshotSelected((
+                                            screenshot.path.clone(),
+                                            selected == false,
+                                        )))
+                                        .unwrap();
+                                }
+                            }
+                        });
+
+                        ui.add_space(8.0);
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseScreenshots).unwrap();
+        }
+    }
+
+    pub(super) fn view_prefixes(&self, ctx: &egui::Context, prefixes: &Prefixes) {
+        let mut open = true;
+
+        egui::Window::new("Proton prefixes")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([960.0, 600.0])
+            .show(ctx, |ui| {
+                let list = match &prefixes.prefixes {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.spinner();
+                        return;
+                    }
+                };
+
+                if list.prefixes.is_empty() {
+                    ui.label("No Proton prefixes found.");
+                    return;
+                }
+
+                ui.columns(2, |columns| {
+                    egui::ScrollArea::vertical()
+                        .id_source("prefixes_list")
+                        .show(&mut columns[0], |ui| {
+                            for prefix in list.prefixes.iter() {
+                                let text = match &prefix.name {
+                                    Some(v) => format!("{} ({})", v, prefix.appid),
+                                    None => prefix.appid.to_string(),
+                                };
+                                let selected = prefixes.selected == Some(prefix.appid);
+                                if ui.selectable_label(selected, text).clicked() {
+                                    self.tx.send(WebUiMessage::ActPrefix(prefix.appid)).unwrap();
+                                }
+                            }
+                        });
+
+                    let ui = &mut columns[1];
+                    if prefixes.selected.is_none() {
+                        ui.label("Select a game to show its save locations.");
+                        return;
+                    }
+
+                    let rsp = match &prefixes.locations {
+                        Some(Ok(v)) => v,
+                        Some(Err(e)) => {
+                            ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                            return;
+                        }
+                        None => {
+                            ui.spinner();
+                            return;
+                        }
+                    };
+
+                    ui.heading(
+                        rsp.prefix
+                            .name
+                            .clone()
+                            .unwrap_or(rsp.prefix.appid.to_string()),
+                    );
+                    for location in rsp.locations.iter() {
+                        ui.horizontal(|ui| {
+                            let btn = egui::Button::new(format!("📁 {}", location.name));
+                            if ui.add(btn).on_hover_text(location.path.as_str()).clicked() {
+                                self.cd(ctx, location.path.as_str());
+                            }
+
+                            let btn = egui::Button::new("💾");
+                            let rsp = ui
+                                .add_enabled(self.backup_busy == false, btn)
+                                .on_hover_text("Back up this folder");
+                            if rsp.clicked() {
+                                self.tx
+                                    .send(WebUiMessage::ActBackup(location.path.clone()))
+                                    .unwrap();
+                            }
+                        });
+                    }
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::ClosePrefixes).unwrap();
+        }
+    }
+
+    /// Fetch installed Steam apps.
+    pub(super) fn fetch_steam_apps(&self, ctx: &egui::Context) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamAppsRequest {},
+            move |rsp: Result<crate::protocol::SteamAppsResponse, String>| {
+                tx.send(WebUiMessage::SetSteamApps(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Fetch non-Steam games.
+    pub(super) fn fetch_steam_shortcuts(&self, ctx: &egui::Context) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamShortcutsRequest {},
+            move |rsp: Result<crate::protocol::SteamShortcutsResponse, String>| {
+                tx.send(WebUiMessage::SetSteamShortcuts(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Submit the "Add to Steam" dialog.
+    pub(super) fn save_shortcut(&mut self, ctx: &egui::Context) {
+        let add_shortcut = match &mut self.add_shortcut {
+            Some(v) => v,
+            None => return,
+        };
+        add_shortcut.busy = true;
+        add_shortcut.error = None;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            add_shortcut.form.clone(),
+            move |rsp: Result<crate::protocol::SteamShortcutAddResponse, String>| {
+                tx.send(WebUiMessage::FinishAddShortcut(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Remove a non-Steam game.
+    ///
+    /// # Arguments
+    /// + `user`: the id of the Steam user owning the shortcut.
+    /// + `appid`: the app id of the shortcut.
+    pub(super) fn remove_shortcut(&self, ctx: &egui::Context, user: String, appid: u32) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamShortcutRemoveRequest {
+                user: user,
+                appid: appid,
+            },
+            move |rsp: Result<crate::protocol::SteamShortcutRemoveResponse, String>| {
+                let status = match rsp {
+                    Ok(_) => "Removed from Steam, restart Steam to see it".to_string(),
+                    Err(e) => format!("Remove from Steam failed: {}", e),
+                };
+                tx.send(WebUiMessage::SetStatus(status)).unwrap();
+                tx.send(WebUiMessage::ActSteamApps).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Open cleanup wizard and find orphaned folders.
+    pub(super) fn fetch_cleanup(&mut self, ctx: &egui::Context) {
+        self.cleanup = Some(Cleanup {
+            orphans: None,
+            selected: std::collections::HashSet::new(),
+            removing: false,
+            result: None,
+        });
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamCleanupRequest {},
+            move |rsp: Result<crate::protocol::SteamCleanupResponse, String>| {
+                tx.send(WebUiMessage::SetCleanup(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Remove the selected orphaned folders.
+    pub(super) fn cleanup_remove(&mut self, ctx: &egui::Context) {
+        let cleanup = match &mut self.cleanup {
+            Some(v) => v,
+            None => return,
+        };
+        cleanup.removing = true;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamCleanupRemoveRequest {
+                paths: cleanup.selected.iter().cloned().collect(),
+            },
+            move |rsp: Result<crate::protocol::SteamCleanupRemoveResponse, String>| {
+                tx.send(WebUiMessage::FinishCleanupRemove(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Open screenshots gallery and fetch all screenshots.
+    pub(super) fn fetch_screenshots(&mut self, ctx: &egui::Context) {
+        self.screenshots = Some(Screenshots {
+            games: None,
+            thumbnails: std::collections::HashMap::new(),
+            selected: std::collections::HashSet::new(),
+            downloading: false,
+        });
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamScreenshotsRequest {},
+            move |rsp: Result<crate::protocol::SteamScreenshotsResponse, String>| {
+                tx.send(WebUiMessage::SetScreenshots(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Fetch missing thumbnails of the screenshots gallery, a few at a time.
+    pub(super) fn fetch_thumbnails(&mut self, ctx: &egui::Context) {
+        const MAX_IN_FLIGHT: usize = 8;
+
+        let screenshots = match &mut self.screenshots {
+            Some(v) => v,
+            None => return,
+        };
+        let games = match &screenshots.games {
+            Some(Ok(v)) => v,
+            _ => return,
+        };
+
+        let mut in_flight = screenshots
+            .thumbnails
+            .values()
+            .filter(|v| v.is_none())
+            .count();
+
+        let paths = games.games.iter().flat_map(|v| {
+            v.screenshots
+                .iter()
+                .map(|v| v.thumbnail.clone().unwrap_or(v.path.clone()))
+        });
+        for path in paths {
+            if in_flight >= MAX_IN_FLIGHT {
+                break;
+            }
+            if screenshots.thumbnails.contains_key(&path) {
+                continue;
+            }
+
+            screenshots.thumbnails.insert(path.clone(), None);
+            in_flight += 1;
+
+            let url = format!(
+                "/api/steam/screenshot?{}",
+                url::form_urlencoded::Serializer::new(String::new())
+                    .append_pair("path", path.as_str())
+                    .finish()
+            );
+            let ctx = ctx.clone();
+            let tx = self.tx.clone();
+            self.client.get(url.as_str(), move |rsp| {
+                tx.send(WebUiMessage::SetThumbnail((path, rsp))).unwrap();
+                ctx.request_repaint();
+            });
+        }
+    }
+
+    /// Download the selected screenshots as zip.
+    pub(super) fn download_screenshots(&mut self, ctx: &egui::Context) {
+        let screenshots = match &mut self.screenshots {
+            Some(v) => v,
+            None => return,
+        };
+        screenshots.downloading = true;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post_raw(
+            crate::protocol::SteamScreenshotsZipRequest {
+                paths: screenshots.selected.iter().cloned().collect(),
+            },
+            move |rsp| {
+                tx.send(WebUiMessage::FinishDownloadScreenshots(rsp))
+                    .unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Open Proton prefixes view and fetch all prefixes.
+    pub(super) fn fetch_prefixes(&mut self, ctx: &egui::Context) {
+        self.prefixes = Some(Prefixes {
+            prefixes: None,
+            selected: None,
+            locations: None,
+        });
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamPrefixesRequest {},
+            move |rsp: Result<crate::protocol::SteamPrefixesResponse, String>| {
+                tx.send(WebUiMessage::SetPrefixes(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Select a Proton prefix and fetch its locations.
+    ///
+    /// # Arguments
+    /// + `appid`: the app id of the game.
+    pub(super) fn fetch_prefix(&mut self, ctx: &egui::Context, appid: u32) {
+        let prefixes = match &mut self.prefixes {
+            Some(v) => v,
+            None => return,
+        };
+        prefixes.selected = Some(appid);
+        prefixes.locations = None;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::SteamPrefixRequest { appid: appid },
+            move |rsp: Result<crate::protocol::SteamPrefixResponse, String>| {
+                tx.send(WebUiMessage::SetPrefix(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+}
@@ -0,0 +1,437 @@
+use super::{Location, ViewMode, WebUI, WebUiMessage, LOCATION_ID};
+
+impl WebUI {
+    pub(super) fn view_top_panel(&self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            // Switch between dark and light mode.
+            {
+                let (text, hover) = match self.settings.dark_mode {
+                    true => ("☀", "Light mode"),
+                    false => ("🌙", "Dark mode"),
+                };
+                let btn = egui::Button::new(text);
+                let rsp = ui.add(btn).on_hover_text(hover);
+                if rsp.clicked() {
+                    let mut settings = self.settings.clone();
+                    settings.dark_mode = settings.dark_mode == false;
+                    self.tx.send(WebUiMessage::SetSettings(settings)).unwrap();
+                }
+            }
+
+            // Settings.
+            {
+                let btn = egui::Button::new("⚙");
+                let rsp = ui.add(btn).on_hover_text("Settings");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ShowSettings(true)).unwrap();
+                }
+            }
+
+            // Home.
+            {
+                let btn = egui::Button::new("🏠");
+                let rsp = ui.add(btn).on_hover_text("Goto Home");
+                if rsp.clicked() {
+                    if let Some(path) = &self.homedir {
+                        self.cd(ctx, path.as_str());
+                    }
+                }
+            }
+
+            // Back.
+            {
+                let btn = egui::Button::new("⬅");
+                let rsp = ui
+                    .add_enabled(self.browser().back.is_empty() == false, btn)
+                    .on_hover_text("Back (Alt+Left)");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActBack).unwrap();
+                }
+            }
+
+            // Forward.
+            {
+                let btn = egui::Button::new("➡");
+                let rsp = ui
+                    .add_enabled(self.browser().forward.is_empty() == false, btn)
+                    .on_hover_text("Forward (Alt+Right)");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActForward).unwrap();
+                }
+            }
+
+            // Parent directory.
+            {
+                let has_parent = self
+                    .browser()
+                    .cwd
+                    .as_ref()
+                    .and_then(|v| std::path::Path::new(v).parent())
+                    .is_some();
+                let btn = egui::Button::new("⬆");
+                let rsp = ui
+                    .add_enabled(has_parent, btn)
+                    .on_hover_text("Parent directory (Alt+Up)");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActUp).unwrap();
+                }
+            }
+
+            // Refresh.
+            {
+                let btn = egui::Button::new("🔃");
+                let rsp = ui.add(btn).on_hover_text("Refresh");
+                if rsp.clicked() {
+                    self.refresh(ctx);
+                }
+            }
+
+            // Disk usage.
+            {
+                let btn = egui::Button::new("📊");
+                let rsp = ui.add(btn).on_hover_text("Disk usage of this folder");
+                if rsp.clicked() {
+                    if let Some(cwd) = &self.browser().cwd {
+                        self.tx
+                            .send(WebUiMessage::ActDu((cwd.clone(), false)))
+                            .unwrap();
+                    }
+                }
+            }
+
+            // Steam games.
+            {
+                let btn = egui::Button::new("🚂");
+                let rsp = ui.add(btn).on_hover_text("Steam games");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActSteamApps).unwrap();
+                    self.tx.send(WebUiMessage::ShowSteamApps(true)).unwrap();
+                }
+            }
+
+            // Steam screenshots.
+            {
+                let btn = egui::Button::new("📷");
+                let rsp = ui.add(btn).on_hover_text("Steam screenshots");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActScreenshots).unwrap();
+                }
+            }
+
+            // Proton prefixes.
+            {
+                let btn = egui::Button::new("🍷");
+                let rsp = ui.add(btn).on_hover_text("Proton prefixes");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActPrefixes).unwrap();
+                }
+            }
+
+            // Save backups.
+            {
+                let btn = egui::Button::new("💾");
+                let rsp = ui.add(btn).on_hover_text("Backups");
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActBackups).unwrap();
+                }
+            }
+
+            // Verify ROMs.
+            {
+                let btn = egui::Button::new("🎮");
+                let rsp = ui.add(btn).on_hover_text("Verify ROMs in this folder");
+                if rsp.clicked() {
+                    if let Some(cwd) = &self.browser().cwd {
+                        self.tx
+                            .send(WebUiMessage::ActRomCheck(cwd.clone()))
+                            .unwrap();
+                    }
+                }
+            }
+
+            // Upload folder.
+            {
+                let btn = egui::Button::new("📤");
+                let rsp = ui.add(btn).on_hover_text("Upload folder");
+                if rsp.clicked() {
+                    let ctx = ctx.clone();
+                    let tx = self.tx.clone();
+                    crate::picker::pick_folder(move |files| {
+                        tx.send(WebUiMessage::ActUpload(files)).unwrap();
+                        ctx.request_repaint();
+                    });
+                }
+            }
+
+            // Split view.
+            {
+                let btn = egui::Button::new("◫").selected(self.settings.split);
+                let rsp = ui.add(btn).on_hover_text("Two panes (F5 copy, F6 move)");
+                if rsp.clicked() {
+                    self.tx
+                        .send(WebUiMessage::ActSplit(self.settings.split == false))
+                        .unwrap();
+                }
+            }
+
+            // View mode.
+            {
+                let (text, hover, mode) = match self.settings.view_mode {
+                    ViewMode::List => ("⊞", "Grid view", ViewMode::Grid),
+                    ViewMode::Grid => ("☰", "List view", ViewMode::List),
+                };
+                let btn = egui::Button::new(text);
+                let rsp = ui.add(btn).on_hover_text(hover);
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::SetViewMode(mode)).unwrap();
+                }
+            }
+
+            // Thumbnails.
+            {
+                let btn = egui::Button::new("🖼").selected(self.settings.show_thumbnails);
+                let rsp = ui
+                    .add_enabled(self.settings.view_mode == ViewMode::Grid, btn)
+                    .on_hover_text("Thumbnails of images");
+                if rsp.clicked() {
+                    self.tx
+                        .send(WebUiMessage::SetShowThumbnails(
+                            self.settings.show_thumbnails == false,
+                        ))
+                        .unwrap();
+                }
+            }
+
+            ui.separator();
+
+            // Cut.
+            {
+                let btn = egui::Button::new("✂");
+                let rsp = ui
+                    .add_enabled(self.browser().selected.is_empty() == false, btn)
+                    .on_hover_text("Cut (Ctrl+X)");
+                if rsp.clicked() {
+                    self.tx
+                        .send(WebUiMessage::ActClipboard(crate::protocol::PasteMode::Move))
+                        .unwrap();
+                }
+            }
+
+            // Copy.
+            {
+                let btn = egui::Button::new("📄");
+                let rsp = ui
+                    .add_enabled(self.browser().selected.is_empty() == false, btn)
+                    .on_hover_text("Copy (Ctrl+C)");
+                if rsp.clicked() {
+                    self.tx
+                        .send(WebUiMessage::ActClipboard(crate::protocol::PasteMode::Copy))
+                        .unwrap();
+                }
+            }
+
+            // Paste.
+            {
+                let btn = egui::Button::new("📋");
+                let hover = match &self.clipboard {
+                    Some(v) => format!("Paste {} files (Ctrl+V)", v.paths.len()),
+                    None => "Paste (Ctrl+V)".to_string(),
+                };
+                let rsp = ui
+                    .add_enabled(self.clipboard.is_some(), btn)
+                    .on_hover_text(hover);
+                if rsp.clicked() {
+                    self.tx.send(WebUiMessage::ActPaste).unwrap();
+                }
+            }
+
+            ui.separator();
+
+            self.view_path_bar(ctx, ui);
+        });
+    }
+
+    fn view_path_bar(&self, ctx: &egui::Context, ui: &mut egui::Ui) {
+        let cwd = match &self.browser().cwd {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Some(location) = &self.location {
+            self.view_location(ctx, ui, location);
+            return;
+        }
+
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 0.0;
+
+            let components: Vec<std::path::Component> =
+                std::path::Path::new(cwd).components().collect();
+            let mut path = std::path::PathBuf::new();
+            for (idx, component) in components.iter().enumerate() {
+                path.push(component);
+                let path = path.to_string_lossy().to_string();
+
+                let name = component.as_os_str().to_string_lossy().to_string();
+                let mut text = egui::RichText::new(name);
+                if idx + 1 == components.len() {
+                    text = text.strong();
+                }
+                if ui.add(egui::Button::new(text).frame(false)).clicked() {
+                    self.cd(ctx, path.as_str());
+                }
+
+                // Subdirectories, which are the siblings of the next component.
+                ui.menu_button("▸", |ui| {
+                    self.view_subdirs_menu(ctx, ui, path.as_str());
+                });
+            }
+
+            ui.add_space(8.0);
+            let btn = egui::Button::new("✏").small();
+            if ui.add(btn).on_hover_text("Edit location").clicked() {
+                self.tx.send(WebUiMessage::ShowLocation(true)).unwrap();
+            }
+        });
+    }
+
+    /// Subdirectories of a breadcrumb, fetched when the menu opens.
+    fn view_subdirs_menu(&self, ctx: &egui::Context, ui: &mut egui::Ui, path: &str) {
+        let dirs = match self.subdirs.get(path) {
+            Some(Some(v)) => v,
+            Some(None) => {
+                ui.spinner();
+                return;
+            }
+            None => {
+                self.tx
+                    .send(WebUiMessage::ActSubdirs(path.to_string()))
+                    .unwrap();
+                ui.spinner();
+                return;
+            }
+        };
+
+        if dirs.is_empty() {
+            ui.label("No subdirectories");
+            return;
+        }
+
+        egui::ScrollArea::vertical()
+            .max_height(320.0)
+            .show(ui, |ui| {
+                for dir in dirs {
+                    let name = std::path::Path::new(dir)
+                        .file_name()
+                        .map(|v| v.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let current = self
+                        .browser()
+                        .cwd
+                        .as_ref()
+                        .map(|v| std::path::Path::new(v).starts_with(dir))
+                        .unwrap_or(false);
+                    if ui
+                        .selectable_label(current, format!("📁 {}", name))
+                        .clicked()
+                    {
+                        self.cd(ctx, dir.as_str());
+                        ui.close_menu();
+                    }
+                }
+            });
+    }
+
+    fn view_location(&self, ctx: &egui::Context, ui: &mut egui::Ui, location: &Location) {
+        let id = egui::Id::new(LOCATION_ID);
+
+        // Tab takes the first completion instead of moving the focus.
+        let tab = ui.memory(|m| m.has_focus(id))
+            && ui.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::Tab));
+        if let (true, Some(first)) = (tab, location.completions.first()) {
+            let text = format!("{}/", first);
+            if let Some(mut state) = egui::text_edit::TextEditState::load(ctx, id) {
+                let cursor = egui::text::CCursor::new(text.chars().count());
+                state.set_ccursor_range(Some(egui::text::CCursorRange::one(cursor)));
+                state.store(ctx, id);
+            }
+            self.tx.send(WebUiMessage::SetLocation(text)).unwrap();
+        }
+
+        let mut text = location.text.clone();
+        let edit = egui::TextEdit::singleline(&mut text)
+            .id(id)
+            .desired_width(ui.available_width().min(480.0))
+            .hint_text("Location");
+        let rsp = ui.add(edit);
+        if text != location.text {
+            self.tx.send(WebUiMessage::SetLocation(text)).unwrap();
+        }
+
+        // Enter opens the location, Esc or a click elsewhere cancels.
+        if rsp.lost_focus() {
+            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                let path = match location.text.len() > 1 {
+                    true => location.text.trim_end_matches('/'),
+                    false => location.text.as_str(),
+                };
+                self.cd(ctx, path);
+            }
+            self.tx.send(WebUiMessage::ShowLocation(false)).unwrap();
+        }
+
+        let popup_id = id.with("completions");
+        if rsp.has_focus() && location.completions.is_empty() == false {
+            ui.memory_mut(|m| m.open_popup(popup_id));
+        }
+        egui::popup::popup_below_widget(ui, popup_id, &rsp, |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(320.0)
+                .show(ui, |ui| {
+                    for dir in location.completions.iter() {
+                        if ui.selectable_label(false, dir.as_str()).clicked() {
+                            self.cd(ctx, dir.as_str());
+                            self.tx.send(WebUiMessage::ShowLocation(false)).unwrap();
+                        }
+                    }
+                });
+        });
+    }
+
+    /// Complete a location of the path bar.
+    ///
+    /// # Arguments
+    /// + `text`: the location being edited.
+    pub(super) fn complete(&self, ctx: &egui::Context, text: String) {
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::CompleteRequest { path: text.clone() },
+            move |rsp: Result<crate::protocol::CompleteResponse, String>| {
+                tx.send(WebUiMessage::SetCompletions((text, rsp))).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Fetch subdirectories of a breadcrumb of the path bar.
+    ///
+    /// # Arguments
+    /// + `path`: the absolute path of the directory.
+    pub(super) fn fetch_subdirs(&self, ctx: &egui::Context, path: String) {
+        let query = match path.ends_with('/') {
+            true => path.clone(),
+            false => format!("{}/", path),
+        };
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::CompleteRequest { path: query },
+            move |rsp: Result<crate::protocol::CompleteResponse, String>| {
+                tx.send(WebUiMessage::SetSubdirs((path, rsp))).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+}
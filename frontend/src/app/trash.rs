@@ -0,0 +1,233 @@
+use super::{convert_epoch_to_local_time, format_size, Trash, WebUI, WebUiMessage};
+
+impl WebUI {
+    pub(super) fn view_trash(&self, ctx: &egui::Context, trash: &Trash) {
+        let mut open = true;
+
+        egui::Window::new("Trash")
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([720.0, 480.0])
+            .show(ctx, |ui| {
+                let rsp = match &trash.items {
+                    Some(Ok(v)) => v,
+                    Some(Err(e)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, e.as_str());
+                        return;
+                    }
+                    None => {
+                        ui.spinner();
+                        return;
+                    }
+                };
+
+                if rsp.items.is_empty() {
+                    ui.label("The trash is empty.");
+                    return;
+                }
+
+                let idle = self.trash_busy == false;
+                let has_selection = trash.selected.is_empty() == false;
+                ui.horizontal(|ui| {
+                    let btn = egui::Button::new("♻ Restore");
+                    if ui.add_enabled(idle && has_selection, btn).clicked() {
+                        self.tx.send(WebUiMessage::ActTrashRestore).unwrap();
+                    }
+
+                    let btn = egui::Button::new("❌ Delete permanently");
+                    if ui.add_enabled(idle && has_selection, btn).clicked() {
+                        self.tx.send(WebUiMessage::ActTrashDelete).unwrap();
+                    }
+
+                    if trash.confirm_empty {
+                        ui.label("Delete all files permanently?");
+                        if ui.add_enabled(idle, egui::Button::new("Empty")).clicked() {
+                            self.tx.send(WebUiMessage::ActTrashEmpty).unwrap();
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.tx
+                                .send(WebUiMessage::SetTrashConfirmEmpty(false))
+                                .unwrap();
+                        }
+                    } else {
+                        let btn = egui::Button::new("🗑 Empty trash");
+                        if ui.add_enabled(idle, btn).clicked() {
+                            self.tx
+                                .send(WebUiMessage::SetTrashConfirmEmpty(true))
+                                .unwrap();
+                        }
+                    }
+
+                    if self.trash_busy {
+                        ui.spinner();
+                    }
+                });
+
+                ui.separator();
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("trash_grid")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for item in rsp.items.iter() {
+                                let icon = if item.is_dir { "📁" } else { "📒" };
+                                let mut selected = trash.selected.contains(&item.path);
+                                let rsp = ui
+                                    .checkbox(&mut selected, format!("{} {}", icon, item.name))
+                                    .on_hover_text(item.original_path.as_str());
+                                if rsp.changed() {
+                                    self.tx
+                                        .send(WebUiMessage::SetTrashSelected((
+                                            item.path.clone(),
+                                            selected,
+                                        )))
+                                        .unwrap();
+                                }
+
+                                ui.label(item.original_path.as_str());
+                                match item.size {
+                                    Some(v) => ui.label(format_size(v)),
+                                    None => match trash.sizes.get(&item.path) {
+                                        Some(Some(v)) => ui.label(format_size(*v)),
+                                        Some(None) => ui.label("?"),
+                                        None => ui.spinner(),
+                                    },
+                                };
+                                ui.label(convert_epoch_to_local_time(item.deleted));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if open == false {
+            self.tx.send(WebUiMessage::CloseTrash).unwrap();
+        }
+    }
+
+    /// Fetch trashed files for the trash view.
+    ///
+    /// # Arguments
+    /// + `open`: Open the trash view first, clearing its state.
+    pub(super) fn fetch_trash(&mut self, ctx: &egui::Context, open: bool) {
+        if open {
+            self.trash = Some(Trash {
+                items: None,
+                selected: std::collections::HashSet::new(),
+                sizes: std::collections::HashMap::new(),
+                confirm_empty: false,
+            });
+        }
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::TrashListRequest {},
+            move |rsp: Result<crate::protocol::TrashListResponse, String>| {
+                tx.send(WebUiMessage::SetTrash(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Fetch sizes of trashed directories that are not known yet, as they
+    /// take a scan each.
+    pub(super) fn fetch_trash_sizes(&self, ctx: &egui::Context) {
+        let paths: Vec<String> = match &self.trash {
+            Some(Trash {
+                items: Some(Ok(v)),
+                sizes,
+                ..
+            }) => v
+                .items
+                .iter()
+                .filter(|v| v.size.is_none() && sizes.contains_key(&v.path) == false)
+                .map(|v| v.path.clone())
+                .collect(),
+            _ => return,
+        };
+        if paths.is_empty() {
+            return;
+        }
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::TrashSizeRequest {
+                paths: paths.clone(),
+            },
+            move |rsp: Result<crate::protocol::TrashSizeResponse, String>| {
+                tx.send(WebUiMessage::SetTrashSizes((paths, rsp))).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Move files to the trash.
+    ///
+    /// # Arguments
+    /// + `paths`: the absolute paths of the files.
+    pub(super) fn trash_put(&mut self, ctx: &egui::Context, paths: Vec<String>) {
+        self.trash_busy = true;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::TrashPutRequest { paths: paths },
+            move |rsp: Result<crate::protocol::TrashPutResponse, String>| {
+                let rsp = rsp.map(|v| match v.errors.first() {
+                    Some(e) => format!("Moved {} files to the trash, {}", v.trashed, e),
+                    None => format!("Moved {} files to the trash", v.trashed),
+                });
+                tx.send(WebUiMessage::FinishTrash(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Restore the selected trashed files.
+    pub(super) fn trash_restore(&mut self, ctx: &egui::Context) {
+        let paths = match &self.trash {
+            Some(v) => v.selected.iter().cloned().collect(),
+            None => return,
+        };
+        self.trash_busy = true;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::TrashRestoreRequest { paths: paths },
+            move |rsp: Result<crate::protocol::TrashRestoreResponse, String>| {
+                let rsp = rsp.map(|v| match v.errors.first() {
+                    Some(e) => format!("Restored {} files, {}", v.restored.len(), e),
+                    None => format!("Restored {} files", v.restored.len()),
+                });
+                tx.send(WebUiMessage::FinishTrash(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+
+    /// Delete trashed files permanently.
+    ///
+    /// # Arguments
+    /// + `paths`: the paths inside the trash, or `None` to empty the trash.
+    pub(super) fn trash_empty(&mut self, ctx: &egui::Context, paths: Option<Vec<String>>) {
+        self.trash_busy = true;
+
+        let ctx = ctx.clone();
+        let tx = self.tx.clone();
+        self.client.post(
+            crate::protocol::TrashEmptyRequest { paths: paths },
+            move |rsp: Result<crate::protocol::TrashEmptyResponse, String>| {
+                let rsp = rsp.map(|v| match v.errors.first() {
+                    Some(e) => format!("Deleted {} files, {}", v.deleted, e),
+                    None => format!("Deleted {} files", v.deleted),
+                });
+                tx.send(WebUiMessage::FinishTrash(rsp)).unwrap();
+                ctx.request_repaint();
+            },
+        );
+    }
+}
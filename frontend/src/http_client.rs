@@ -7,6 +7,28 @@ pub struct HttpClient {
     rt: tokio::runtime::Runtime,
 }
 
+/// The size of the chunks local files are read in.
+pub(crate) const CHUNK_SIZE: usize = 256 * 1024;
+
+/// A file to upload. The content is only read while uploading, so large
+/// folders are never held in memory.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UploadFile {
+    /// The path relative to the upload directory, `/` separated.
+    pub relative_path: String,
+    /// The size in bytes.
+    pub size: u64,
+    /// The xxh3 digest, lowercase hex.
+    pub xxh3: String,
+    /// The local file.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub path: std::path::PathBuf,
+    /// The index of the file picked in the browser, see
+    /// [crate::picker::take_picked].
+    #[cfg(target_arch = "wasm32")]
+    pub index: usize,
+}
+
 pub fn new(host: &str) -> HttpClient {
     let base_url = url::Url::parse(host).unwrap();
 
@@ -105,6 +127,212 @@ impl HttpClient {
         });
     }
 
+    /// Post the given request and call the given function with the raw response
+    /// body, for responses that are not json, like archives.
+    ///
+    /// # Arguments
+    /// + `req` - The request to send.
+    /// + `func` - The function to call with the response body.
+    pub fn post_raw<T, F>(&self, req: T, func: F)
+    where
+        T: crate::protocol::Request,
+        F: FnOnce(Result<Vec<u8>, String>) + Send + 'static,
+    {
+        let url = self.url(req.url());
+        let mut rs = reqwest::Client::new().post(url);
+        if let Some(body) = req.to_json() {
+            rs = rs.json(&body);
+        };
+
+        self.spawn(async move {
+            let ret = match rs.send().await {
+                Ok(v) => v,
+                Err(e) => {
+                    func(Err(e.to_string()));
+                    return;
+                }
+            };
+
+            let status_code = ret.status();
+
+            let body = match ret.bytes().await {
+                Ok(v) => v,
+                Err(e) => {
+                    func(Err(e.to_string()));
+                    return;
+                }
+            };
+
+            if status_code != 200 {
+                func(Err(String::from_utf8_lossy(&body).to_string()));
+                return;
+            }
+
+            func(Ok(body.to_vec()));
+        });
+    }
+
+    /// Upload files into the given directory.
+    ///
+    /// The size and xxh3 digest of each file are sent along, so the server
+    /// rejects files that do not arrive intact. Files are streamed from disk.
+    ///
+    /// # Arguments
+    /// + `path` - The directory to upload to.
+    /// + `id` - The id to query progress with [crate::protocol::UploadProgressRequest].
+    /// + `files` - The files to upload, paths are relative to `path`.
+    /// + `func` - The function to call with the response.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn upload<F>(&self, path: &str, id: &str, files: Vec<UploadFile>, func: F)
+    where
+        F: FnOnce(Result<crate::protocol::UploadResponse, String>) + Send + 'static,
+    {
+        let url = self.upload_url(path, id);
+
+        let mut form = reqwest::multipart::Form::new().text("manifest", upload_manifest(&files));
+        for file in files {
+            let part =
+                reqwest::multipart::Part::stream_with_length(file_body(file.path), file.size)
+                    .file_name(file.relative_path);
+            form = form.part("file", part);
+        }
+        let rs = reqwest::Client::new().post(url.as_str()).multipart(form);
+
+        self.spawn(async move {
+            let ret = match rs.send().await {
+                Ok(v) => v,
+                Err(e) => {
+                    func(Err(e.to_string()));
+                    return;
+                }
+            };
+
+            let status_code = ret.status().as_u16();
+
+            let body = match ret.text().await {
+                Ok(v) => v,
+                Err(e) => {
+                    func(Err(e.to_string()));
+                    return;
+                }
+            };
+
+            func(upload_response(status_code, body));
+        });
+    }
+
+    /// Upload files into the given directory.
+    ///
+    /// The size and xxh3 digest of each file are sent along, so the server
+    /// rejects files that do not arrive intact. The browser streams the
+    /// picked files from disk.
+    ///
+    /// # Arguments
+    /// + `path` - The directory to upload to.
+    /// + `id` - The id to query progress with [crate::protocol::UploadProgressRequest].
+    /// + `files` - The files to upload, paths are relative to `path`.
+    /// + `func` - The function to call with the response.
+    #[cfg(target_arch = "wasm32")]
+    pub fn upload<F>(&self, path: &str, id: &str, files: Vec<UploadFile>, func: F)
+    where
+        F: FnOnce(Result<crate::protocol::UploadResponse, String>) + Send + 'static,
+    {
+        use wasm_bindgen::JsCast;
+
+        let url = self.upload_url(path, id);
+        let picked = crate::picker::take_picked();
+
+        let form = web_sys::FormData::new().unwrap();
+        form.append_with_str("manifest", upload_manifest(&files).as_str())
+            .unwrap();
+        for file in files.iter() {
+            let blob = match picked.get(file.index) {
+                Some(v) => v,
+                None => {
+                    func(Err(format!("{} is not picked", file.relative_path)));
+                    return;
+                }
+            };
+            form.append_with_blob_and_filename("file", blob, file.relative_path.as_str())
+                .unwrap();
+        }
+
+        let mut init = web_sys::RequestInit::new();
+        init.method("POST").body(Some(form.as_ref()));
+        let promise = web_sys::window()
+            .unwrap()
+            .fetch_with_str_and_init(url.as_str(), &init);
+
+        self.spawn(async move {
+            let ret = match wasm_bindgen_futures::JsFuture::from(promise).await {
+                Ok(v) => v.dyn_into::<web_sys::Response>().unwrap(),
+                Err(e) => {
+                    func(Err(format!("{:?}", e)));
+                    return;
+                }
+            };
+
+            let text = match ret.text() {
+                Ok(v) => wasm_bindgen_futures::JsFuture::from(v).await,
+                Err(e) => Err(e),
+            };
+            let body = match text {
+                Ok(v) => v.as_string().unwrap_or_default(),
+                Err(e) => {
+                    func(Err(format!("{:?}", e)));
+                    return;
+                }
+            };
+
+            func(upload_response(ret.status(), body));
+        });
+    }
+
+    fn upload_url(&self, path: &str, id: &str) -> url::Url {
+        let mut url = self.base.join("/upload").unwrap();
+        url.query_pairs_mut()
+            .append_pair("path", path)
+            .append_pair("id", id);
+        return url;
+    }
+
+    /// Download the resource at the given path into a local file.
+    ///
+    /// Browsers download the resource themselves, so it is never held in
+    /// memory.
+    ///
+    /// # Arguments
+    /// + `path` - The path to the resource.
+    /// + `name` - The suggested file name.
+    #[cfg(target_arch = "wasm32")]
+    pub fn download(&self, path: &str, name: &str) {
+        use wasm_bindgen::JsCast;
+
+        let document = web_sys::window().unwrap().document().unwrap();
+        let anchor = document
+            .create_element("a")
+            .unwrap()
+            .dyn_into::<web_sys::HtmlAnchorElement>()
+            .unwrap();
+        anchor.set_href(self.url(path).as_str());
+        anchor.set_download(name);
+        anchor.click();
+    }
+
+    /// Download the resource at the given path, and ask where to save it.
+    ///
+    /// # Arguments
+    /// + `path` - The path to the resource.
+    /// + `name` - The suggested file name.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn download(&self, path: &str, name: &str) {
+        let name = name.to_string();
+        self.get(path, move |rsp| match rsp {
+            Ok(v) => crate::picker::save_file(name.as_str(), v),
+            Err(e) => log::warn!("download {} failed: {}", name, e),
+        });
+    }
+
     fn url(&self, path: &str) -> String {
         let url = self.base.join(path).unwrap();
         return url.as_str().to_string();
@@ -126,3 +354,60 @@ impl HttpClient {
         self.rt.spawn(future);
     }
 }
+
+/// The `manifest` field of an upload, see [crate::protocol::UploadManifestItem].
+fn upload_manifest(files: &[UploadFile]) -> String {
+    let manifest = files
+        .iter()
+        .map(|v| crate::protocol::UploadManifestItem {
+            path: v.relative_path.clone(),
+            size: Some(v.size),
+            sha256: None,
+            xxh3: Some(v.xxh3.clone()),
+        })
+        .collect::<Vec<crate::protocol::UploadManifestItem>>();
+    return serde_json::to_string(&manifest).unwrap();
+}
+
+fn upload_response(
+    status_code: u16,
+    body: String,
+) -> Result<crate::protocol::UploadResponse, String> {
+    if status_code != 200 {
+        return Err(body);
+    }
+
+    return Ok(
+        <crate::protocol::UploadResponse as crate::protocol::Response>::from_json(body.as_str()),
+    );
+}
+
+/// Stream a local file in chunks of [CHUNK_SIZE]. The file is only opened
+/// once the upload reaches it, so large folders do not run out of file
+/// handles.
+#[cfg(not(target_arch = "wasm32"))]
+fn file_body(path: std::path::PathBuf) -> reqwest::Body {
+    let stream = futures_util::stream::unfold(Some((path, None)), |state| async move {
+        let (path, f) = state?;
+        let mut f = match f {
+            Some(v) => v,
+            None => match tokio::fs::File::open(&path).await {
+                Ok(v) => v,
+                Err(e) => return Some((Err(e), None)),
+            },
+        };
+
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        match tokio::io::AsyncReadExt::read(&mut f, &mut buf).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(buf), Some((path, Some(f)))))
+            }
+            // End the stream after an error.
+            Err(e) => Some((Err::<Vec<u8>, std::io::Error>(e), None)),
+        }
+    });
+
+    return reqwest::Body::wrap_stream(stream);
+}
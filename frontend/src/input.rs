@@ -0,0 +1,511 @@
+/// Stick deflection counted as a D-pad press.
+#[cfg(target_arch = "wasm32")]
+const STICK_THRESHOLD: f64 = 0.5;
+
+/// Seconds a direction is held before it repeats.
+const REPEAT_DELAY_SECS: f64 = 0.4;
+
+/// Seconds between repeats of a held direction.
+const REPEAT_INTERVAL_SECS: f64 = 0.1;
+
+/// What the user wants to do, independent of the input device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Action {
+    /// Move the cursor up.
+    Up,
+    /// Move the cursor down.
+    Down,
+    /// Move the cursor up by a page.
+    PageUp,
+    /// Move the cursor down by a page.
+    PageDown,
+    /// Move the cursor to the first entry.
+    First,
+    /// Move the cursor to the last entry.
+    Last,
+    /// Open the entry under the cursor.
+    Open,
+    /// Go to the parent directory, or close the context menu.
+    Parent,
+    /// Open the context menu of the entry under the cursor.
+    ContextMenu,
+    /// Select or unselect the entry under the cursor.
+    ToggleSelection,
+    /// Move the focus to the previous pane.
+    PreviousPane,
+    /// Move the focus to the next pane.
+    NextPane,
+}
+
+/// All actions, in the order shown in the settings.
+pub const ACTIONS: [Action; 12] = [
+    Action::Up,
+    Action::Down,
+    Action::PageUp,
+    Action::PageDown,
+    Action::First,
+    Action::Last,
+    Action::Open,
+    Action::Parent,
+    Action::ContextMenu,
+    Action::ToggleSelection,
+    Action::PreviousPane,
+    Action::NextPane,
+];
+
+/// Buttons of a gamepad in the standard layout, named as on the Steam Deck.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum Button {
+    A,
+    B,
+    X,
+    Y,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+}
+
+impl Button {
+    /// Index of the button in the standard gamepad mapping.
+    /// See <https://w3c.github.io/gamepad/#remapping>.
+    pub fn standard_index(&self) -> u32 {
+        return match self {
+            Button::A => 0,
+            Button::B => 1,
+            Button::X => 2,
+            Button::Y => 3,
+            Button::LeftBumper => 4,
+            Button::RightBumper => 5,
+            Button::LeftTrigger => 6,
+            Button::RightTrigger => 7,
+            Button::DpadUp => 12,
+            Button::DpadDown => 13,
+            Button::DpadLeft => 14,
+            Button::DpadRight => 15,
+        };
+    }
+
+    /// Whether holding the button repeats it, like a held key.
+    fn repeats(&self) -> bool {
+        return matches!(
+            self,
+            Button::DpadUp | Button::DpadDown | Button::DpadLeft | Button::DpadRight
+        );
+    }
+}
+
+/// All buttons, polled in this order.
+#[cfg(target_arch = "wasm32")]
+const BUTTONS: [Button; 12] = [
+    Button::A,
+    Button::B,
+    Button::X,
+    Button::Y,
+    Button::LeftBumper,
+    Button::RightBumper,
+    Button::LeftTrigger,
+    Button::RightTrigger,
+    Button::DpadUp,
+    Button::DpadDown,
+    Button::DpadLeft,
+    Button::DpadRight,
+];
+
+/// A raw input that can be bound to an action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum Input {
+    /// A key press, with the modifiers held.
+    Key {
+        key: egui::Key,
+        modifiers: egui::Modifiers,
+    },
+    /// A gamepad button press.
+    Button(Button),
+}
+
+impl Input {
+    /// A key press without modifiers.
+    pub fn key(key: egui::Key) -> Input {
+        return Input::Key {
+            key: key,
+            modifiers: egui::Modifiers::NONE,
+        };
+    }
+
+    /// Whether a raw input triggers this binding. Modifiers must match
+    /// exactly, so `Alt+Left` does not trigger `Left`.
+    fn matches(&self, input: &Input) -> bool {
+        return match (self, input) {
+            (
+                Input::Key { key, modifiers },
+                Input::Key {
+                    key: input_key,
+                    modifiers: input_modifiers,
+                },
+            ) => key == input_key && input_modifiers.matches(*modifiers),
+            (Input::Button(button), Input::Button(input_button)) => button == input_button,
+            _ => false,
+        };
+    }
+}
+
+/// Raw inputs bound to actions, so the UI only handles actions whatever the
+/// input device. Several inputs may trigger the same action.
+///
+/// In Game Mode, Steam Input usually turns the controller into key presses,
+/// while browsers also expose it through the Gamepad API, see [Gamepad].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Bindings {
+    pub bindings: Vec<(Input, Action)>,
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        let keys = [
+            (egui::Key::ArrowUp, Action::Up),
+            (egui::Key::ArrowDown, Action::Down),
+            (egui::Key::PageUp, Action::PageUp),
+            (egui::Key::PageDown, Action::PageDown),
+            (egui::Key::Home, Action::First),
+            (egui::Key::End, Action::Last),
+            (egui::Key::Enter, Action::Open),
+            (egui::Key::Backspace, Action::Parent),
+            (egui::Key::Space, Action::ToggleSelection),
+            (egui::Key::ArrowLeft, Action::PreviousPane),
+            (egui::Key::ArrowRight, Action::NextPane),
+        ];
+        let buttons = [
+            (Button::DpadUp, Action::Up),
+            (Button::DpadDown, Action::Down),
+            (Button::DpadLeft, Action::PreviousPane),
+            (Button::DpadRight, Action::NextPane),
+            (Button::LeftTrigger, Action::PageUp),
+            (Button::RightTrigger, Action::PageDown),
+            (Button::A, Action::Open),
+            (Button::B, Action::Parent),
+            (Button::X, Action::ContextMenu),
+            (Button::Y, Action::ToggleSelection),
+            (Button::LeftBumper, Action::PreviousPane),
+            (Button::RightBumper, Action::NextPane),
+        ];
+
+        let mut bindings: Vec<(Input, Action)> = keys
+            .into_iter()
+            .map(|(key, action)| (Input::key(key), action))
+            .collect();
+
+        // The context menu key of most desktops.
+        bindings.push((
+            Input::Key {
+                key: egui::Key::F10,
+                modifiers: egui::Modifiers::SHIFT,
+            },
+            Action::ContextMenu,
+        ));
+
+        bindings.extend(
+            buttons
+                .into_iter()
+                .map(|(button, action)| (Input::Button(button), action)),
+        );
+
+        return Bindings { bindings: bindings };
+    }
+}
+
+impl Bindings {
+    /// Map raw inputs to actions, in order. Unbound inputs are ignored.
+    pub fn actions(&self, inputs: &[Input]) -> Vec<Action> {
+        return inputs
+            .iter()
+            .filter_map(|input| {
+                self.bindings
+                    .iter()
+                    .find(|(binding, _)| binding.matches(input))
+                    .map(|(_, action)| *action)
+            })
+            .collect();
+    }
+
+    /// Bind an input to an action, replacing what the input triggered before.
+    pub fn bind(&mut self, input: Input, action: Action) {
+        self.bindings.retain(|(binding, _)| binding != &input);
+        self.bindings.push((input, action));
+    }
+}
+
+/// Key presses among input events, including key repeats.
+pub fn key_inputs(events: &[egui::Event]) -> Vec<Input> {
+    return events
+        .iter()
+        .filter_map(|event| match event {
+            egui::Event::Key {
+                key,
+                pressed: true,
+                modifiers,
+                ..
+            } => Some(Input::Key {
+                key: *key,
+                modifiers: *modifiers,
+            }),
+            _ => None,
+        })
+        .collect();
+}
+
+/// Turns the held buttons of gamepads into button presses.
+#[derive(Debug, Default)]
+pub struct Gamepad {
+    /// Buttons held at the last poll.
+    held: Vec<Button>,
+
+    /// When the held direction repeats next.
+    repeat_at: f64,
+
+    /// Whether a gamepad was connected at the last poll.
+    connected: bool,
+}
+
+impl Gamepad {
+    /// Whether a gamepad was connected at the last poll. Gamepads must be
+    /// polled every few frames while connected.
+    pub fn is_connected(&self) -> bool {
+        return self.connected;
+    }
+
+    /// Poll gamepads through the browser Gamepad API.
+    ///
+    /// # Arguments
+    /// + `now` - The current time in seconds.
+    #[cfg(target_arch = "wasm32")]
+    pub fn poll(&mut self, now: f64) -> Vec<Input> {
+        use wasm_bindgen::JsCast;
+
+        let pads = match web_sys::window().unwrap().navigator().get_gamepads() {
+            Ok(v) => v,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut held = Vec::new();
+        let mut connected = false;
+        for pad in pads.iter() {
+            // Disconnected slots are null.
+            let pad = match pad.dyn_into::<web_sys::Gamepad>() {
+                Ok(v) if v.connected() => v,
+                _ => continue,
+            };
+            connected = true;
+            if pad.mapping() != web_sys::GamepadMappingType::Standard {
+                continue;
+            }
+
+            let buttons = pad.buttons();
+            for button in BUTTONS {
+                let pressed = buttons
+                    .get(button.standard_index())
+                    .dyn_into::<web_sys::GamepadButton>()
+                    .map(|v| v.pressed())
+                    .unwrap_or(false);
+                if pressed {
+                    held.push(button);
+                }
+            }
+
+            // The left stick moves like the D-pad.
+            let axes = pad.axes();
+            let x = axes.get(0).as_f64().unwrap_or_default();
+            let y = axes.get(1).as_f64().unwrap_or_default();
+            if y < -STICK_THRESHOLD {
+                held.push(Button::DpadUp);
+            } else if y > STICK_THRESHOLD {
+                held.push(Button::DpadDown);
+            } else if x < -STICK_THRESHOLD {
+                held.push(Button::DpadLeft);
+            } else if x > STICK_THRESHOLD {
+                held.push(Button::DpadRight);
+            }
+        }
+
+        self.connected = connected;
+        return self.update(held, now);
+    }
+
+    /// Native builds rely on Steam Input turning the controller into key
+    /// presses.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn poll(&mut self, _now: f64) -> Vec<Input> {
+        return Vec::new();
+    }
+
+    /// Turn the buttons held now into presses of newly held buttons, and
+    /// repeats of a held direction.
+    ///
+    /// # Arguments
+    /// + `held` - The buttons held now.
+    /// + `now` - The current time in seconds.
+    pub fn update(&mut self, held: Vec<Button>, now: f64) -> Vec<Input> {
+        let mut ret: Vec<Input> = held
+            .iter()
+            .filter(|v| self.held.contains(v) == false)
+            .map(|v| Input::Button(*v))
+            .collect();
+
+        match held.iter().find(|v| v.repeats()) {
+            // Held since an earlier update, repeat when due.
+            Some(v) if self.held.contains(v) && now >= self.repeat_at => {
+                ret.push(Input::Button(*v));
+                self.repeat_at = now + REPEAT_INTERVAL_SECS;
+            }
+            Some(v) if self.held.contains(v) => {}
+            // Newly held, the first repeat comes after a delay.
+            Some(_) => self.repeat_at = now + REPEAT_DELAY_SECS,
+            None => {}
+        }
+
+        self.held = held;
+        return ret;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key_event(key: egui::Key, modifiers: egui::Modifiers, pressed: bool) -> egui::Event {
+        return egui::Event::Key {
+            key: key,
+            pressed: pressed,
+            repeat: false,
+            modifiers: modifiers,
+        };
+    }
+
+    #[test]
+    fn key_inputs_keeps_presses() {
+        let events = [
+            key_event(egui::Key::ArrowDown, egui::Modifiers::NONE, true),
+            key_event(egui::Key::ArrowDown, egui::Modifiers::NONE, false),
+            egui::Event::Text("a".to_string()),
+            key_event(egui::Key::F10, egui::Modifiers::SHIFT, true),
+        ];
+
+        let inputs = key_inputs(&events);
+        assert_eq!(
+            inputs,
+            vec![
+                Input::key(egui::Key::ArrowDown),
+                Input::Key {
+                    key: egui::Key::F10,
+                    modifiers: egui::Modifiers::SHIFT,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_exact_modifiers() {
+        let left = Input::key(egui::Key::ArrowLeft);
+        let alt_left = Input::Key {
+            key: egui::Key::ArrowLeft,
+            modifiers: egui::Modifiers::ALT,
+        };
+
+        assert!(left.matches(&left));
+        assert!(left.matches(&alt_left) == false);
+        assert!(alt_left.matches(&left) == false);
+        assert!(left.matches(&Input::key(egui::Key::ArrowRight)) == false);
+        assert!(left.matches(&Input::Button(Button::DpadLeft)) == false);
+        assert!(Input::Button(Button::A).matches(&Input::Button(Button::A)));
+    }
+
+    #[test]
+    fn actions_of_default_bindings() {
+        let bindings = Bindings::default();
+        let events = [
+            key_event(egui::Key::ArrowDown, egui::Modifiers::NONE, true),
+            key_event(egui::Key::ArrowLeft, egui::Modifiers::ALT, true),
+            key_event(egui::Key::F10, egui::Modifiers::SHIFT, true),
+            key_event(egui::Key::F10, egui::Modifiers::NONE, true),
+            key_event(egui::Key::Enter, egui::Modifiers::NONE, true),
+        ];
+
+        let mut inputs = key_inputs(&events);
+        inputs.push(Input::Button(Button::B));
+        assert_eq!(
+            bindings.actions(&inputs),
+            vec![
+                Action::Down,
+                Action::ContextMenu,
+                Action::Open,
+                Action::Parent
+            ]
+        );
+    }
+
+    #[test]
+    fn bind_replaces_input() {
+        let mut bindings = Bindings::default();
+        let count = bindings.bindings.len();
+
+        bindings.bind(Input::key(egui::Key::ArrowUp), Action::First);
+        assert_eq!(bindings.bindings.len(), count);
+        assert_eq!(
+            bindings.actions(&[Input::key(egui::Key::ArrowUp)]),
+            vec![Action::First]
+        );
+
+        bindings.bind(Input::key(egui::Key::K), Action::Up);
+        assert_eq!(bindings.bindings.len(), count + 1);
+        assert_eq!(
+            bindings.actions(&[Input::key(egui::Key::K)]),
+            vec![Action::Up]
+        );
+    }
+
+    #[test]
+    fn gamepad_presses_new_buttons_once() {
+        let mut gamepad = Gamepad::default();
+
+        let inputs = gamepad.update(vec![Button::A], 0.0);
+        assert_eq!(inputs, vec![Input::Button(Button::A)]);
+
+        // Only directions repeat.
+        let inputs = gamepad.update(vec![Button::A], 1.0);
+        assert!(inputs.is_empty());
+
+        let inputs = gamepad.update(Vec::new(), 1.1);
+        assert!(inputs.is_empty());
+
+        let inputs = gamepad.update(vec![Button::A], 1.2);
+        assert_eq!(inputs, vec![Input::Button(Button::A)]);
+    }
+
+    #[test]
+    fn gamepad_repeats_held_direction() {
+        let mut gamepad = Gamepad::default();
+        let down = Input::Button(Button::DpadDown);
+
+        assert_eq!(gamepad.update(vec![Button::DpadDown], 0.0), vec![down]);
+
+        // Nothing until the delay has passed.
+        assert!(gamepad.update(vec![Button::DpadDown], 0.2).is_empty());
+        assert!(gamepad.update(vec![Button::DpadDown], 0.39).is_empty());
+        assert_eq!(gamepad.update(vec![Button::DpadDown], 0.4), vec![down]);
+
+        // Then at the interval.
+        assert!(gamepad.update(vec![Button::DpadDown], 0.45).is_empty());
+        assert_eq!(gamepad.update(vec![Button::DpadDown], 0.5), vec![down]);
+        assert_eq!(gamepad.update(vec![Button::DpadDown], 0.61), vec![down]);
+
+        // Released and pressed again starts over with the delay.
+        assert!(gamepad.update(Vec::new(), 0.65).is_empty());
+        assert_eq!(gamepad.update(vec![Button::DpadDown], 0.7), vec![down]);
+        assert!(gamepad.update(vec![Button::DpadDown], 0.8).is_empty());
+        assert_eq!(gamepad.update(vec![Button::DpadDown], 1.1), vec![down]);
+    }
+}
@@ -1,5 +1,7 @@
 pub mod app;
 pub mod http_client;
+pub mod input;
+pub mod picker;
 pub mod protocol;
 
 #[cfg(target_arch = "wasm32")]
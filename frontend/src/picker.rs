@@ -0,0 +1,257 @@
+use crate::http_client::UploadFile;
+
+/// Ask the user to pick a local folder, and list all files inside it.
+///
+/// The relative path of each file starts with the folder name, the same as
+/// `webkitRelativePath` in browsers. Files are read in chunks to compute their
+/// digests, but not kept in memory.
+///
+/// # Arguments
+/// + `func` - The function to call with the files. Not called if the user
+///   cancels the dialog.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn pick_folder<F>(func: F)
+where
+    F: FnOnce(Vec<UploadFile>) + Send + 'static,
+{
+    std::thread::spawn(move || {
+        let folder = match rfd::FileDialog::new().pick_folder() {
+            Some(v) => v,
+            None => return,
+        };
+
+        let base = match folder.parent() {
+            Some(v) => v.to_path_buf(),
+            None => folder.clone(),
+        };
+
+        let mut ret = Vec::new();
+        read_folder(&base, &folder, &mut ret);
+        func(ret);
+    });
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read_folder(base: &std::path::Path, path: &std::path::Path, ret: &mut Vec<UploadFile>) {
+    let entries = match std::fs::read_dir(path) {
+        Ok(v) => v,
+        Err(e) => {
+            log::warn!("read {} failed: {}", path.display(), e);
+            return;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            read_folder(base, &path, ret);
+            continue;
+        }
+
+        let (size, xxh3) = match digest_file(&path) {
+            Ok(v) => v,
+            Err(e) => {
+                log::warn!("read {} failed: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        let relative_path = path
+            .strip_prefix(base)
+            .unwrap()
+            .components()
+            .map(|v| v.as_os_str().to_string_lossy().to_string())
+            .collect::<Vec<String>>()
+            .join("/");
+
+        ret.push(UploadFile {
+            relative_path: relative_path,
+            size: size,
+            xxh3: xxh3,
+            path: path,
+        });
+    }
+}
+
+/// Get the size and xxh3 digest of a local file.
+#[cfg(not(target_arch = "wasm32"))]
+fn digest_file(path: &std::path::Path) -> Result<(u64, String), std::io::Error> {
+    use std::io::Read;
+
+    let mut f = std::fs::File::open(path)?;
+    let mut buf = vec![0u8; crate::http_client::CHUNK_SIZE];
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+    let mut size: u64 = 0;
+
+    loop {
+        let n = f.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+        size += n as u64;
+    }
+
+    return Ok((size, format!("{:016x}", hasher.digest())));
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    /// The files of the folder picked last, see [UploadFile::index].
+    static PICKED: std::cell::RefCell<Vec<web_sys::File>> = std::cell::RefCell::new(Vec::new());
+}
+
+/// Take the files of the folder picked last. Browser files can not be sent
+/// between threads, so [UploadFile] only holds their index.
+#[cfg(target_arch = "wasm32")]
+pub fn take_picked() -> Vec<web_sys::File> {
+    return PICKED.with(|v| std::mem::take(&mut *v.borrow_mut()));
+}
+
+/// Ask the user to pick a local folder, and list all files inside it.
+///
+/// The relative path of each file starts with the folder name, the same as
+/// `webkitRelativePath` in browsers. Files are read in chunks to compute their
+/// digests, but not kept in memory.
+///
+/// # Arguments
+/// + `func` - The function to call with the files. Not called if the user
+///   cancels the dialog.
+#[cfg(target_arch = "wasm32")]
+pub fn pick_folder<F>(func: F)
+where
+    F: FnOnce(Vec<UploadFile>) + Send + 'static,
+{
+    use wasm_bindgen::JsCast;
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let input = document
+        .create_element("input")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlInputElement>()
+        .unwrap();
+    input.set_type("file");
+    input.set_multiple(true);
+    input.set_attribute("webkitdirectory", "").unwrap();
+
+    let input_clone = input.clone();
+    let onchange = wasm_bindgen::closure::Closure::once(move || {
+        let files = match input_clone.files() {
+            Some(v) => v,
+            None => return,
+        };
+
+        wasm_bindgen_futures::spawn_local(async move {
+            let mut ret = Vec::new();
+            let mut picked = Vec::new();
+
+            for idx in 0..files.length() {
+                let file = files.get(idx).unwrap();
+
+                let relative_path = js_sys::Reflect::get(&file, &"webkitRelativePath".into())
+                    .ok()
+                    .and_then(|v| v.as_string())
+                    .filter(|v| v.is_empty() == false)
+                    .unwrap_or(file.name());
+
+                let xxh3 = match digest_blob(&file).await {
+                    Ok(v) => v,
+                    Err(e) => {
+                        log::warn!("read {} failed: {:?}", relative_path, e);
+                        continue;
+                    }
+                };
+
+                ret.push(UploadFile {
+                    relative_path: relative_path,
+                    size: file.size() as u64,
+                    xxh3: xxh3,
+                    index: picked.len(),
+                });
+                picked.push(file);
+            }
+
+            PICKED.with(|v| *v.borrow_mut() = picked);
+            func(ret);
+        });
+    });
+
+    input.set_onchange(Some(onchange.as_ref().unchecked_ref()));
+    onchange.forget();
+    input.click();
+}
+
+/// Get the xxh3 digest of a browser file, reading it in slices.
+#[cfg(target_arch = "wasm32")]
+async fn digest_blob(blob: &web_sys::Blob) -> Result<String, wasm_bindgen::JsValue> {
+    let size = blob.size();
+    let mut hasher = xxhash_rust::xxh3::Xxh3::new();
+
+    let mut start = 0.0;
+    while start < size {
+        let end = (start + crate::http_client::CHUNK_SIZE as f64).min(size);
+        let slice = blob.slice_with_f64_and_f64(start, end)?;
+        let buf = wasm_bindgen_futures::JsFuture::from(slice.array_buffer()).await?;
+        hasher.update(&js_sys::Uint8Array::new(&buf).to_vec());
+        start = end;
+    }
+
+    return Ok(format!("{:016x}", hasher.digest()));
+}
+
+/// Ask the user where to save a file, and write it there.
+///
+/// # Arguments
+/// + `name` - The suggested file name.
+/// + `data` - The file content.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn save_file(name: &str, data: Vec<u8>) {
+    let name = name.to_string();
+    std::thread::spawn(move || {
+        let path = match rfd::FileDialog::new()
+            .set_file_name(name.as_str())
+            .save_file()
+        {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Err(e) = std::fs::write(&path, data) {
+            log::warn!("write {} failed: {}", path.display(), e);
+        }
+    });
+}
+
+/// Let the browser download a file.
+///
+/// # Arguments
+/// + `name` - The suggested file name.
+/// + `data` - The file content.
+#[cfg(target_arch = "wasm32")]
+pub fn save_file(name: &str, data: Vec<u8>) {
+    use wasm_bindgen::JsCast;
+
+    let parts = js_sys::Array::new();
+    parts.push(&js_sys::Uint8Array::from(data.as_slice()));
+    let blob = web_sys::Blob::new_with_u8_array_sequence(&parts).unwrap();
+    let url = web_sys::Url::create_object_url_with_blob(&blob).unwrap();
+
+    let document = web_sys::window().unwrap().document().unwrap();
+    let anchor = document
+        .create_element("a")
+        .unwrap()
+        .dyn_into::<web_sys::HtmlAnchorElement>()
+        .unwrap();
+    anchor.set_href(url.as_str());
+    anchor.set_download(name);
+    anchor.click();
+
+    // Some browsers start the download only after the click handler returns.
+    let revoke = wasm_bindgen::closure::Closure::once_into_js(move || {
+        web_sys::Url::revoke_object_url(url.as_str()).ok();
+    });
+    web_sys::window()
+        .unwrap()
+        .set_timeout_with_callback_and_timeout_and_arguments_0(revoke.unchecked_ref(), 60_000)
+        .ok();
+}
@@ -148,6 +148,24 @@ pub struct ReaddirResponseItem {
     /// The last modified time of the file in seconds.
     #[serde(rename = "modified")]
     pub f_modified: u64,
+
+    /// A display label, like the game name of a Steam app id folder.
+    #[serde(rename = "label", default)]
+    pub f_label: Option<String>,
+
+    /// An icon to show instead of the default one.
+    #[serde(rename = "icon", default)]
+    pub f_icon: Option<String>,
+
+    /// The MIME type guessed from the file extension, `None` for
+    /// directories.
+    #[serde(rename = "mime", default)]
+    pub f_mime: Option<String>,
+
+    /// Whether the file looks like a program, like an AppImage or a Windows
+    /// `.exe`.
+    #[serde(rename = "executable", default)]
+    pub f_executable: bool,
 }
 
 impl ReaddirResponse {
@@ -161,3 +179,1448 @@ impl Response for ReaddirResponse {
         return serde_json::from_str(s).unwrap();
     }
 }
+
+/// `/api/complete`: Complete a partial path to existing directories.
+/// See [CompleteResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CompleteRequest {
+    /// The partial absolute path. Its last component is a prefix.
+    pub path: String,
+}
+
+impl Request for CompleteRequest {
+    fn url(&self) -> &str {
+        return "/api/complete";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/complete`: Response of [CompleteRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct CompleteResponse {
+    /// The absolute paths of matching directories, sorted by name.
+    pub dirs: Vec<String>,
+}
+
+impl Response for CompleteResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/paste`: Copy or move files into a directory.
+/// See [PasteResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PasteRequest {
+    /// The absolute paths of the files.
+    pub sources: Vec<String>,
+
+    /// The absolute path of the destination directory.
+    pub dest: String,
+
+    /// Whether to copy or move.
+    pub mode: PasteMode,
+
+    /// What to do with existing destinations.
+    pub conflict: ConflictPolicy,
+}
+
+impl Request for PasteRequest {
+    fn url(&self) -> &str {
+        return "/api/paste";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// Whether [PasteRequest] copies or moves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PasteMode {
+    /// Keep the sources.
+    Copy,
+
+    /// Remove the sources, like cut and paste.
+    Move,
+}
+
+/// What [PasteRequest] does with existing destinations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum ConflictPolicy {
+    /// Change nothing, and report the conflicts.
+    Ask,
+
+    /// Replace the destinations, which are moved to the trash.
+    Overwrite,
+
+    /// Keep the destinations, and skip the sources.
+    Skip,
+
+    /// Keep both, pasting under a new name like `name (2).ext`.
+    Rename,
+}
+
+/// `/api/paste`: Response of [PasteRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PasteResponse {
+    /// Names of existing destinations. Nothing is pasted if there are
+    /// conflicts and the policy is [ConflictPolicy::Ask].
+    pub conflicts: Vec<String>,
+
+    /// The number of pasted files.
+    pub done: u64,
+
+    /// The number of skipped files.
+    pub skipped: u64,
+
+    /// The reasons of failures.
+    pub errors: Vec<String>,
+}
+
+impl Response for PasteResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/rename`: Rename a file or directory, keeping it in the same directory.
+/// See [RenameResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RenameRequest {
+    /// The absolute path of the file.
+    pub path: String,
+
+    /// The new file name, without any directory.
+    pub name: String,
+}
+
+impl Request for RenameRequest {
+    fn url(&self) -> &str {
+        return "/api/rename";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/rename`: Response of [RenameRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RenameResponse {
+    /// The new absolute path of the file.
+    pub path: String,
+}
+
+impl Response for RenameResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/upload`: Response of a multipart upload.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UploadResponse {
+    /// The number of files written.
+    pub files: u64,
+
+    /// The number of directories created.
+    pub dirs: u64,
+
+    /// The number of bytes written.
+    pub bytes: u64,
+
+    /// Files listed in the manifest that were never received.
+    #[serde(default)]
+    pub missing: Vec<String>,
+}
+
+impl Response for UploadResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/upload`: Expected properties of an uploaded file.
+///
+/// A list of items is sent as json in a multipart field named `manifest`,
+/// before the file fields it describes.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UploadManifestItem {
+    /// The relative path of the file, same as the filename of the field.
+    pub path: String,
+
+    /// The expected size of the file in bytes.
+    pub size: Option<u64>,
+
+    /// The expected SHA-256 digest of the file, lowercase hex.
+    pub sha256: Option<String>,
+
+    /// The expected xxh3 digest of the file, lowercase hex.
+    pub xxh3: Option<String>,
+}
+
+/// `/api/upload/progress`: Request the progress of an in-flight upload.
+/// See [UploadProgressResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct UploadProgressRequest {
+    /// The id passed as `id` query string of `/upload`.
+    pub id: String,
+}
+
+impl Request for UploadProgressRequest {
+    fn url(&self) -> &str {
+        return "/api/upload/progress";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/upload/progress`: Response of [UploadProgressRequest].
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct UploadProgressResponse {
+    /// The number of files finished.
+    pub files: u64,
+
+    /// The file being written.
+    pub current: Option<String>,
+
+    /// The number of bytes written.
+    pub bytes_written: u64,
+
+    /// The number of bytes expected, 0 if unknown.
+    pub bytes_total: u64,
+}
+
+impl Response for UploadProgressResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/checksum`: Request checksums of a file.
+/// See [ChecksumResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChecksumRequest {
+    /// The id to cancel the request with [ChecksumCancelRequest].
+    pub id: Option<String>,
+
+    /// The absolute path to the file.
+    pub path: String,
+}
+
+impl Request for ChecksumRequest {
+    fn url(&self) -> &str {
+        return "/api/checksum";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/checksum`: Response of [ChecksumRequest].
+///
+/// All digests are lowercase hex.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChecksumResponse {
+    /// The absolute path to the file.
+    pub path: String,
+
+    /// The number of bytes hashed.
+    pub size: u64,
+
+    /// The CRC32 checksum.
+    pub crc32: String,
+
+    /// The MD5 digest.
+    pub md5: String,
+
+    /// The SHA-1 digest.
+    pub sha1: String,
+
+    /// The SHA-256 digest.
+    pub sha256: String,
+
+    /// The xxh3 64-bit digest.
+    pub xxh3: String,
+}
+
+impl Response for ChecksumResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/checksum/cancel`: Cancel an in-flight [ChecksumRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChecksumCancelRequest {
+    /// The id of the [ChecksumRequest].
+    pub id: String,
+}
+
+impl Request for ChecksumCancelRequest {
+    fn url(&self) -> &str {
+        return "/api/checksum/cancel";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/checksum/cancel`: Response of [ChecksumCancelRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ChecksumCancelResponse {
+    /// Whether a request with the id was in flight.
+    pub cancelled: bool,
+}
+
+impl Response for ChecksumCancelResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/romcheck`: Verify ROMs in a directory against the configured DAT files.
+/// See [RomCheckResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomCheckRequest {
+    /// The absolute path to the ROM directory.
+    pub path: String,
+}
+
+impl Request for RomCheckRequest {
+    fn url(&self) -> &str {
+        return "/api/romcheck";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/romcheck`: Response of [RomCheckRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomCheckResponse {
+    /// The names of the loaded DAT files.
+    pub dats: Vec<String>,
+
+    /// One entry per file, or per file inside a ZIP.
+    pub entries: Vec<RomCheckEntry>,
+
+    /// ROMs of matched games that were not found.
+    pub missing: Vec<RomCheckMissing>,
+}
+
+impl Response for RomCheckResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// The verification result of a [RomCheckEntry].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RomCheckStatus {
+    /// The content and the name match the DAT.
+    Matched,
+
+    /// The content matches the DAT, but the name does not.
+    Rename,
+
+    /// The content matches the DAT, but the file inside the ZIP is named
+    /// differently, so the archive has to be repacked.
+    Repack,
+
+    /// The content is not in any DAT.
+    Unknown,
+}
+
+/// An entry of [RomCheckResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomCheckEntry {
+    /// The absolute path to the file on disk.
+    pub path: String,
+
+    /// The name of the file inside the ZIP, if `path` is a ZIP.
+    pub entry: Option<String>,
+
+    /// The verification result.
+    pub status: RomCheckStatus,
+
+    /// The name of the matched DAT.
+    pub dat: Option<String>,
+
+    /// The name of the matched game.
+    pub game: Option<String>,
+
+    /// The name of the matched ROM.
+    pub rom: Option<String>,
+
+    /// The name `path` should be renamed to, if it can be fixed by renaming.
+    pub fix_name: Option<String>,
+}
+
+/// A ROM listed in [RomCheckResponse::missing].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomCheckMissing {
+    /// The name of the DAT.
+    pub dat: String,
+
+    /// The name of the game.
+    pub game: String,
+
+    /// The name of the ROM.
+    pub rom: String,
+}
+
+/// `/api/romcheck/fix`: Rename files to their DAT names.
+/// See [RomFixResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomFixRequest {
+    /// The files to rename.
+    pub renames: Vec<RomRename>,
+}
+
+impl Request for RomFixRequest {
+    fn url(&self) -> &str {
+        return "/api/romcheck/fix";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// A rename of [RomFixRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomRename {
+    /// The absolute path to the file.
+    pub path: String,
+
+    /// The new file name, in the same directory.
+    pub name: String,
+}
+
+/// `/api/romcheck/fix`: Response of [RomFixRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct RomFixResponse {
+    /// The number of renamed files.
+    pub renamed: u64,
+
+    /// The reasons of failed renames.
+    pub errors: Vec<String>,
+}
+
+impl Response for RomFixResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/du`: Request disk usage of a directory.
+///
+/// The first request for a directory starts a background scan, poll with the
+/// same request until [DuResponse::scanning] is `false`. Subdirectories of a
+/// scanned directory are answered from the cache.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DuRequest {
+    /// The absolute path to the directory.
+    pub path: String,
+
+    /// Whether to descend into other mounted filesystems.
+    pub cross_fs: bool,
+
+    /// Whether to drop the cached result and scan again.
+    pub refresh: bool,
+}
+
+impl Request for DuRequest {
+    fn url(&self) -> &str {
+        return "/api/du";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/du`: Response of [DuRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DuResponse {
+    /// The requested path.
+    pub path: String,
+
+    /// Whether the scan is still running.
+    pub scanning: bool,
+
+    /// The number of entries scanned so far.
+    pub scanned: u64,
+
+    /// The requested directory, once the scan is finished.
+    pub node: Option<DuEntry>,
+
+    /// The entries of the requested directory, largest on disk first.
+    pub children: Vec<DuEntry>,
+}
+
+impl Response for DuResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// An entry of [DuResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct DuEntry {
+    /// The file name.
+    pub name: String,
+
+    /// The absolute path.
+    pub path: String,
+
+    /// Whether this is a directory.
+    pub is_dir: bool,
+
+    /// The sum of file lengths in bytes.
+    pub apparent: u64,
+
+    /// The sum of allocated blocks in bytes.
+    pub disk: u64,
+
+    /// The number of files.
+    pub files: u64,
+}
+
+/// `/api/volumes`: Request mounted filesystems.
+/// See [VolumesResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VolumesRequest {}
+
+impl Request for VolumesRequest {
+    fn url(&self) -> &str {
+        return "/api/volumes";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/volumes`: Response of [VolumesRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct VolumesResponse {
+    /// The mounted filesystems.
+    pub volumes: Vec<Volume>,
+}
+
+impl Response for VolumesResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A mounted filesystem of [VolumesResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Volume {
+    /// The filesystem label, or the mount point name if there is none.
+    pub label: String,
+
+    /// The absolute path where the filesystem is mounted.
+    pub mount_point: String,
+
+    /// The device path, like `/dev/mmcblk0p1`.
+    pub device: String,
+
+    /// The filesystem type, like `ext4`.
+    pub fs_type: String,
+
+    /// The size of the filesystem in bytes.
+    pub total: u64,
+
+    /// The bytes available to unprivileged users.
+    pub free: u64,
+
+    /// The bytes in use.
+    pub used: u64,
+
+    /// Whether the filesystem is mounted read-only.
+    pub read_only: bool,
+
+    /// Whether the filesystem is on removable media, like the microSD card.
+    pub removable: bool,
+}
+
+/// `/api/steam/apps`: Request installed Steam apps.
+/// See [SteamAppsResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamAppsRequest {}
+
+impl Request for SteamAppsRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/apps";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/apps`: Response of [SteamAppsRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamAppsResponse {
+    /// The Steam installation directory, `None` if Steam is not found.
+    pub root: Option<String>,
+
+    /// The installed apps, sorted by name.
+    pub apps: Vec<SteamApp>,
+}
+
+impl Response for SteamAppsResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// An installed Steam app, from its `appmanifest_<appid>.acf`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamApp {
+    /// The Steam app id.
+    pub appid: u32,
+
+    /// The display name, like `ELDEN RING`.
+    pub name: String,
+
+    /// The absolute path to `steamapps/common/<installdir>`.
+    pub install_dir: String,
+
+    /// The absolute path to the library folder.
+    pub library: String,
+
+    /// The size on disk in bytes, as reported by Steam.
+    pub size_on_disk: u64,
+
+    /// The last update time in seconds since epoch.
+    pub last_updated: u64,
+}
+
+/// `/api/steam/prefixes`: Request Proton prefixes of all Steam libraries.
+/// See [SteamPrefixesResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamPrefixesRequest {}
+
+impl Request for SteamPrefixesRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/prefixes";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/prefixes`: Response of [SteamPrefixesRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamPrefixesResponse {
+    /// The prefixes, sorted by game name.
+    pub prefixes: Vec<SteamPrefix>,
+}
+
+impl Response for SteamPrefixesResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A Proton prefix `steamapps/compatdata/<appid>`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamPrefix {
+    /// The app id of the game.
+    pub appid: u32,
+
+    /// The game name, `None` if the game is unknown.
+    pub name: Option<String>,
+
+    /// The absolute path to the prefix.
+    pub path: String,
+}
+
+/// `/api/steam/prefix`: Request well-known locations inside a Proton prefix.
+/// See [SteamPrefixResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamPrefixRequest {
+    /// The app id of the game.
+    pub appid: u32,
+}
+
+impl Request for SteamPrefixRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/prefix";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/prefix`: Response of [SteamPrefixRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamPrefixResponse {
+    /// The prefix.
+    pub prefix: SteamPrefix,
+
+    /// The existing well-known locations, like `AppData/Roaming`.
+    pub locations: Vec<PrefixLocation>,
+}
+
+impl Response for SteamPrefixResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A location of [SteamPrefixResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PrefixLocation {
+    /// The display name, like `AppData/Roaming`.
+    pub name: String,
+
+    /// The absolute path.
+    pub path: String,
+}
+
+/// A non-Steam game, see [SteamShortcutsRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcut {
+    /// The id of the Steam user owning the shortcut.
+    pub user: String,
+
+    /// The app id, also the name of its `compatdata` folder.
+    pub appid: u32,
+
+    /// The display name.
+    pub name: String,
+
+    /// The executable.
+    pub exe: String,
+
+    /// The working directory.
+    pub start_dir: String,
+
+    /// The launch options.
+    pub launch_options: String,
+}
+
+/// `/api/steam/shortcuts`: List non-Steam games of all Steam users.
+/// See [SteamShortcutsResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcutsRequest {}
+
+impl Request for SteamShortcutsRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/shortcuts";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/shortcuts`: Response of [SteamShortcutsRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcutsResponse {
+    /// The ids of Steam users.
+    pub users: Vec<String>,
+
+    /// The non-Steam games, sorted by name.
+    pub shortcuts: Vec<SteamShortcut>,
+
+    /// Whether Steam is running, and would overwrite changes to shortcuts
+    /// when it exits.
+    #[serde(default)]
+    pub steam_running: bool,
+}
+
+impl Response for SteamShortcutsResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/steam/shortcuts/add`: Add a non-Steam game.
+/// See [SteamShortcutAddResponse] for the response.
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcutAddRequest {
+    /// The id of the Steam user, or `None` for the last logged in user.
+    pub user: Option<String>,
+
+    /// The display name, the file name of `exe` if empty.
+    pub name: String,
+
+    /// The executable.
+    pub exe: String,
+
+    /// The working directory, the folder of `exe` if empty.
+    pub start_dir: String,
+
+    /// The launch options.
+    pub launch_options: String,
+}
+
+impl Request for SteamShortcutAddRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/shortcuts/add";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/shortcuts/add`: Response of [SteamShortcutAddRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcutAddResponse {
+    /// The new shortcut.
+    pub shortcut: SteamShortcut,
+}
+
+impl Response for SteamShortcutAddResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/steam/shortcuts/remove`: Remove a non-Steam game.
+/// See [SteamShortcutRemoveResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcutRemoveRequest {
+    /// The id of the Steam user owning the shortcut.
+    pub user: String,
+
+    /// The app id of the shortcut.
+    pub appid: u32,
+}
+
+impl Request for SteamShortcutRemoveRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/shortcuts/remove";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/shortcuts/remove`: Response of [SteamShortcutRemoveRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamShortcutRemoveResponse {}
+
+impl Response for SteamShortcutRemoveResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/steam/screenshots`: List screenshots of all Steam users.
+/// See [SteamScreenshotsResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamScreenshotsRequest {}
+
+impl Request for SteamScreenshotsRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/screenshots";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/screenshots`: Response of [SteamScreenshotsRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamScreenshotsResponse {
+    /// The games with screenshots, sorted by name. Unknown games come last.
+    pub games: Vec<ScreenshotGame>,
+}
+
+impl Response for SteamScreenshotsResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// The screenshots of a game, see [SteamScreenshotsResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct ScreenshotGame {
+    /// The game id, the app id for Steam games.
+    pub gameid: u64,
+
+    /// The game name, `None` if the game is unknown.
+    pub name: Option<String>,
+
+    /// The screenshots, newest first.
+    pub screenshots: Vec<Screenshot>,
+}
+
+/// A screenshot, see [ScreenshotGame].
+///
+/// Images are served by `/api/steam/screenshot?path=<path>`.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct Screenshot {
+    /// The absolute path to the image.
+    pub path: String,
+
+    /// The absolute path to the thumbnail, `None` if Steam did not create one.
+    pub thumbnail: Option<String>,
+
+    /// The unix timestamp when the screenshot was taken.
+    pub created: u64,
+
+    /// The size of the image in bytes.
+    pub size: u64,
+}
+
+/// `/api/steam/screenshots/zip`: Download screenshots as a zip archive.
+///
+/// The response is the archive itself.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamScreenshotsZipRequest {
+    /// The paths of the screenshots.
+    pub paths: Vec<String>,
+}
+
+impl Request for SteamScreenshotsZipRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/screenshots/zip";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/cleanup`: Find `compatdata` and `shadercache` folders of
+/// uninstalled games.
+/// See [SteamCleanupResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamCleanupRequest {}
+
+impl Request for SteamCleanupRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/cleanup";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/cleanup`: Response of [SteamCleanupRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamCleanupResponse {
+    /// The orphaned folders, largest first.
+    pub orphans: Vec<SteamOrphan>,
+}
+
+impl Response for SteamCleanupResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A folder of an uninstalled game, see [SteamCleanupResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamOrphan {
+    /// The kind of folder, `compatdata` or `shadercache`.
+    pub kind: String,
+
+    /// The app id of the uninstalled game.
+    pub appid: u32,
+
+    /// The absolute path.
+    pub path: String,
+
+    /// The size on disk in bytes.
+    pub size: u64,
+}
+
+/// `/api/steam/cleanup/remove`: Remove orphaned folders.
+/// See [SteamCleanupRemoveResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamCleanupRemoveRequest {
+    /// The folders to remove, from [SteamCleanupResponse].
+    pub paths: Vec<String>,
+}
+
+impl Request for SteamCleanupRemoveRequest {
+    fn url(&self) -> &str {
+        return "/api/steam/cleanup/remove";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/steam/cleanup/remove`: Response of [SteamCleanupRemoveRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SteamCleanupRemoveResponse {
+    /// The number of folders moved to the trash.
+    pub trashed: u64,
+
+    /// The reasons of failed removals.
+    pub errors: Vec<String>,
+}
+
+impl Response for SteamCleanupRemoveResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A trashed file, see [TrashListResponse].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashItem {
+    /// The absolute path inside the trash, identifying the item.
+    pub path: String,
+
+    /// The original file name.
+    pub name: String,
+
+    /// The absolute path the file was deleted from.
+    pub original_path: String,
+
+    /// The unix timestamp of the deletion.
+    pub deleted: u64,
+
+    /// Whether this is a directory.
+    pub is_dir: bool,
+
+    /// The size in bytes, `None` for a directory, see [TrashSizeRequest].
+    pub size: Option<u64>,
+}
+
+/// `/api/trash/list`: List trashed files of the home trash and all volumes.
+/// See [TrashListResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashListRequest {}
+
+impl Request for TrashListRequest {
+    fn url(&self) -> &str {
+        return "/api/trash/list";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/trash/list`: Response of [TrashListRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashListResponse {
+    /// The trashed files, most recently deleted first.
+    pub items: Vec<TrashItem>,
+}
+
+impl Response for TrashListResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/trash/size`: Sum up the sizes of trashed directories.
+/// See [TrashSizeResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashSizeRequest {
+    /// The paths inside the trash, see [TrashItem::path].
+    pub paths: Vec<String>,
+}
+
+impl Request for TrashSizeRequest {
+    fn url(&self) -> &str {
+        return "/api/trash/size";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/trash/size`: Response of [TrashSizeRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashSizeResponse {
+    /// The sizes in bytes of all files, by path. Paths that could not be
+    /// scanned are left out.
+    pub sizes: std::collections::HashMap<String, u64>,
+}
+
+impl Response for TrashSizeResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/trash/put`: Move files to the trash.
+/// See [TrashPutResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashPutRequest {
+    /// The absolute paths of the files.
+    pub paths: Vec<String>,
+}
+
+impl Request for TrashPutRequest {
+    fn url(&self) -> &str {
+        return "/api/trash/put";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/trash/put`: Response of [TrashPutRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashPutResponse {
+    /// The number of trashed files.
+    pub trashed: u64,
+
+    /// The reasons of failures.
+    pub errors: Vec<String>,
+}
+
+impl Response for TrashPutResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/trash/restore`: Move trashed files back to where they were deleted
+/// from.
+/// See [TrashRestoreResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashRestoreRequest {
+    /// The paths inside the trash, see [TrashItem::path].
+    pub paths: Vec<String>,
+}
+
+impl Request for TrashRestoreRequest {
+    fn url(&self) -> &str {
+        return "/api/trash/restore";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/trash/restore`: Response of [TrashRestoreRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashRestoreResponse {
+    /// The original paths of the restored files.
+    pub restored: Vec<String>,
+
+    /// The reasons of failures, like a file existing at the original path.
+    pub errors: Vec<String>,
+}
+
+impl Response for TrashRestoreResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/trash/empty`: Delete trashed files permanently.
+/// See [TrashEmptyResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashEmptyRequest {
+    /// The paths inside the trash, see [TrashItem::path], or `None` to empty
+    /// all trashes.
+    pub paths: Option<Vec<String>>,
+}
+
+impl Request for TrashEmptyRequest {
+    fn url(&self) -> &str {
+        return "/api/trash/empty";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/trash/empty`: Response of [TrashEmptyRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TrashEmptyResponse {
+    /// The number of deleted files.
+    pub deleted: u64,
+
+    /// The reasons of failures.
+    pub errors: Vec<String>,
+}
+
+impl Response for TrashEmptyResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A snapshot of a folder, see [BackupCreateRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupSnapshot {
+    /// The id of the snapshot, unique across all folders.
+    pub id: String,
+
+    /// The absolute path of the backed up folder.
+    pub source: String,
+
+    /// The unix timestamp when the snapshot was taken.
+    pub created: u64,
+
+    /// The compressed size in bytes.
+    pub size: u64,
+
+    /// The number of files in the snapshot.
+    pub files: u64,
+}
+
+/// `/api/backup/create`: Snapshot a folder.
+/// See [BackupCreateResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupCreateRequest {
+    /// The folder to back up.
+    pub path: String,
+}
+
+impl Request for BackupCreateRequest {
+    fn url(&self) -> &str {
+        return "/api/backup/create";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/backup/create`: Response of [BackupCreateRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupCreateResponse {
+    /// The new snapshot.
+    pub snapshot: BackupSnapshot,
+
+    /// Symlinks and special files of the folder that were not backed up.
+    #[serde(default)]
+    pub skipped: Vec<String>,
+}
+
+impl Response for BackupCreateResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/backup/list`: List snapshots.
+/// See [BackupListResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupListRequest {
+    /// Only list snapshots of this folder, or all if `None`.
+    pub path: Option<String>,
+}
+
+impl Request for BackupListRequest {
+    fn url(&self) -> &str {
+        return "/api/backup/list";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/backup/list`: Response of [BackupListRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupListResponse {
+    /// The snapshots, newest first.
+    pub snapshots: Vec<BackupSnapshot>,
+}
+
+impl Response for BackupListResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/backup/restore`: Replace a folder with the content of a snapshot.
+///
+/// The current content of the folder is snapshotted first.
+/// See [BackupRestoreResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupRestoreRequest {
+    /// The id of the snapshot to restore.
+    pub id: String,
+}
+
+impl Request for BackupRestoreRequest {
+    fn url(&self) -> &str {
+        return "/api/backup/restore";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/backup/restore`: Response of [BackupRestoreRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupRestoreResponse {
+    /// The snapshot of the folder before restoring, `None` if the folder did
+    /// not exist.
+    pub previous: Option<BackupSnapshot>,
+
+    /// Symlinks and special files of the folder that were not in `previous`
+    /// and are gone after restoring.
+    #[serde(default)]
+    pub skipped: Vec<String>,
+}
+
+impl Response for BackupRestoreResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/backup/delete`: Delete a snapshot.
+/// See [BackupDeleteResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupDeleteRequest {
+    /// The id of the snapshot to delete.
+    pub id: String,
+}
+
+impl Request for BackupDeleteRequest {
+    fn url(&self) -> &str {
+        return "/api/backup/delete";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/backup/delete`: Response of [BackupDeleteRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BackupDeleteResponse {}
+
+impl Response for BackupDeleteResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// A bookmarked folder, see [BookmarkListRequest].
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Bookmark {
+    /// The absolute path to the folder.
+    pub path: String,
+
+    /// The name shown in the side panel.
+    pub name: String,
+}
+
+/// `/api/bookmark/list`: List the bookmarked folders.
+/// See [BookmarkListResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkListRequest {}
+
+impl Request for BookmarkListRequest {
+    fn url(&self) -> &str {
+        return "/api/bookmark/list";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/bookmark/list`: Response of [BookmarkListRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkListResponse {
+    /// The bookmarks, in order.
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl Response for BookmarkListResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/bookmark/add`: Bookmark a folder, or rename its bookmark.
+/// See [BookmarkAddResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkAddRequest {
+    /// The folder to bookmark.
+    pub path: String,
+
+    /// The name shown in the side panel, the folder name if `None`.
+    pub name: Option<String>,
+}
+
+impl Request for BookmarkAddRequest {
+    fn url(&self) -> &str {
+        return "/api/bookmark/add";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/bookmark/add`: Response of [BookmarkAddRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkAddResponse {
+    /// The bookmarks after the change, in order.
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl Response for BookmarkAddResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/bookmark/remove`: Remove the bookmark of a folder.
+/// See [BookmarkRemoveResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkRemoveRequest {
+    /// The bookmarked folder.
+    pub path: String,
+}
+
+impl Request for BookmarkRemoveRequest {
+    fn url(&self) -> &str {
+        return "/api/bookmark/remove";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/bookmark/remove`: Response of [BookmarkRemoveRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkRemoveResponse {
+    /// The bookmarks after the change, in order.
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl Response for BookmarkRemoveResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
+
+/// `/api/bookmark/move`: Move a bookmark to another position.
+/// See [BookmarkMoveResponse] for the response.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkMoveRequest {
+    /// The bookmarked folder.
+    pub path: String,
+
+    /// The new position, counted from 0.
+    pub index: usize,
+}
+
+impl Request for BookmarkMoveRequest {
+    fn url(&self) -> &str {
+        return "/api/bookmark/move";
+    }
+    fn to_json(&self) -> Option<serde_json::Value> {
+        return Some(serde_json::to_value(self).unwrap());
+    }
+}
+
+/// `/api/bookmark/move`: Response of [BookmarkMoveRequest].
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct BookmarkMoveResponse {
+    /// The bookmarks after the change, in order.
+    pub bookmarks: Vec<Bookmark>,
+}
+
+impl Response for BookmarkMoveResponse {
+    fn from_json(s: &str) -> Self {
+        return serde_json::from_str(s).unwrap();
+    }
+}
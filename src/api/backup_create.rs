@@ -0,0 +1,33 @@
+/// Snapshot a folder into the backup directory.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "path": "path/to/saves"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BackupCreateResponse].
+#[actix_web::post("/api/backup/create")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BackupCreateRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+    let backup = config.backup.clone();
+
+    let (snapshot, skipped) =
+        actix_web::web::block(move || crate::backup::create(&backup, path.as_str())).await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BackupCreateResponse {
+            snapshot: snapshot,
+            skipped: skipped,
+        },
+    ))
+}
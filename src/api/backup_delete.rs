@@ -0,0 +1,29 @@
+/// Delete a snapshot from the backup directory.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "id": "saves-0123456789abcdef/1700000000000.zip"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// An empty json object.
+#[actix_web::post("/api/backup/delete")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BackupDeleteRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let id = info.id.clone();
+    let backup = config.backup.clone();
+
+    actix_web::web::block(move || crate::backup::delete(&backup, id.as_str())).await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BackupDeleteResponse {},
+    ))
+}
@@ -0,0 +1,30 @@
+/// List snapshots in the backup directory.
+///
+/// # Arguments
+///
+/// The query body is a json object, `path` is optional:
+///
+/// ```json
+/// {
+///     "path": "path/to/saves"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BackupListResponse].
+#[actix_web::post("/api/backup/list")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BackupListRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+    let backup = config.backup.clone();
+
+    let ret =
+        actix_web::web::block(move || crate::backup::list(&backup, path.as_deref())).await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BackupListResponse { snapshots: ret },
+    ))
+}
@@ -0,0 +1,33 @@
+/// Restore a snapshot into its folder, after snapshotting the current content.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "id": "saves-0123456789abcdef/1700000000000.zip"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BackupRestoreResponse].
+#[actix_web::post("/api/backup/restore")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BackupRestoreRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let id = info.id.clone();
+    let backup = config.backup.clone();
+
+    let (previous, skipped) =
+        actix_web::web::block(move || crate::backup::restore(&backup, id.as_str())).await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BackupRestoreResponse {
+            previous: previous,
+            skipped: skipped,
+        },
+    ))
+}
@@ -0,0 +1,35 @@
+/// Bookmark a folder, or rename its bookmark.
+///
+/// # Arguments
+///
+/// The query body is a json object, `name` may be null for the folder name:
+///
+/// ```json
+/// {
+///     "path": "/home/deck/Emulation/roms",
+///     "name": "ROMs"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BookmarkAddResponse].
+#[actix_web::post("/api/bookmark/add")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BookmarkAddRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let req = info.into_inner();
+    let file = config.bookmarks_file.clone();
+
+    let ret = actix_web::web::block(move || {
+        let _lock = data.bookmarks.lock().unwrap();
+        crate::bookmarks::add(&file, req.path.as_str(), req.name.as_deref())
+    })
+    .await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BookmarkAddResponse { bookmarks: ret },
+    ))
+}
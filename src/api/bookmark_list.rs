@@ -0,0 +1,27 @@
+/// List the bookmarked folders.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BookmarkListResponse].
+#[actix_web::post("/api/bookmark/list")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::BookmarkListRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let file = config.bookmarks_file.clone();
+
+    let ret = actix_web::web::block(move || {
+        let _lock = data.bookmarks.lock().unwrap();
+        crate::bookmarks::list(&file)
+    })
+    .await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BookmarkListResponse { bookmarks: ret },
+    ))
+}
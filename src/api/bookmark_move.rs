@@ -0,0 +1,37 @@
+/// Move the bookmark of a folder to another position in the list.
+///
+/// # Arguments
+///
+/// The query body is a json object, `index` is the new position counted
+/// from 0:
+///
+/// ```json
+/// {
+///     "path": "/home/deck/Emulation/roms",
+///     "index": 0
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BookmarkMoveResponse].
+#[actix_web::post("/api/bookmark/move")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BookmarkMoveRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+    let index = info.index;
+    let file = config.bookmarks_file.clone();
+
+    let ret = actix_web::web::block(move || {
+        let _lock = data.bookmarks.lock().unwrap();
+        crate::bookmarks::move_to(&file, path.as_str(), index)
+    })
+    .await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BookmarkMoveResponse { bookmarks: ret },
+    ))
+}
@@ -0,0 +1,34 @@
+/// Remove the bookmark of a folder.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "path": "/home/deck/Emulation/roms"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::BookmarkRemoveResponse].
+#[actix_web::post("/api/bookmark/remove")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::BookmarkRemoveRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+    let file = config.bookmarks_file.clone();
+
+    let ret = actix_web::web::block(move || {
+        let _lock = data.bookmarks.lock().unwrap();
+        crate::bookmarks::remove(&file, path.as_str())
+    })
+    .await??;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::BookmarkRemoveResponse { bookmarks: ret },
+    ))
+}
@@ -0,0 +1,81 @@
+/// Compute CRC32, MD5, SHA-1, SHA-256 and xxh3 of a file.
+///
+/// The file is streamed on the blocking thread pool. The computation stops
+/// when the client goes away or cancels it through `/api/checksum/cancel`.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "id": "optional id to cancel with",
+///     "path": "path/to/file"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::ChecksumResponse].
+#[actix_web::post("/api/checksum")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::ChecksumRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let guard = CancelGuard::new(data, info.id.clone());
+
+    let path = info.path.clone();
+    let cancel = guard.cancel.clone();
+    let ret =
+        actix_web::web::block(move || crate::hash::checksum_file(path.as_str(), &cancel)).await?;
+
+    let rsp = match ret {
+        Ok(v) => actix_web::HttpResponse::Ok().json(v),
+        Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {
+            tracing::debug!("checksum of {} cancelled", info.path);
+            actix_web::HttpResponse::BadRequest().body(e.to_string())
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    return Ok(rsp);
+}
+
+/// Register the cancel flag of a checksum request in
+/// [crate::webserver::SharedData], and raise it when the request is dropped.
+struct CancelGuard {
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+    id: Option<String>,
+    cancel: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl CancelGuard {
+    fn new(data: actix_web::web::Data<crate::webserver::SharedData>, id: Option<String>) -> Self {
+        let cancel = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+
+        if let Some(id) = &id {
+            data.checksums
+                .lock()
+                .unwrap()
+                .insert(id.clone(), cancel.clone());
+        }
+
+        return CancelGuard {
+            data: data,
+            id: id,
+            cancel: cancel,
+        };
+    }
+}
+
+impl Drop for CancelGuard {
+    fn drop(&mut self) {
+        // Stop the blocking task if the client went away.
+        self.cancel
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+
+        if let Some(id) = &self.id {
+            self.data.checksums.lock().unwrap().remove(id);
+        }
+    }
+}
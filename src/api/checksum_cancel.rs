@@ -0,0 +1,34 @@
+/// Cancel an in-flight `/api/checksum` request.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "id": "checksum request id"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::ChecksumCancelResponse].
+#[actix_web::post("/api/checksum/cancel")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::ChecksumCancelRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> impl actix_web::Responder {
+    let checksums = data.checksums.lock().unwrap();
+
+    let cancelled = match checksums.get(&info.id) {
+        Some(v) => {
+            v.store(true, std::sync::atomic::Ordering::Relaxed);
+            true
+        }
+        None => false,
+    };
+
+    actix_web::web::Json(frontend::protocol::ChecksumCancelResponse {
+        cancelled: cancelled,
+    })
+}
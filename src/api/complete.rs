@@ -0,0 +1,65 @@
+/// The maximum number of completions returned.
+const MAX_COMPLETIONS: usize = 100;
+
+/// Complete a partial path to existing directories.
+///
+/// # Arguments
+///
+/// The query body is a json object. The last component is matched as a case
+/// insensitive prefix, so a path ending with `/` lists all subdirectories:
+///
+/// ```json
+/// {
+///     "path": "/home/deck/Do"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::CompleteResponse].
+#[actix_web::post("/api/complete")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::CompleteRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+
+    let ret = actix_web::web::block(move || complete(path.as_str())).await?;
+
+    Ok(actix_web::web::Json(frontend::protocol::CompleteResponse {
+        dirs: ret,
+    }))
+}
+
+fn complete(path: &str) -> Vec<String> {
+    let (dir, prefix) = match path.rfind('/') {
+        Some(idx) => (&path[..idx + 1], &path[idx + 1..]),
+        None => return Vec::new(),
+    };
+
+    let entries = match std::fs::read_dir(dir) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+
+    let prefix_lower = prefix.to_lowercase();
+    let mut names: Vec<String> = entries
+        .flatten()
+        .filter(|v| v.path().is_dir())
+        .map(|v| v.file_name().to_string_lossy().to_string())
+        .filter(|v| v.to_lowercase().starts_with(prefix_lower.as_str()))
+        // Hidden directories only if asked for.
+        .filter(|v| v.starts_with('.') == false || prefix.starts_with('.'))
+        .collect();
+    names.sort_by_key(|v| v.to_lowercase());
+    names.truncate(MAX_COMPLETIONS);
+
+    return names
+        .into_iter()
+        .map(|v| {
+            std::path::Path::new(dir)
+                .join(v)
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect();
+}
@@ -0,0 +1,76 @@
+/// The size of the chunks a file is streamed in.
+const CHUNK_SIZE: usize = 256 * 1024;
+
+#[derive(serde::Deserialize)]
+struct DownloadRequest {
+    /// The path to the file.
+    path: String,
+}
+
+/// Download a file as an attachment. The file is streamed, so large files
+/// are never read into memory.
+///
+/// # Arguments
+/// + `path`: The path to the file, passed as query string.
+#[actix_web::get("/api/download")]
+pub async fn get(
+    info: actix_web::web::Query<DownloadRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = std::path::PathBuf::from(&info.path);
+
+    let f = match tokio::fs::File::open(&path).await {
+        Ok(v) => v,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            return Err(actix_web::error::ErrorNotFound(e));
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let metadata = f.metadata().await?;
+    if metadata.is_file() == false {
+        return Err(actix_web::error::ErrorBadRequest(format!(
+            "{} is not a file",
+            info.path
+        )));
+    }
+
+    let name = path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    tracing::info!("download {}", info.path);
+
+    Ok(actix_web::HttpResponse::Ok()
+        .content_type(
+            mime_guess::from_path(&path)
+                .first_or_octet_stream()
+                .as_ref(),
+        )
+        .insert_header(actix_web::http::header::ContentDisposition::attachment(
+            name,
+        ))
+        .no_chunking(metadata.len())
+        .streaming(stream(f)))
+}
+
+/// Stream a file in chunks of [CHUNK_SIZE] from its current position.
+///
+/// # Arguments
+/// + `f`: The file to stream.
+pub(crate) fn stream(
+    f: tokio::fs::File,
+) -> impl futures_util::Stream<Item = Result<actix_web::web::Bytes, std::io::Error>> {
+    return futures_util::stream::unfold(Some(f), |f| async move {
+        let mut f = f?;
+        let mut buf = vec![0u8; CHUNK_SIZE];
+        match tokio::io::AsyncReadExt::read(&mut f, &mut buf).await {
+            Ok(0) => None,
+            Ok(n) => {
+                buf.truncate(n);
+                Some((Ok(actix_web::web::Bytes::from(buf)), Some(f)))
+            }
+            // End the stream after an error.
+            Err(e) => Some((Err::<actix_web::web::Bytes, std::io::Error>(e), None)),
+        }
+    });
+}
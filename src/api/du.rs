@@ -0,0 +1,183 @@
+/// Finished scans kept in [crate::webserver::SharedData], the least recently
+/// used are dropped first.
+const MAX_JOBS: usize = 8;
+
+/// Finished scans not used for this long are dropped.
+const MAX_JOB_AGE: std::time::Duration = std::time::Duration::from_secs(30 * 60);
+
+/// The id of the next scan.
+static NEXT_JOB_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// A disk usage scan, cached in [crate::webserver::SharedData].
+pub struct DuJob {
+    /// Tells apart scans of the same directory, when a refresh drops a scan
+    /// that is still running.
+    pub id: u64,
+
+    /// The scanned directory.
+    pub root: std::path::PathBuf,
+
+    /// Whether the scan descends into other mounted filesystems.
+    pub cross_fs: bool,
+
+    /// The number of entries scanned so far.
+    pub scanned: std::sync::Arc<std::sync::atomic::AtomicU64>,
+
+    /// The result, `None` while scanning.
+    pub result: Option<std::sync::Arc<crate::du::Node>>,
+
+    /// When the scan was last started or queried.
+    pub used: std::time::Instant,
+}
+
+/// Get disk usage of a directory.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "path": "path/to/dir",
+///     "cross_fs": false,
+///     "refresh": false
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::DuResponse].
+#[actix_web::post("/api/du")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::DuRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = std::path::PathBuf::from(&info.path);
+    let mut jobs = data.du_jobs.lock().unwrap();
+
+    jobs.retain(|v| v.result.is_none() || v.used.elapsed() < MAX_JOB_AGE);
+    if info.refresh {
+        jobs.retain(|v| path.starts_with(&v.root) == false && v.root.starts_with(&path) == false);
+    }
+
+    // The most specific scan containing the path.
+    let job = jobs
+        .iter_mut()
+        .filter(|v| v.cross_fs == info.cross_fs && path.starts_with(&v.root))
+        .max_by_key(|v| v.root.components().count());
+
+    let mut ret = frontend::protocol::DuResponse {
+        path: info.path.clone(),
+        scanning: true,
+        scanned: 0,
+        node: None,
+        children: Vec::new(),
+    };
+
+    let job = match job {
+        Some(v) => v,
+        None => {
+            start_scan(&mut jobs, data.clone(), path, info.cross_fs);
+            return Ok(actix_web::web::Json(ret));
+        }
+    };
+
+    job.used = std::time::Instant::now();
+    ret.scanned = job.scanned.load(std::sync::atomic::Ordering::Relaxed);
+    let result = match &job.result {
+        Some(v) => v.clone(),
+        None => return Ok(actix_web::web::Json(ret)),
+    };
+
+    // Not in the tree, like a mount point skipped by a scan that does not
+    // cross filesystems, so scan the path itself.
+    let node = match result.find(path.strip_prefix(&job.root).unwrap()) {
+        Some(v) => v,
+        None if path.is_dir() == false => {
+            return Err(actix_web::error::ErrorNotFound(format!(
+                "{} not found in scan of {}",
+                info.path,
+                job.root.display()
+            )));
+        }
+        None => {
+            start_scan(&mut jobs, data.clone(), path, info.cross_fs);
+            return Ok(actix_web::web::Json(ret));
+        }
+    };
+
+    ret.scanning = false;
+    ret.node = Some(to_entry(node, &path));
+    ret.children = node
+        .children
+        .iter()
+        .map(|v| to_entry(v, &path.join(&v.name)))
+        .collect();
+    ret.children.sort_by_key(|v| std::cmp::Reverse(v.disk));
+
+    Ok(actix_web::web::Json(ret))
+}
+
+fn start_scan(
+    jobs: &mut Vec<DuJob>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+    root: std::path::PathBuf,
+    cross_fs: bool,
+) {
+    // Drop the least recently used finished scans.
+    loop {
+        let finished = jobs.iter().filter(|v| v.result.is_some());
+        if finished.clone().count() < MAX_JOBS {
+            break;
+        }
+        let oldest = finished.map(|v| v.used).min().unwrap();
+        jobs.retain(|v| v.result.is_none() || v.used != oldest);
+    }
+
+    let id = NEXT_JOB_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let scanned = std::sync::Arc::new(std::sync::atomic::AtomicU64::new(0));
+    jobs.push(DuJob {
+        id: id,
+        root: root.clone(),
+        cross_fs: cross_fs,
+        scanned: scanned.clone(),
+        result: None,
+        used: std::time::Instant::now(),
+    });
+
+    tracing::info!("start disk usage scan of {}", root.display());
+
+    actix_web::rt::task::spawn_blocking(move || {
+        let ret = crate::du::scan(&root, cross_fs, &scanned);
+
+        let mut jobs = data.du_jobs.lock().unwrap();
+        let job = jobs.iter().position(|v| v.id == id);
+        let idx = match job {
+            Some(v) => v,
+            // Dropped by a refresh while scanning.
+            None => return,
+        };
+
+        match ret {
+            Ok(v) => {
+                tracing::info!("finish disk usage scan of {}", root.display());
+                jobs[idx].result = Some(std::sync::Arc::new(v));
+            }
+            Err(e) => {
+                tracing::warn!("disk usage scan of {} failed: {}", root.display(), e);
+                jobs.remove(idx);
+            }
+        }
+    });
+}
+
+fn to_entry(node: &crate::du::Node, path: &std::path::Path) -> frontend::protocol::DuEntry {
+    return frontend::protocol::DuEntry {
+        name: node.name.clone(),
+        path: path.to_string_lossy().to_string(),
+        is_dir: node.is_dir,
+        apparent: node.apparent,
+        disk: node.disk,
+        files: node.files,
+    };
+}
@@ -1,5 +1,40 @@
 pub mod assets;
+pub mod backup_create;
+pub mod backup_delete;
+pub mod backup_list;
+pub mod backup_restore;
+pub mod bookmark_add;
+pub mod bookmark_list;
+pub mod bookmark_move;
+pub mod bookmark_remove;
+pub mod checksum;
+pub mod checksum_cancel;
+pub mod complete;
 pub mod dirs;
+pub mod download;
+pub mod du;
 pub mod index;
+pub mod paste;
 pub mod readdir;
+pub mod rename;
+pub mod romcheck;
+pub mod romcheck_fix;
+pub mod steam_apps;
+pub mod steam_cleanup;
+pub mod steam_cleanup_remove;
+pub mod steam_prefix;
+pub mod steam_prefixes;
+pub mod steam_screenshot;
+pub mod steam_screenshots;
+pub mod steam_screenshots_zip;
+pub mod steam_shortcuts;
+pub mod steam_shortcuts_add;
+pub mod steam_shortcuts_remove;
+pub mod trash_empty;
+pub mod trash_list;
+pub mod trash_put;
+pub mod trash_restore;
+pub mod trash_size;
 pub mod upload;
+pub mod upload_progress;
+pub mod volumes;
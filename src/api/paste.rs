@@ -0,0 +1,31 @@
+/// Copy or move files into a directory.
+///
+/// # Arguments
+///
+/// The query body is a json object, `mode` is `Copy` or `Move`, and
+/// `conflict` is `Ask`, `Overwrite`, `Skip` or `Rename`:
+///
+/// ```json
+/// {
+///     "sources": [
+///         "path/to/file"
+///     ],
+///     "dest": "path/to/dir",
+///     "mode": "Copy",
+///     "conflict": "Ask"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::PasteResponse].
+#[actix_web::post("/api/paste")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::PasteRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let req = info.into_inner();
+
+    let ret = actix_web::web::block(move || crate::fileops::paste(&req)).await?;
+
+    Ok(actix_web::web::Json(ret))
+}
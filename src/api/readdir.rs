@@ -25,6 +25,10 @@
 ///             "type": "FILE",
 ///             "size": 123,
 ///             "modified": 123456789,
+///             "label": "game name of a Steam app id folder, or null",
+///             "icon": "icon of a Steam app id folder, or null",
+///             "mime": "image/png, or null for directories",
+///             "executable": false
 ///         },
 ///         // more entries list
 ///     ]
@@ -33,24 +37,36 @@
 #[actix_web::post("/api/readdir")]
 pub async fn post(
     info: actix_web::web::Json<frontend::protocol::ReaddirRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
 ) -> actix_web::Result<impl actix_web::Responder> {
-    let path = &info.path;
-    let ret = listdir(path.as_str()).await?;
+    let path = std::path::PathBuf::from(&info.path);
+
+    // The scan reads the start of every file with an executable bit, which
+    // is slow on exFAT cards, so it runs off the async workers.
+    let ret = actix_web::web::block(move || {
+        let mut ret = listdir(&path)?;
+        if crate::steam::is_annotated(&path) {
+            crate::steam::annotate(&data.steam_apps, &path, &mut ret.entries);
+        }
+        Ok::<_, std::io::Error>(ret)
+    })
+    .await??;
 
     Ok(actix_web::web::Json(ret))
 }
 
-async fn listdir(path: &str) -> Result<frontend::protocol::ReaddirResponse, std::io::Error> {
+fn listdir(path: &std::path::Path) -> Result<frontend::protocol::ReaddirResponse, std::io::Error> {
     let mut ret = Vec::<frontend::protocol::ReaddirResponseItem>::new();
-    let mut entries = tokio::fs::read_dir(path).await?;
 
-    while let Some(entry) = entries.next_entry().await? {
-        let metadata = entry.metadata().await?;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        let is_dir = entry.file_type()?.is_dir();
 
         ret.push(frontend::protocol::ReaddirResponseItem {
             f_name: entry.file_name().to_str().unwrap().to_string(),
             f_path: entry.path().to_str().unwrap().to_string(),
-            f_type: if entry.file_type().await?.is_dir() {
+            f_type: if is_dir {
                 "DIR".to_string()
             } else {
                 "FILE".to_string()
@@ -63,8 +79,63 @@ async fn listdir(path: &str) -> Result<frontend::protocol::ReaddirResponse, std:
                     .as_secs(),
                 Err(_) => 0,
             },
+            f_label: None,
+            f_icon: None,
+            f_mime: match is_dir {
+                true => None,
+                false => mime_guess::from_path(entry.path())
+                    .first()
+                    .map(|v| v.to_string()),
+            },
+            f_executable: is_executable(&entry.path(), &metadata),
         });
     }
 
     return Ok(frontend::protocol::ReaddirResponse { entries: ret });
 }
+
+/// Whether a file looks like a program: it has an executable bit and starts
+/// like a program, or it is a Windows program that runs through Proton.
+///
+/// The content is checked as well, since exFAT and FAT cards report every file
+/// as executable.
+fn is_executable(path: &std::path::Path, metadata: &std::fs::Metadata) -> bool {
+    if metadata.is_file() == false {
+        return false;
+    }
+
+    let ext = path
+        .extension()
+        .map(|v| v.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    if ["exe", "bat", "msi", "appimage"].contains(&ext.as_str()) {
+        return true;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if metadata.permissions().mode() & 0o111 == 0 {
+            return false;
+        }
+        return has_program_header(path);
+    }
+
+    #[cfg(not(unix))]
+    return false;
+}
+
+/// Whether a file starts with an ELF header, which AppImages have as well, or
+/// a `#!` interpreter line.
+#[cfg(unix)]
+fn has_program_header(path: &std::path::Path) -> bool {
+    use std::io::Read;
+
+    let mut header = [0u8; 4];
+    let ret = std::fs::File::open(path).and_then(|mut f| f.read_exact(&mut header));
+    if ret.is_err() {
+        return false;
+    }
+
+    return &header == b"\x7fELF" || header.starts_with(b"#!");
+}
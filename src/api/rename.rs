@@ -0,0 +1,31 @@
+/// Rename a file or directory, keeping it in the same directory.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "path": "path/to/file",
+///     "name": "new name"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::RenameResponse].
+#[actix_web::post("/api/rename")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::RenameRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let req = info.into_inner();
+
+    let ret = actix_web::web::block(move || crate::fileops::rename(&req.path, &req.name)).await?;
+
+    match ret {
+        Ok(v) => Ok(actix_web::web::Json(frontend::protocol::RenameResponse {
+            path: v,
+        })),
+        Err(e) => Err(actix_web::error::ErrorBadRequest(e)),
+    }
+}
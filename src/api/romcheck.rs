@@ -0,0 +1,31 @@
+/// Verify ROMs in a directory against the DAT files in the configured folder.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "path": "path/to/roms"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::RomCheckResponse].
+#[actix_web::post("/api/romcheck")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::RomCheckRequest>,
+    config: actix_web::web::Data<crate::webserver::Config>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+    let dat_dir = config.dat_dir.clone();
+
+    let ret = actix_web::web::block(move || {
+        let dats = crate::dat::load_dir(dat_dir.as_str());
+        crate::romcheck::check(&dats, path.as_str())
+    })
+    .await??;
+
+    Ok(actix_web::web::Json(ret))
+}
@@ -0,0 +1,28 @@
+/// Rename ROMs to the names found by `/api/romcheck`.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "renames": [
+///         {
+///             "path": "path/to/roms/game (1).zip",
+///             "name": "game.zip"
+///         }
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::RomFixResponse].
+#[actix_web::post("/api/romcheck/fix")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::RomFixRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let ret = actix_web::web::block(move || crate::romcheck::fix(&info.renames)).await?;
+
+    Ok(actix_web::web::Json(ret))
+}
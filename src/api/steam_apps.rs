@@ -0,0 +1,22 @@
+/// List installed Steam apps of all Steam libraries.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamAppsResponse].
+#[actix_web::post("/api/steam/apps")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::SteamAppsRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let (root, apps) = actix_web::web::block(crate::steam::installed_apps).await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::SteamAppsResponse {
+            root: root.map(|v| v.to_string_lossy().to_string()),
+            apps: apps,
+        },
+    ))
+}
@@ -0,0 +1,20 @@
+/// Find `compatdata` and `shadercache` folders of games that are not
+/// installed anymore.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamCleanupResponse].
+#[actix_web::post("/api/steam/cleanup")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::SteamCleanupRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let orphans = actix_web::web::block(crate::steam::orphans).await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::SteamCleanupResponse { orphans: orphans },
+    ))
+}
@@ -0,0 +1,28 @@
+/// Remove orphaned `compatdata` and `shadercache` folders by moving them to the
+/// trash.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "paths": [
+///         "path/to/steamapps/shadercache/1245620"
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamCleanupRemoveResponse].
+#[actix_web::post("/api/steam/cleanup/remove")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::SteamCleanupRemoveRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let paths = info.paths.clone();
+
+    let ret = actix_web::web::block(move || crate::steam::remove_orphans(&paths)).await?;
+
+    Ok(actix_web::web::Json(ret))
+}
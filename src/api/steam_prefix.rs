@@ -0,0 +1,43 @@
+/// Get well-known locations inside the Proton prefix of a game.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "appid": 1245620
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamPrefixResponse], or
+/// `404 Not Found` if the game has no prefix.
+#[actix_web::post("/api/steam/prefix")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::SteamPrefixRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let appid = info.appid;
+
+    let ret = actix_web::web::block(move || {
+        let prefix = crate::steam::prefixes()
+            .into_iter()
+            .find(|v| v.appid == appid)?;
+        let locations = crate::steam::proton::locations(std::path::Path::new(&prefix.path));
+
+        Some(frontend::protocol::SteamPrefixResponse {
+            prefix: prefix,
+            locations: locations,
+        })
+    })
+    .await?;
+
+    match ret {
+        Some(v) => Ok(actix_web::web::Json(v)),
+        None => Err(actix_web::error::ErrorNotFound(format!(
+            "no Proton prefix for {}",
+            appid
+        ))),
+    }
+}
@@ -0,0 +1,19 @@
+/// List Proton prefixes of all Steam libraries.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamPrefixesResponse].
+#[actix_web::post("/api/steam/prefixes")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::SteamPrefixesRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let prefixes = actix_web::web::block(crate::steam::prefixes).await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::SteamPrefixesResponse { prefixes: prefixes },
+    ))
+}
@@ -0,0 +1,34 @@
+#[derive(serde::Deserialize)]
+struct ScreenshotRequest {
+    /// The path to the screenshot or thumbnail.
+    path: String,
+}
+
+/// Get a screenshot or a thumbnail listed by `/api/steam/screenshots`.
+///
+/// Other files are refused with `403 Forbidden`.
+///
+/// # Arguments
+/// + `path`: The path to the image, passed as query string.
+#[actix_web::get("/api/steam/screenshot")]
+pub async fn get(
+    info: actix_web::web::Query<ScreenshotRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let path = info.path.clone();
+
+    let ret = actix_web::web::block(move || crate::steam::read_screenshot(path.as_str())).await?;
+
+    match ret {
+        Ok(v) => Ok(actix_web::HttpResponse::Ok()
+            .content_type(
+                mime_guess::from_path(&info.path)
+                    .first_or_octet_stream()
+                    .as_ref(),
+            )
+            .body(v)),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            Err(actix_web::error::ErrorForbidden(e))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
@@ -0,0 +1,19 @@
+/// List screenshots of all Steam users, grouped by game.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamScreenshotsResponse].
+#[actix_web::post("/api/steam/screenshots")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::SteamScreenshotsRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let games = actix_web::web::block(crate::steam::screenshots).await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::SteamScreenshotsResponse { games: games },
+    ))
+}
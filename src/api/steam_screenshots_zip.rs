@@ -0,0 +1,46 @@
+/// Download screenshots as a zip archive, in one folder per game.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "paths": [
+///         "path/to/userdata/12345678/760/remote/1245620/screenshots/20240101120000_1.jpg"
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// The zip archive, streamed from a temporary file, or `403 Forbidden` if a
+/// path is not a screenshot.
+#[actix_web::post("/api/steam/screenshots/zip")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::SteamScreenshotsZipRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let paths = info.paths.clone();
+
+    let ret = actix_web::web::block(move || {
+        let f = crate::steam::zip_screenshots(&paths)?;
+        let len = f.metadata()?.len();
+        Ok::<_, std::io::Error>((f, len))
+    })
+    .await?;
+
+    match ret {
+        Ok((f, len)) => Ok(actix_web::HttpResponse::Ok()
+            .content_type("application/zip")
+            .insert_header((
+                actix_web::http::header::CONTENT_DISPOSITION,
+                "attachment; filename=\"screenshots.zip\"",
+            ))
+            .no_chunking(len)
+            .streaming(crate::api::download::stream(tokio::fs::File::from_std(f)))),
+        Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+            Err(actix_web::error::ErrorForbidden(e))
+        }
+        Err(e) => Err(e.into()),
+    }
+}
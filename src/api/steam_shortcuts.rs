@@ -0,0 +1,27 @@
+/// List non-Steam games of all Steam users.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamShortcutsResponse].
+#[actix_web::post("/api/steam/shortcuts")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::SteamShortcutsRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let (users, shortcuts, running) = actix_web::web::block(|| {
+        let (users, shortcuts) = crate::steam::shortcuts();
+        (users, shortcuts, crate::steam::is_running())
+    })
+    .await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::SteamShortcutsResponse {
+            users: users,
+            shortcuts: shortcuts,
+            steam_running: running,
+        },
+    ))
+}
@@ -0,0 +1,40 @@
+/// Add a non-Steam game to `shortcuts.vdf`, after backing up the file.
+///
+/// # Arguments
+///
+/// The query body is a json object, `user` may be null for the last logged in
+/// user, and `name` and `start_dir` may be empty:
+///
+/// ```json
+/// {
+///     "user": "12345678",
+///     "name": "RetroArch",
+///     "exe": "/home/deck/Applications/RetroArch.AppImage",
+///     "start_dir": "",
+///     "launch_options": ""
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::SteamShortcutAddResponse].
+#[actix_web::post("/api/steam/shortcuts/add")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::SteamShortcutAddRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let req = info.into_inner();
+
+    let ret = actix_web::web::block(move || {
+        let _lock = data.shortcuts.lock().unwrap();
+        crate::steam::add_shortcut(&req)
+    })
+    .await?;
+
+    match ret {
+        Ok(v) => Ok(actix_web::web::Json(
+            frontend::protocol::SteamShortcutAddResponse { shortcut: v },
+        )),
+        Err(e) => Err(actix_web::error::ErrorBadRequest(e)),
+    }
+}
@@ -0,0 +1,37 @@
+/// Remove a non-Steam game from `shortcuts.vdf`, after backing up the file.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "user": "12345678",
+///     "appid": 3456789012
+/// }
+/// ```
+///
+/// # Returns
+///
+/// An empty json object.
+#[actix_web::post("/api/steam/shortcuts/remove")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::SteamShortcutRemoveRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let user = info.user.clone();
+    let appid = info.appid;
+
+    let ret = actix_web::web::block(move || {
+        let _lock = data.shortcuts.lock().unwrap();
+        crate::steam::remove_shortcut(user.as_str(), appid)
+    })
+    .await?;
+
+    match ret {
+        Ok(_) => Ok(actix_web::web::Json(
+            frontend::protocol::SteamShortcutRemoveResponse {},
+        )),
+        Err(e) => Err(actix_web::error::ErrorBadRequest(e)),
+    }
+}
@@ -0,0 +1,32 @@
+/// Delete trashed files permanently.
+///
+/// # Arguments
+///
+/// The query body is a json object, `paths` is null to empty all trashes:
+///
+/// ```json
+/// {
+///     "paths": [
+///         "path/to/Trash/files/file"
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::TrashEmptyResponse].
+#[actix_web::post("/api/trash/empty")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::TrashEmptyRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let paths = info.paths.clone();
+
+    let (deleted, errors) = actix_web::web::block(move || crate::trash::empty(paths)).await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::TrashEmptyResponse {
+            deleted: deleted,
+            errors: errors,
+        },
+    ))
+}
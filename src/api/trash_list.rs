@@ -0,0 +1,19 @@
+/// List trashed files of the home trash and of all mounted volumes.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::TrashListResponse].
+#[actix_web::post("/api/trash/list")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::TrashListRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let items = actix_web::web::block(crate::trash::list).await?;
+
+    Ok(actix_web::web::Json(
+        frontend::protocol::TrashListResponse { items: items },
+    ))
+}
@@ -0,0 +1,42 @@
+/// Move files to the trash of their volume.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "paths": [
+///         "path/to/file"
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::TrashPutResponse].
+#[actix_web::post("/api/trash/put")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::TrashPutRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let paths = info.paths.clone();
+
+    let ret = actix_web::web::block(move || {
+        let mut ret = frontend::protocol::TrashPutResponse {
+            trashed: 0,
+            errors: Vec::new(),
+        };
+
+        for path in paths {
+            match crate::trash::put(std::path::Path::new(&path)) {
+                Ok(_) => ret.trashed += 1,
+                Err(e) => ret.errors.push(format!("{}: {}", path, e)),
+            }
+        }
+
+        ret
+    })
+    .await?;
+
+    Ok(actix_web::web::Json(ret))
+}
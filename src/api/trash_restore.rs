@@ -0,0 +1,42 @@
+/// Move trashed files back to where they were deleted from.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "paths": [
+///         "path/to/Trash/files/file"
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::TrashRestoreResponse].
+#[actix_web::post("/api/trash/restore")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::TrashRestoreRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let paths = info.paths.clone();
+
+    let ret = actix_web::web::block(move || {
+        let mut ret = frontend::protocol::TrashRestoreResponse {
+            restored: Vec::new(),
+            errors: Vec::new(),
+        };
+
+        for path in paths {
+            match crate::trash::restore(path.as_str()) {
+                Ok(v) => ret.restored.push(v),
+                Err(e) => ret.errors.push(format!("{}: {}", path, e)),
+            }
+        }
+
+        ret
+    })
+    .await?;
+
+    Ok(actix_web::web::Json(ret))
+}
@@ -0,0 +1,43 @@
+/// Sum up the sizes of trashed directories.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "paths": [
+///         "path/to/Trash/files/folder"
+///     ]
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::TrashSizeResponse].
+#[actix_web::post("/api/trash/size")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::TrashSizeRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let paths = info.paths.clone();
+
+    let ret = actix_web::web::block(move || {
+        let mut ret = frontend::protocol::TrashSizeResponse {
+            sizes: std::collections::HashMap::new(),
+        };
+
+        for path in paths {
+            match crate::trash::size(path.as_str()) {
+                Ok(v) => {
+                    ret.sizes.insert(path, v);
+                }
+                Err(e) => tracing::warn!("size of {} failed: {}", path, e),
+            }
+        }
+
+        ret
+    })
+    .await?;
+
+    Ok(actix_web::web::Json(ret))
+}
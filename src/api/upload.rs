@@ -2,55 +2,445 @@
 struct UploadRequest {
     /// The path to upload to.
     path: String,
+
+    /// The id to query progress with `/api/upload/progress`.
+    id: Option<String>,
 }
 
+/// The sequence number of the next temporary upload file.
+static NEXT_TEMP_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+
+/// Upload files into a directory.
+///
+/// Each multipart field is stored as one file. The filename of a field may be
+/// a relative path like `dir/sub/file.txt` (as sent by `webkitdirectory`
+/// uploads), in which case the intermediate directories are created inside
+/// the target path.
+///
+/// A field named `manifest` may carry a json list of
+/// [frontend::protocol::UploadManifestItem]. It must be the first field, so
+/// files are checked as they are written. Files listed there are checked
+/// against the expected size and digests, and rejected on mismatch. Files
+/// listed there that never arrive are reported as missing.
+///
+/// # Arguments
+/// + `path`: The directory to upload to, passed as query string.
+/// + `id`: The optional upload id, passed as query string.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::UploadResponse].
 #[actix_web::post("/upload")]
 pub async fn post(
     mut payload: actix_multipart::Multipart,
     info: actix_web::web::Query<UploadRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
 ) -> actix_web::Result<impl actix_web::Responder> {
     use futures_util::TryStreamExt;
+    use sha2::Digest;
     use tokio::io::AsyncWriteExt;
 
     tracing::debug!("start upload");
 
+    let mut ret = frontend::protocol::UploadResponse {
+        files: 0,
+        dirs: 0,
+        bytes: 0,
+        missing: Vec::new(),
+    };
+    let mut received = std::collections::HashSet::<String>::new();
+    let mut manifest =
+        std::collections::HashMap::<String, frontend::protocol::UploadManifestItem>::new();
+    let progress = ProgressGuard::new(data, info.id.clone());
+    let mut fields = 0;
+
     while let Some(mut field) = payload.try_next().await? {
         // A multipart/form-data stream has to contain `content_disposition`
         let content_disposition = field.content_disposition();
 
-        let filename = content_disposition.get_filename().unwrap();
-        let actual_filepath = format!("{}/{}", info.path, filename);
+        let filename = match content_disposition.get_filename() {
+            Some(v) => v.to_string(),
+            None if field.name() == "manifest" => {
+                // Files before the manifest would silently go unchecked.
+                if fields > 0 {
+                    return Ok(actix_web::HttpResponse::BadRequest()
+                        .body("manifest must be the first field"));
+                }
+                fields += 1;
+
+                let mut body = Vec::new();
+                while let Some(chunk) = field.try_next().await? {
+                    body.extend_from_slice(&chunk);
+                }
+
+                let items: Vec<frontend::protocol::UploadManifestItem> =
+                    match serde_json::from_slice(&body) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            return Ok(actix_web::HttpResponse::BadRequest()
+                                .body(format!("invalid manifest: {}", e)));
+                        }
+                    };
+
+                for item in items {
+                    manifest.insert(item.path.clone(), item);
+                }
+                let bytes_total = manifest.values().filter_map(|v| v.size).sum();
+                progress.update(|v| v.bytes_total = bytes_total);
+                continue;
+            }
+            None => {
+                return Ok(actix_web::HttpResponse::BadRequest().body("missing filename"));
+            }
+        };
+
+        fields += 1;
+
+        let relative_path = match sanitize_relative_path(filename.as_str()) {
+            Some(v) => v,
+            None => {
+                tracing::warn!("reject upload of {}", filename);
+                return Ok(actix_web::HttpResponse::BadRequest()
+                    .body(format!("invalid filename: {}", filename)));
+            }
+        };
+
+        let actual_filepath = std::path::Path::new(&info.path).join(&relative_path);
+        let parent_dir = match actual_filepath.parent() {
+            Some(v) => v.to_path_buf(),
+            None => std::path::PathBuf::from(&info.path),
+        };
+
+        tracing::info!("uploading {}", actual_filepath.display());
+        progress.update(|v| v.current = Some(filename.clone()));
+
+        // Only compute digests the client asked us to verify.
+        let expected = manifest.get(&filename);
+        let mut sha256 = expected
+            .and_then(|v| v.sha256.as_ref())
+            .map(|_| sha2::Sha256::new());
+        let mut xxh3 = expected
+            .and_then(|v| v.xxh3.as_ref())
+            .map(|_| xxhash_rust::xxh3::Xxh3::new());
 
-        tracing::info!("uploading {}", actual_filepath);
+        // Create intermediate directories.
+        if parent_dir.is_dir() == false {
+            let missing = parent_dir
+                .ancestors()
+                .take_while(|v| v.exists() == false)
+                .count();
+            if let Err(e) = tokio::fs::create_dir_all(&parent_dir).await {
+                tracing::warn!("create {} failed: {}", parent_dir.display(), e);
+                return Ok(actix_web::HttpResponse::Forbidden().body(e.to_string()));
+            }
+            ret.dirs += missing as u64;
+        }
 
         // We do not write to actual file, but instead write to a temporary file.
         // Once the upload is complete, we rename the temporary file to the actual file.
-        let temp_filepath = format!(
-            "{}/incomplete.{}.upload",
-            info.path,
+        // The sequence number keeps concurrent uploads to the same directory apart.
+        let temp_filepath = parent_dir.join(format!(
+            "incomplete.{}.{}.upload",
             std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)
                 .unwrap()
-                .as_millis()
-        );
+                .as_millis(),
+            NEXT_TEMP_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+        ));
 
-        // Create the temporary file.
+        // Create the temporary file, removed again if anything below fails.
         let mut f = match tokio::fs::File::create(&temp_filepath).await {
             Ok(f) => f,
             Err(e) => {
-                tracing::warn!("create {} failed: {}", temp_filepath, e);
+                tracing::warn!("create {} failed: {}", temp_filepath.display(), e);
                 return Ok(actix_web::HttpResponse::Forbidden().body(e.to_string()));
             }
         };
+        let temp_file = TempFileGuard::new(temp_filepath);
         // Write the field data to the temporary file.
+        let mut size: u64 = 0;
         while let Some(chunk) = field.try_next().await? {
             f.write_all(&chunk).await?;
+            if let Some(h) = &mut sha256 {
+                h.update(&chunk);
+            }
+            if let Some(h) = &mut xxh3 {
+                h.update(&chunk);
+            }
+
+            size += chunk.len() as u64;
+            progress.update(|v| v.bytes_written += chunk.len() as u64);
         }
         f.shutdown().await?;
 
+        // Verify the file against the manifest.
+        if let Some(expected) = expected {
+            let actual = frontend::protocol::UploadManifestItem {
+                path: filename.clone(),
+                size: Some(size),
+                sha256: sha256.map(|v| crate::hash::to_hex(&v.finalize())),
+                xxh3: xxh3.map(|v| crate::hash::xxh3_to_hex(v.digest())),
+            };
+
+            if let Some(e) = verify(expected, &actual) {
+                tracing::warn!("reject {}: {}", filename, e);
+                return Ok(
+                    actix_web::HttpResponse::BadRequest().body(format!("{}: {}", filename, e))
+                );
+            }
+        }
+        ret.bytes += size;
+
         // Rename the temporary file to the actual file.
-        tokio::fs::rename(temp_filepath, actual_filepath).await?;
+        temp_file.persist(&actual_filepath).await?;
+        ret.files += 1;
+        progress.update(|v| v.files += 1);
+        received.insert(filename);
+    }
+
+    ret.missing = manifest
+        .into_keys()
+        .filter(|v| received.contains(v) == false)
+        .collect();
+    ret.missing.sort();
+    for v in ret.missing.iter() {
+        tracing::warn!("missing upload of {}", v);
+    }
+
+    return Ok(actix_web::HttpResponse::Ok().json(ret));
+}
+
+/// Convert an uploaded filename into a relative path that stays inside the
+/// target directory.
+///
+/// # Arguments
+/// + `name`: The filename of the multipart field, `/` or `\` separated.
+///
+/// # Returns
+/// + `None` if the name is empty, absolute, or contains `..`.
+fn sanitize_relative_path(name: &str) -> Option<std::path::PathBuf> {
+    let mut ret = std::path::PathBuf::new();
+
+    for part in name.split(['/', '\\']) {
+        match part {
+            "" | "." => continue,
+            ".." => return None,
+            v => ret.push(v),
+        }
+    }
+
+    if name.starts_with('/') || name.starts_with('\\') || ret.as_os_str().is_empty() {
+        return None;
+    }
+
+    return Some(ret);
+}
+
+/// Compare an uploaded file with its manifest entry.
+///
+/// # Arguments
+/// + `expected`: The manifest entry sent by the client.
+/// + `actual`: The properties of the written file.
+///
+/// # Returns
+/// + `Some(reason)` if anything that was expected does not match.
+fn verify(
+    expected: &frontend::protocol::UploadManifestItem,
+    actual: &frontend::protocol::UploadManifestItem,
+) -> Option<String> {
+    if let Some(size) = expected.size {
+        if Some(size) != actual.size {
+            return Some(format!(
+                "size mismatch, expected {} got {}",
+                size,
+                actual.size.unwrap_or_default()
+            ));
+        }
+    }
+
+    if let Some(sha256) = &expected.sha256 {
+        if Some(sha256.to_lowercase()) != actual.sha256 {
+            return Some("sha256 mismatch".to_string());
+        }
     }
 
-    return Ok(actix_web::HttpResponse::Ok().finish());
+    if let Some(xxh3) = &expected.xxh3 {
+        if Some(xxh3.to_lowercase()) != actual.xxh3 {
+            return Some("xxh3 mismatch".to_string());
+        }
+    }
+
+    return None;
+}
+
+/// Remove a temporary upload file when dropped, unless it was renamed to its
+/// final name, so aborted or failed uploads leave nothing behind.
+struct TempFileGuard {
+    path: Option<std::path::PathBuf>,
+}
+
+impl TempFileGuard {
+    fn new(path: std::path::PathBuf) -> Self {
+        return TempFileGuard { path: Some(path) };
+    }
+
+    /// Rename the temporary file to its final name.
+    async fn persist(mut self, dest: &std::path::Path) -> Result<(), std::io::Error> {
+        let path = match self.path.take() {
+            Some(v) => v,
+            None => return Ok(()),
+        };
+
+        let ret = tokio::fs::rename(&path, dest).await;
+        if ret.is_err() {
+            self.path = Some(path);
+        }
+        return ret;
+    }
+}
+
+impl Drop for TempFileGuard {
+    fn drop(&mut self) {
+        if let Some(path) = &self.path {
+            if let Err(e) = std::fs::remove_file(path) {
+                tracing::warn!("remove {} failed: {}", path.display(), e);
+            }
+        }
+    }
+}
+
+/// Publish upload progress in [crate::webserver::SharedData], and remove it
+/// once the upload is finished or failed.
+struct ProgressGuard {
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+    id: Option<String>,
+}
+
+impl ProgressGuard {
+    fn new(data: actix_web::web::Data<crate::webserver::SharedData>, id: Option<String>) -> Self {
+        if let Some(id) = &id {
+            data.uploads.lock().unwrap().insert(
+                id.clone(),
+                frontend::protocol::UploadProgressResponse::default(),
+            );
+        }
+
+        return ProgressGuard { data: data, id: id };
+    }
+
+    /// Modify the progress of this upload.
+    fn update<F>(&self, func: F)
+    where
+        F: FnOnce(&mut frontend::protocol::UploadProgressResponse),
+    {
+        let id = match &self.id {
+            Some(v) => v,
+            None => return,
+        };
+
+        if let Some(v) = self.data.uploads.lock().unwrap().get_mut(id) {
+            func(v);
+        }
+    }
+}
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        if let Some(id) = &self.id {
+            self.data.uploads.lock().unwrap().remove(id);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(
+        size: Option<u64>,
+        sha256: Option<&str>,
+        xxh3: Option<&str>,
+    ) -> frontend::protocol::UploadManifestItem {
+        return frontend::protocol::UploadManifestItem {
+            path: "dir/file.txt".to_string(),
+            size: size,
+            sha256: sha256.map(|v| v.to_string()),
+            xxh3: xxh3.map(|v| v.to_string()),
+        };
+    }
+
+    #[test]
+    fn parses_manifest() {
+        let json = r#"[
+            {"path": "dir/file.txt", "size": 5, "xxh3": "9555E8555C62DCFD"},
+            {"path": "other.bin"}
+        ]"#;
+        let items: Vec<frontend::protocol::UploadManifestItem> =
+            serde_json::from_str(json).unwrap();
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].size, Some(5));
+        assert_eq!(items[0].xxh3.as_deref(), Some("9555E8555C62DCFD"));
+        assert_eq!(items[1].size, None);
+        assert_eq!(items[1].sha256, None);
+
+        assert!(
+            serde_json::from_str::<Vec<frontend::protocol::UploadManifestItem>>(r#"[{"size": 5}]"#)
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn verifies_size_and_digests() {
+        // sha256 and xxh3 of "hello".
+        let sha256 = "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824";
+        let xxh3 = "9555e8555c62dcfd";
+        let actual = item(Some(5), Some(sha256), Some(xxh3));
+
+        assert_eq!(verify(&item(None, None, None), &actual), None);
+        assert_eq!(verify(&item(Some(5), None, None), &actual), None);
+        // Digests from the client may be uppercase.
+        let expected = item(
+            Some(5),
+            Some(&sha256.to_uppercase()),
+            Some("9555E8555C62DCFD"),
+        );
+        assert_eq!(verify(&expected, &actual), None);
+
+        assert_eq!(
+            verify(&item(Some(6), None, None), &actual),
+            Some("size mismatch, expected 6 got 5".to_string())
+        );
+        assert_eq!(
+            verify(&item(None, Some(&sha256.replace('2', "3")), None), &actual),
+            Some("sha256 mismatch".to_string())
+        );
+        assert_eq!(
+            verify(&item(None, None, Some("0000000000000000")), &actual),
+            Some("xxh3 mismatch".to_string())
+        );
+        // A digest that was not computed never matches.
+        assert_eq!(
+            verify(&item(None, None, Some(xxh3)), &item(Some(5), None, None)),
+            Some("xxh3 mismatch".to_string())
+        );
+    }
+
+    #[test]
+    fn sanitizes_relative_paths() {
+        assert_eq!(
+            sanitize_relative_path("dir/sub/file.txt"),
+            Some(std::path::PathBuf::from("dir/sub/file.txt"))
+        );
+        assert_eq!(
+            sanitize_relative_path("dir\\./file.txt"),
+            Some(std::path::PathBuf::from("dir/file.txt"))
+        );
+        assert_eq!(sanitize_relative_path("../file.txt"), None);
+        assert_eq!(sanitize_relative_path("dir/../../file.txt"), None);
+        assert_eq!(sanitize_relative_path("/etc/passwd"), None);
+        assert_eq!(sanitize_relative_path("\\file.txt"), None);
+        assert_eq!(sanitize_relative_path("./"), None);
+        assert_eq!(sanitize_relative_path(""), None);
+    }
 }
@@ -0,0 +1,30 @@
+/// Query the progress of an in-flight upload.
+///
+/// # Arguments
+///
+/// The query body is a json object:
+///
+/// ```json
+/// {
+///     "id": "upload id"
+/// }
+/// ```
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::UploadProgressResponse], or
+/// `404 Not Found` if no upload with the id is in flight.
+#[actix_web::post("/api/upload/progress")]
+pub async fn post(
+    info: actix_web::web::Json<frontend::protocol::UploadProgressRequest>,
+    data: actix_web::web::Data<crate::webserver::SharedData>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let uploads = data.uploads.lock().unwrap();
+
+    let rsp = match uploads.get(&info.id) {
+        Some(v) => actix_web::HttpResponse::Ok().json(v),
+        None => actix_web::HttpResponse::NotFound().body("404 Not Found"),
+    };
+
+    return Ok(rsp);
+}
@@ -0,0 +1,19 @@
+/// List mounted filesystems with their usage.
+///
+/// # Arguments
+///
+/// The query body is an empty json object.
+///
+/// # Returns
+///
+/// A json object, see [frontend::protocol::VolumesResponse].
+#[actix_web::post("/api/volumes")]
+pub async fn post(
+    _info: actix_web::web::Json<frontend::protocol::VolumesRequest>,
+) -> actix_web::Result<impl actix_web::Responder> {
+    let volumes = actix_web::web::block(crate::volumes::list).await??;
+
+    Ok(actix_web::web::Json(frontend::protocol::VolumesResponse {
+        volumes: volumes,
+    }))
+}
@@ -0,0 +1,399 @@
+/// The file holding the source path in each backup folder.
+const SOURCE_FILE: &str = "source.txt";
+
+/// Configuration of the backup subsystem.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// The directory to store snapshots in.
+    pub dir: std::path::PathBuf,
+
+    /// The maximum number of snapshots kept per source folder.
+    pub retention: usize,
+}
+
+/// Create a snapshot of a folder.
+///
+/// Snapshots are stored as `<dir>/<key>/<timestamp>.zip`, where `key` is
+/// derived from the source path. Old snapshots beyond the retention limit are
+/// deleted afterwards.
+///
+/// # Arguments
+/// + `config`: The backup configuration.
+/// + `source`: The folder to back up.
+///
+/// # Returns
+/// + The snapshot, and the paths of symlinks and special files that were
+///   skipped.
+pub fn create(
+    config: &Config,
+    source: &str,
+) -> Result<(frontend::protocol::BackupSnapshot, Vec<String>), std::io::Error> {
+    return create_keeping(config, source, &[]);
+}
+
+/// Create a snapshot of a folder, see [create].
+///
+/// # Arguments
+/// + `keep`: Snapshot ids that are never pruned.
+fn create_keeping(
+    config: &Config,
+    source: &str,
+    keep: &[&str],
+) -> Result<(frontend::protocol::BackupSnapshot, Vec<String>), std::io::Error> {
+    let source_path = std::path::Path::new(source);
+    if source_path.is_dir() == false {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a directory", source),
+        ));
+    }
+
+    // A backup directory inside the folder would be backed up into itself.
+    let dir = std::fs::canonicalize(&config.dir).unwrap_or(config.dir.clone());
+    if dir.starts_with(std::fs::canonicalize(source_path)?) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!(
+                "the backup directory {} is inside {}",
+                config.dir.display(),
+                source
+            ),
+        ));
+    }
+
+    let key = source_key(source);
+    let folder = config.dir.join(&key);
+    std::fs::create_dir_all(&folder)?;
+    std::fs::write(folder.join(SOURCE_FILE), source)?;
+
+    let created = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64;
+    let name = format!("{}.zip", created);
+
+    // Write to a temporary file, so an interrupted backup is never listed.
+    let temp_path = folder.join(format!("{}.tmp", name));
+    let mut skipped = Vec::new();
+    let ret = write_zip(source_path, &temp_path, &mut skipped);
+    if let Err(e) = ret {
+        std::fs::remove_file(&temp_path).ok();
+        return Err(e);
+    }
+    std::fs::rename(&temp_path, folder.join(&name))?;
+
+    tracing::info!("backup {} to {}/{}", source, key, name);
+    for path in skipped.iter() {
+        tracing::warn!("backup of {} skipped {}", source, path);
+    }
+
+    let id = format!("{}/{}", key, name);
+    let mut keep = keep.to_vec();
+    keep.push(id.as_str());
+    prune(config, &key, &keep)?;
+
+    return Ok((snapshot(config, id.as_str())?, skipped));
+}
+
+/// List snapshots, newest first.
+///
+/// # Arguments
+/// + `config`: The backup configuration.
+/// + `source`: Only list snapshots of this folder.
+pub fn list(
+    config: &Config,
+    source: Option<&str>,
+) -> Result<Vec<frontend::protocol::BackupSnapshot>, std::io::Error> {
+    let mut ret = Vec::new();
+
+    let keys: Vec<String> = match source {
+        Some(v) => vec![source_key(v)],
+        None => match std::fs::read_dir(&config.dir) {
+            Ok(v) => v
+                .flatten()
+                .map(|v| v.file_name().to_string_lossy().to_string())
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e),
+        },
+    };
+
+    for key in keys {
+        let entries = match std::fs::read_dir(config.dir.join(&key)) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.ends_with(".zip") == false {
+                continue;
+            }
+
+            match snapshot(config, format!("{}/{}", key, name).as_str()) {
+                Ok(v) => ret.push(v),
+                Err(e) => tracing::warn!("read backup {}/{} failed: {}", key, name, e),
+            }
+        }
+    }
+
+    ret.sort_by_key(|v| std::cmp::Reverse(v.created));
+    return Ok(ret);
+}
+
+/// Restore a snapshot into its source folder.
+///
+/// The current state of the source folder is snapshotted first, then the
+/// folder is replaced by the content of the snapshot.
+///
+/// # Arguments
+/// + `config`: The backup configuration.
+/// + `id`: The id of the snapshot, see [frontend::protocol::BackupSnapshot::id].
+///
+/// The snapshot is extracted next to the folder first, and swapped into place
+/// once complete, so a failed restore leaves the folder untouched.
+///
+/// # Returns
+/// + The snapshot of the state before restoring, `None` if the source folder
+///   did not exist, and the paths of symlinks and special files missing from
+///   that snapshot.
+pub fn restore(
+    config: &Config,
+    id: &str,
+) -> Result<(Option<frontend::protocol::BackupSnapshot>, Vec<String>), std::io::Error> {
+    let target = snapshot(config, id)?;
+    let source = std::path::Path::new(&target.source);
+    let (parent, name) = match (source.parent(), source.file_name()) {
+        (Some(parent), Some(name)) => (parent, name.to_string_lossy().to_string()),
+        _ => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("cannot restore to {}", target.source),
+            ));
+        }
+    };
+
+    // Never prune the snapshot being restored.
+    let (safety, skipped) = if source.is_dir() {
+        let (snapshot, skipped) = create_keeping(config, target.source.as_str(), &[id])?;
+        (Some(snapshot), skipped)
+    } else {
+        (None, Vec::new())
+    };
+
+    let stamp = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_millis();
+    let temp = parent.join(format!(".{}.restore-{}", name, stamp));
+    std::fs::create_dir_all(&temp)?;
+    if let Err(e) = extract(config, id, &temp) {
+        std::fs::remove_dir_all(&temp).ok();
+        return Err(e);
+    }
+
+    // Swap the restored folder into place, and put the old one back if that
+    // fails.
+    let old = parent.join(format!(".{}.old-{}", name, stamp));
+    if source.exists() {
+        if let Err(e) = std::fs::rename(source, &old) {
+            std::fs::remove_dir_all(&temp).ok();
+            return Err(e);
+        }
+    }
+    if let Err(e) = std::fs::rename(&temp, source) {
+        if old.exists() {
+            std::fs::rename(&old, source).ok();
+        }
+        std::fs::remove_dir_all(&temp).ok();
+        return Err(e);
+    }
+    if old.exists() {
+        if let Err(e) = std::fs::remove_dir_all(&old) {
+            tracing::warn!("remove {} failed: {}", old.display(), e);
+        }
+    }
+
+    tracing::info!("restore {} to {}", id, target.source);
+    return Ok((safety, skipped));
+}
+
+/// Extract a snapshot into a directory.
+fn extract(config: &Config, id: &str, dest: &std::path::Path) -> Result<(), std::io::Error> {
+    let f = std::fs::File::open(snapshot_path(config, id)?)?;
+    let mut archive = zip::ZipArchive::new(f)?;
+    for idx in 0..archive.len() {
+        let mut file = archive.by_index(idx)?;
+
+        // Skip entries that would escape the destination.
+        let relative = match file.enclosed_name() {
+            Some(v) => v.to_path_buf(),
+            None => continue,
+        };
+        let path = dest.join(relative);
+
+        if file.is_dir() {
+            std::fs::create_dir_all(&path)?;
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut out = std::fs::File::create(&path)?;
+        std::io::copy(&mut file, &mut out)?;
+    }
+
+    return Ok(());
+}
+
+/// Delete a snapshot.
+///
+/// # Arguments
+/// + `config`: The backup configuration.
+/// + `id`: The id of the snapshot, see [frontend::protocol::BackupSnapshot::id].
+pub fn delete(config: &Config, id: &str) -> Result<(), std::io::Error> {
+    let path = snapshot_path(config, id)?;
+    std::fs::remove_file(&path)?;
+
+    tracing::info!("delete backup {}", id);
+    return Ok(());
+}
+
+/// Read the metadata of a snapshot.
+fn snapshot(
+    config: &Config,
+    id: &str,
+) -> Result<frontend::protocol::BackupSnapshot, std::io::Error> {
+    let path = snapshot_path(config, id)?;
+    let folder = path.parent().unwrap();
+
+    let source = std::fs::read_to_string(folder.join(SOURCE_FILE))?;
+    let created = path
+        .file_stem()
+        .and_then(|v| v.to_str())
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_default();
+
+    let f = std::fs::File::open(&path)?;
+    let size = f.metadata()?.len();
+    let archive = zip::ZipArchive::new(f)?;
+    let files = archive
+        .file_names()
+        .filter(|v| v.ends_with('/') == false)
+        .count();
+
+    return Ok(frontend::protocol::BackupSnapshot {
+        id: id.to_string(),
+        source: source,
+        created: created / 1000,
+        size: size,
+        files: files as u64,
+    });
+}
+
+/// Resolve a snapshot id into a path inside the backup directory.
+fn snapshot_path(config: &Config, id: &str) -> Result<std::path::PathBuf, std::io::Error> {
+    let parts: Vec<&str> = id.split('/').collect();
+
+    let valid = parts.len() == 2
+        && parts
+            .iter()
+            .all(|v| v.is_empty() == false && *v != "." && *v != "..")
+        && parts[1].ends_with(".zip");
+    if valid == false {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid backup id {}", id),
+        ));
+    }
+
+    return Ok(config.dir.join(parts[0]).join(parts[1]));
+}
+
+/// Delete the oldest snapshots of a source beyond the retention limit.
+///
+/// # Arguments
+/// + `keep`: Snapshot ids that are never deleted.
+fn prune(config: &Config, key: &str, keep: &[&str]) -> Result<(), std::io::Error> {
+    let mut ids: Vec<String> = std::fs::read_dir(config.dir.join(key))?
+        .flatten()
+        .map(|v| v.file_name().to_string_lossy().to_string())
+        .filter(|v| v.ends_with(".zip"))
+        .map(|v| format!("{}/{}", key, v))
+        .collect();
+
+    // Names are timestamps of the same width, newest last.
+    ids.sort();
+
+    while ids.len() > config.retention.max(1) {
+        let id = ids.remove(0);
+        if keep.contains(&id.as_str()) {
+            continue;
+        }
+        delete(config, id.as_str())?;
+    }
+
+    return Ok(());
+}
+
+/// The folder name of a source inside the backup directory.
+fn source_key(source: &str) -> String {
+    let source = source.trim_end_matches('/');
+    let name: String = std::path::Path::new(source)
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+
+    return format!(
+        "{}-{}",
+        name,
+        crate::hash::xxh3_to_hex(xxhash_rust::xxh3::xxh3_64(source.as_bytes()))
+    );
+}
+
+fn write_zip(
+    source: &std::path::Path,
+    dest: &std::path::Path,
+    skipped: &mut Vec<String>,
+) -> Result<(), std::io::Error> {
+    let f = std::fs::File::create(dest)?;
+    let mut zip = zip::ZipWriter::new(f);
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Deflated);
+
+    let mut stack = vec![source.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_type = entry.file_type()?;
+
+            let relative = path
+                .strip_prefix(source)
+                .unwrap()
+                .components()
+                .map(|v| v.as_os_str().to_string_lossy().to_string())
+                .collect::<Vec<String>>()
+                .join("/");
+
+            if file_type.is_dir() {
+                zip.add_directory(relative, options)?;
+                stack.push(path);
+            } else if file_type.is_file() {
+                zip.start_file(relative, options)?;
+                let mut f = std::fs::File::open(&path)?;
+                std::io::copy(&mut f, &mut zip)?;
+            } else {
+                // Symlinks, sockets and the like.
+                skipped.push(path.to_string_lossy().to_string());
+            }
+        }
+    }
+
+    zip.finish()?;
+    return Ok(());
+}
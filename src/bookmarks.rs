@@ -0,0 +1,127 @@
+/// Read the bookmarks, in the order shown in the side panel.
+///
+/// # Arguments
+/// + `file`: The json file of bookmarks, which may not exist yet.
+pub fn list(file: &std::path::Path) -> Result<Vec<frontend::protocol::Bookmark>, std::io::Error> {
+    let data = match std::fs::read(file) {
+        Ok(v) => v,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    let bookmarks = serde_json::from_slice(&data)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    return Ok(bookmarks);
+}
+
+/// Bookmark a folder at the end of the list. A folder that is already
+/// bookmarked is only renamed.
+///
+/// # Arguments
+/// + `file`: The json file of bookmarks.
+/// + `path`: The folder to bookmark.
+/// + `name`: The name shown in the side panel, the folder name if `None`.
+pub fn add(
+    file: &std::path::Path,
+    path: &str,
+    name: Option<&str>,
+) -> Result<Vec<frontend::protocol::Bookmark>, std::io::Error> {
+    if std::path::Path::new(path).is_dir() == false {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not a directory", path),
+        ));
+    }
+
+    let name = match name {
+        Some(v) if v.is_empty() == false => v.to_string(),
+        _ => std::path::Path::new(path)
+            .file_name()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or(path.to_string()),
+    };
+
+    let mut bookmarks = list(file)?;
+    match bookmarks.iter_mut().find(|v| v.path == path) {
+        Some(v) => v.name = name,
+        None => bookmarks.push(frontend::protocol::Bookmark {
+            path: path.to_string(),
+            name: name,
+        }),
+    }
+    save(file, &bookmarks)?;
+
+    tracing::info!("bookmark {}", path);
+
+    return Ok(bookmarks);
+}
+
+/// Remove the bookmark of a folder.
+///
+/// # Arguments
+/// + `file`: The json file of bookmarks.
+/// + `path`: The bookmarked folder.
+pub fn remove(
+    file: &std::path::Path,
+    path: &str,
+) -> Result<Vec<frontend::protocol::Bookmark>, std::io::Error> {
+    let mut bookmarks = list(file)?;
+    let idx = find(&bookmarks, path)?;
+    bookmarks.remove(idx);
+    save(file, &bookmarks)?;
+
+    tracing::info!("remove bookmark {}", path);
+
+    return Ok(bookmarks);
+}
+
+/// Move the bookmark of a folder to another position.
+///
+/// # Arguments
+/// + `file`: The json file of bookmarks.
+/// + `path`: The bookmarked folder.
+/// + `index`: The new position, clamped to the end of the list.
+pub fn move_to(
+    file: &std::path::Path,
+    path: &str,
+    index: usize,
+) -> Result<Vec<frontend::protocol::Bookmark>, std::io::Error> {
+    let mut bookmarks = list(file)?;
+    let idx = find(&bookmarks, path)?;
+    let bookmark = bookmarks.remove(idx);
+    let index = index.min(bookmarks.len());
+    bookmarks.insert(index, bookmark);
+    save(file, &bookmarks)?;
+
+    return Ok(bookmarks);
+}
+
+/// The position of the bookmark of a folder.
+fn find(bookmarks: &[frontend::protocol::Bookmark], path: &str) -> Result<usize, std::io::Error> {
+    return bookmarks
+        .iter()
+        .position(|v| v.path == path)
+        .ok_or(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            format!("{} is not bookmarked", path),
+        ));
+}
+
+/// Write the bookmarks, through a temporary file so a crash never leaves a
+/// truncated file behind.
+fn save(
+    file: &std::path::Path,
+    bookmarks: &[frontend::protocol::Bookmark],
+) -> Result<(), std::io::Error> {
+    if let Some(parent) = file.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    let data = serde_json::to_vec_pretty(bookmarks)?;
+    let temp_path = file.with_extension("json.tmp");
+    std::fs::write(&temp_path, data)?;
+    std::fs::rename(&temp_path, file)?;
+
+    return Ok(());
+}
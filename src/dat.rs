@@ -0,0 +1,249 @@
+/// A parsed DAT file.
+#[derive(Debug, Clone, Default)]
+pub struct Datafile {
+    /// The name in `<header><name>`, or the file name if missing.
+    pub name: String,
+
+    /// The games listed in the DAT.
+    pub games: Vec<Game>,
+}
+
+/// A `<game>` (or `<machine>`) entry.
+#[derive(Debug, Clone, Default)]
+pub struct Game {
+    /// The name of the game, also the expected archive name without `.zip`.
+    pub name: String,
+
+    /// The files of the game.
+    pub roms: Vec<Rom>,
+}
+
+/// A `<rom>` entry.
+#[derive(Debug, Clone, Default)]
+pub struct Rom {
+    /// The expected file name.
+    pub name: String,
+
+    /// The size in bytes.
+    pub size: u64,
+
+    /// The CRC32 checksum, lowercase hex.
+    pub crc: Option<String>,
+
+    /// The SHA-1 digest, lowercase hex.
+    pub sha1: Option<String>,
+}
+
+/// Parse a Logiqx XML DAT.
+///
+/// # Arguments
+/// + `xml`: The content of the DAT file.
+pub fn parse(xml: &str) -> Result<Datafile, String> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_str(xml);
+    reader.trim_text(true);
+
+    let mut ret = Datafile::default();
+    let mut stack = Vec::<Vec<u8>>::new();
+
+    loop {
+        let event = match reader.read_event() {
+            Ok(v) => v,
+            Err(e) => {
+                return Err(format!(
+                    "error at position {}: {}",
+                    reader.buffer_position(),
+                    e
+                ));
+            }
+        };
+
+        match event {
+            Event::Start(e) => {
+                handle_element(&mut ret, &e);
+                stack.push(e.name().as_ref().to_vec());
+            }
+            Event::Empty(e) => {
+                handle_element(&mut ret, &e);
+            }
+            Event::End(_) => {
+                stack.pop();
+            }
+            Event::Text(t) => {
+                let in_header_name = stack.len() >= 2
+                    && stack[stack.len() - 2] == b"header"
+                    && stack[stack.len() - 1] == b"name";
+                if in_header_name {
+                    ret.name = t.unescape().map_err(|e| e.to_string())?.to_string();
+                }
+            }
+            Event::Eof => break,
+            _ => (),
+        }
+    }
+
+    return Ok(ret);
+}
+
+/// Load all `.dat` and `.xml` files in a directory.
+///
+/// Files that fail to parse are logged and skipped.
+///
+/// # Arguments
+/// + `path`: The directory containing DAT files.
+pub fn load_dir(path: &str) -> Vec<Datafile> {
+    let mut ret = Vec::new();
+
+    let entries = match std::fs::read_dir(path) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::warn!("read {} failed: {}", path, e);
+            return ret;
+        }
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let ext = path
+            .extension()
+            .map(|v| v.to_string_lossy().to_lowercase())
+            .unwrap_or_default();
+        if ext != "dat" && ext != "xml" {
+            continue;
+        }
+
+        let xml = match std::fs::read_to_string(&path) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("read {} failed: {}", path.display(), e);
+                continue;
+            }
+        };
+
+        match parse(xml.as_str()) {
+            Ok(mut v) => {
+                if v.name.is_empty() {
+                    v.name = entry.file_name().to_string_lossy().to_string();
+                }
+                ret.push(v);
+            }
+            Err(e) => {
+                tracing::warn!("parse {} failed: {}", path.display(), e);
+            }
+        }
+    }
+
+    return ret;
+}
+
+fn handle_element(dat: &mut Datafile, e: &quick_xml::events::BytesStart) {
+    match e.name().as_ref() {
+        b"game" | b"machine" => {
+            dat.games.push(Game {
+                name: attr(e, b"name").unwrap_or_default(),
+                roms: Vec::new(),
+            });
+        }
+        b"rom" => {
+            if let Some(game) = dat.games.last_mut() {
+                game.roms.push(Rom {
+                    name: attr(e, b"name").unwrap_or_default(),
+                    size: attr(e, b"size")
+                        .and_then(|v| v.parse().ok())
+                        .unwrap_or_default(),
+                    crc: attr(e, b"crc").map(|v| v.to_lowercase()),
+                    sha1: attr(e, b"sha1").map(|v| v.to_lowercase()),
+                });
+            }
+        }
+        _ => (),
+    }
+}
+
+/// Get an attribute value of an element.
+fn attr(e: &quick_xml::events::BytesStart, key: &[u8]) -> Option<String> {
+    for a in e.attributes().flatten() {
+        if a.key.as_ref() == key {
+            return a.unescape_value().ok().map(|v| v.to_string());
+        }
+    }
+
+    return None;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A No-Intro style DAT, trimmed to two games.
+    const DAT: &str = r#"<?xml version="1.0"?>
+<!DOCTYPE datafile PUBLIC "-//Logiqx//DTD ROM Management Datafile//EN" "http://www.logiqx.com/Dats/datafile.dtd">
+<datafile>
+	<header>
+		<name>Nintendo - Game Boy</name>
+		<description>Nintendo - Game Boy</description>
+		<version>20231010-123456</version>
+	</header>
+	<game name="Tetris (World) (Rev 1)">
+		<description>Tetris (World) (Rev 1)</description>
+		<rom name="Tetris (World) (Rev 1).gb" size="32768" crc="46DF91AD" md5="084f1e457749cdec86183189bd88ce69" sha1="74591CC9501AF93873F9A5D3EB12DA12C0723BBC" status="verified"/>
+	</game>
+	<game name="Dr. Mario &amp; Friends">
+		<rom name="Dr. Mario &amp; Friends.gb" size="65536" crc="ecf2dc6e"/>
+		<rom name="readme.txt" size="12"/>
+	</game>
+</datafile>
+"#;
+
+    #[test]
+    fn parses_header_and_games() {
+        let dat = parse(DAT).unwrap();
+        assert_eq!(dat.name, "Nintendo - Game Boy");
+        assert_eq!(dat.games.len(), 2);
+
+        let game = &dat.games[0];
+        assert_eq!(game.name, "Tetris (World) (Rev 1)");
+        assert_eq!(game.roms.len(), 1);
+        assert_eq!(game.roms[0].name, "Tetris (World) (Rev 1).gb");
+        assert_eq!(game.roms[0].size, 32768);
+        assert_eq!(game.roms[0].crc.as_deref(), Some("46df91ad"));
+        assert_eq!(
+            game.roms[0].sha1.as_deref(),
+            Some("74591cc9501af93873f9a5d3eb12da12c0723bbc")
+        );
+    }
+
+    #[test]
+    fn unescapes_names() {
+        let dat = parse(DAT).unwrap();
+        let game = &dat.games[1];
+        assert_eq!(game.name, "Dr. Mario & Friends");
+        assert_eq!(game.roms[0].name, "Dr. Mario & Friends.gb");
+        assert_eq!(game.roms[1].crc, None);
+        assert_eq!(game.roms[1].sha1, None);
+    }
+
+    #[test]
+    fn parses_machines() {
+        let xml = r#"<datafile><machine name="pacman"><rom name="pacman.6e" size="4096" crc="c1e6ab10"/></machine></datafile>"#;
+        let dat = parse(xml).unwrap();
+        assert_eq!(dat.name, "");
+        assert_eq!(dat.games[0].name, "pacman");
+        assert_eq!(dat.games[0].roms[0].name, "pacman.6e");
+    }
+
+    #[test]
+    fn ignores_invalid_sizes_and_stray_roms() {
+        let xml = r#"<datafile><rom name="stray" size="1"/><game name="a"><rom name="b" size="big"/></game></datafile>"#;
+        let dat = parse(xml).unwrap();
+        assert_eq!(dat.games.len(), 1);
+        assert_eq!(dat.games[0].roms[0].size, 0);
+    }
+
+    #[test]
+    fn rejects_malformed_xml() {
+        assert!(parse("<datafile><game name=\"a\"></datafile>").is_err());
+        assert!(parse("<datafile><header><name>A &bogus; B</name></header></datafile>").is_err());
+    }
+}
@@ -0,0 +1,171 @@
+/// A file or directory in a disk usage tree.
+#[derive(Debug, Clone, Default)]
+pub struct Node {
+    /// The file name.
+    pub name: String,
+
+    /// Whether this is a directory.
+    pub is_dir: bool,
+
+    /// The sum of file lengths.
+    pub apparent: u64,
+
+    /// The sum of allocated blocks in bytes.
+    pub disk: u64,
+
+    /// The number of files, including this one if it is a file.
+    pub files: u64,
+
+    /// The entries of a directory.
+    pub children: Vec<Node>,
+}
+
+impl Node {
+    /// Find a descendant by relative path.
+    ///
+    /// # Arguments
+    /// + `path`: The path relative to this node.
+    pub fn find(&self, path: &std::path::Path) -> Option<&Node> {
+        let mut node = self;
+        for component in path.components() {
+            let name = component.as_os_str().to_string_lossy();
+            node = node.children.iter().find(|v| v.name == name)?;
+        }
+        return Some(node);
+    }
+}
+
+/// Compute the disk usage of a directory recursively.
+///
+/// Files with several hard links are only counted once, and symlinks are not
+/// followed.
+///
+/// This is blocking and should be called from a blocking thread pool.
+///
+/// # Arguments
+/// + `path`: The directory to scan.
+/// + `cross_fs`: Whether to descend into other mounted filesystems.
+/// + `scanned`: Incremented for each scanned entry.
+pub fn scan(
+    path: &std::path::Path,
+    cross_fs: bool,
+    scanned: &std::sync::atomic::AtomicU64,
+) -> Result<Node, std::io::Error> {
+    let metadata = std::fs::symlink_metadata(path)?;
+    let mut scanner = Scanner {
+        root_dev: file_info(&metadata).dev,
+        cross_fs: cross_fs,
+        seen: std::collections::HashSet::new(),
+        scanned: scanned,
+    };
+
+    let mut ret = scanner.scan_dir(path, &metadata);
+    ret.name = path.to_string_lossy().to_string();
+    return Ok(ret);
+}
+
+struct Scanner<'a> {
+    root_dev: u64,
+    cross_fs: bool,
+    /// `(dev, ino)` of visited files with more than one link.
+    seen: std::collections::HashSet<(u64, u64)>,
+    scanned: &'a std::sync::atomic::AtomicU64,
+}
+
+impl<'a> Scanner<'a> {
+    fn scan_dir(&mut self, path: &std::path::Path, metadata: &std::fs::Metadata) -> Node {
+        let info = file_info(metadata);
+        let mut ret = Node {
+            name: file_name(path),
+            is_dir: true,
+            apparent: info.apparent,
+            disk: info.disk,
+            files: 0,
+            children: Vec::new(),
+        };
+
+        let entries = match std::fs::read_dir(path) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::debug!("read {} failed: {}", path.display(), e);
+                return ret;
+            }
+        };
+
+        for entry in entries.flatten() {
+            self.scanned
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+
+            let path = entry.path();
+            let metadata = match std::fs::symlink_metadata(&path) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            let info = file_info(&metadata);
+
+            let child = if metadata.is_dir() {
+                if self.cross_fs == false && info.dev != self.root_dev {
+                    continue;
+                }
+                self.scan_dir(&path, &metadata)
+            } else {
+                let counted = info.nlink > 1 && self.seen.insert((info.dev, info.ino)) == false;
+                Node {
+                    name: file_name(&path),
+                    is_dir: false,
+                    apparent: if counted { 0 } else { info.apparent },
+                    disk: if counted { 0 } else { info.disk },
+                    files: 1,
+                    children: Vec::new(),
+                }
+            };
+
+            ret.apparent += child.apparent;
+            ret.disk += child.disk;
+            ret.files += child.files;
+            ret.children.push(child);
+        }
+
+        return ret;
+    }
+}
+
+struct FileInfo {
+    dev: u64,
+    ino: u64,
+    nlink: u64,
+    apparent: u64,
+    disk: u64,
+}
+
+#[cfg(unix)]
+fn file_info(metadata: &std::fs::Metadata) -> FileInfo {
+    use std::os::unix::fs::MetadataExt;
+
+    return FileInfo {
+        dev: metadata.dev(),
+        ino: metadata.ino(),
+        nlink: metadata.nlink(),
+        apparent: metadata.len(),
+        // `st_blocks` is always in 512-byte units.
+        disk: metadata.blocks() * 512,
+    };
+}
+
+#[cfg(not(unix))]
+fn file_info(metadata: &std::fs::Metadata) -> FileInfo {
+    return FileInfo {
+        dev: 0,
+        ino: 0,
+        nlink: 1,
+        apparent: metadata.len(),
+        disk: metadata.len(),
+    };
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    return path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default();
+}
@@ -0,0 +1,286 @@
+/// Copy or move files into a directory.
+///
+/// With [frontend::protocol::ConflictPolicy::Ask], nothing is changed if a
+/// destination exists, and the conflicting names are returned instead.
+///
+/// Replaced destinations are moved to the trash where possible.
+///
+/// # Arguments
+/// + `req`: The paste request.
+pub fn paste(req: &frontend::protocol::PasteRequest) -> frontend::protocol::PasteResponse {
+    let mut ret = frontend::protocol::PasteResponse {
+        conflicts: Vec::new(),
+        done: 0,
+        skipped: 0,
+        errors: Vec::new(),
+    };
+
+    let dest = std::path::Path::new(&req.dest);
+    if dest.is_dir() == false {
+        ret.errors
+            .push(format!("{} is not a directory", dest.display()));
+        return ret;
+    }
+
+    // Check all sources first, so a conflict prompt comes before any change.
+    let mut jobs = Vec::new();
+    for source in req.sources.iter() {
+        let source = std::path::Path::new(source);
+        let name = match source.file_name() {
+            Some(v) => v,
+            None => {
+                ret.errors.push(format!("{} has no name", source.display()));
+                continue;
+            }
+        };
+
+        if is_inside(dest, source) {
+            ret.errors.push(format!(
+                "{} can not be pasted into itself",
+                source.display()
+            ));
+            continue;
+        }
+
+        let target = dest.join(name);
+
+        // Moving into the same directory changes nothing.
+        if req.mode == frontend::protocol::PasteMode::Move && target == source {
+            ret.skipped += 1;
+            continue;
+        }
+
+        if std::fs::symlink_metadata(&target).is_ok() {
+            ret.conflicts.push(name.to_string_lossy().to_string());
+        }
+        jobs.push((source.to_path_buf(), target));
+    }
+
+    if req.conflict == frontend::protocol::ConflictPolicy::Ask && ret.conflicts.is_empty() == false
+    {
+        return ret;
+    }
+
+    for (source, target) in jobs {
+        let target = match std::fs::symlink_metadata(&target).is_ok() {
+            false => target,
+            true => match req.conflict {
+                frontend::protocol::ConflictPolicy::Skip => {
+                    ret.skipped += 1;
+                    continue;
+                }
+                frontend::protocol::ConflictPolicy::Rename => unique_name(&target),
+                _ => {
+                    if target == source {
+                        ret.errors
+                            .push(format!("{} can not replace itself", source.display()));
+                        continue;
+                    }
+                    if let Err(e) = remove(&target) {
+                        ret.errors.push(format!("{}: {}", target.display(), e));
+                        continue;
+                    }
+                    target
+                }
+            },
+        };
+
+        let mut special = Vec::new();
+        let result = match req.mode {
+            frontend::protocol::PasteMode::Copy => copy(&source, &target, &mut special),
+            frontend::protocol::PasteMode::Move => move_to(&source, &target, &mut special),
+        };
+        for path in special.iter() {
+            ret.errors
+                .push(format!("{}: not a regular file, skipped", path.display()));
+        }
+        match result {
+            Ok(_) if special.contains(&source) => ret.skipped += 1,
+            Ok(_) => ret.done += 1,
+            Err(e) => ret.errors.push(format!("{}: {}", source.display(), e)),
+        }
+    }
+
+    tracing::info!(
+        "paste {} files into {}: {} done, {} skipped, {} failed",
+        req.sources.len(),
+        req.dest,
+        ret.done,
+        ret.skipped,
+        ret.errors.len()
+    );
+    return ret;
+}
+
+/// Rename a file or directory, keeping it in the same directory.
+///
+/// # Arguments
+/// + `path`: The file to rename.
+/// + `name`: The new file name, without any directory.
+///
+/// # Returns
+/// + The new path.
+pub fn rename(path: &str, name: &str) -> Result<String, std::io::Error> {
+    let invalid = name.is_empty() || name == "." || name == ".." || name.contains(['/', '\0']);
+    if invalid {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("invalid file name {}", name),
+        ));
+    }
+
+    let path = std::path::Path::new(path);
+    let parent = match path.parent() {
+        Some(v) => v,
+        None => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("{} can not be renamed", path.display()),
+            ));
+        }
+    };
+
+    let target = parent.join(name);
+    if target != path && std::fs::symlink_metadata(&target).is_ok() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", target.display()),
+        ));
+    }
+
+    std::fs::rename(path, &target)?;
+
+    tracing::info!("rename {} to {}", path.display(), target.display());
+    return Ok(target.to_string_lossy().to_string());
+}
+
+/// Copy a file or directory recursively. Symlinks are copied as symlinks.
+///
+/// Special files like FIFOs, sockets and device nodes are not copied, since
+/// reading them may block forever.
+///
+/// # Arguments
+/// + `source`: The file or directory to copy.
+/// + `target`: The path of the copy, which must not exist.
+/// + `special`: Receives the skipped special files.
+pub fn copy(
+    source: &std::path::Path,
+    target: &std::path::Path,
+    special: &mut Vec<std::path::PathBuf>,
+) -> Result<(), std::io::Error> {
+    let metadata = std::fs::symlink_metadata(source)?;
+
+    if metadata.file_type().is_symlink() {
+        let link = std::fs::read_link(source)?;
+        return symlink(&link, target);
+    }
+
+    if metadata.is_file() {
+        std::fs::copy(source, target)?;
+        return Ok(());
+    }
+
+    if metadata.is_dir() == false {
+        special.push(source.to_path_buf());
+        return Ok(());
+    }
+
+    std::fs::create_dir(target)?;
+    for entry in std::fs::read_dir(source)? {
+        let entry = entry?;
+        copy(&entry.path(), &target.join(entry.file_name()), special)?;
+    }
+    return Ok(());
+}
+
+/// Move a file or directory, copying it if the target is on another
+/// filesystem.
+///
+/// If the copy skips special files, see [copy], the source is kept.
+pub fn move_to(
+    source: &std::path::Path,
+    target: &std::path::Path,
+    special: &mut Vec<std::path::PathBuf>,
+) -> Result<(), std::io::Error> {
+    match std::fs::rename(source, target) {
+        Ok(_) => return Ok(()),
+        Err(e) if crate::trash::is_cross_device(&e) => {}
+        Err(e) => return Err(e),
+    }
+
+    copy(source, target, special)?;
+    if special.is_empty() == false {
+        return Err(std::io::Error::other(
+            "copied without special files, the source is kept",
+        ));
+    }
+    match std::fs::symlink_metadata(source)?.is_dir() {
+        true => std::fs::remove_dir_all(source)?,
+        false => std::fs::remove_file(source)?,
+    }
+    return Ok(());
+}
+
+/// Whether `path` is `dir` or inside it, after resolving symlinks and `..`.
+///
+/// The last component of `dir` is not resolved, so a symlink to a directory
+/// can be pasted into that directory.
+fn is_inside(path: &std::path::Path, dir: &std::path::Path) -> bool {
+    let path = std::fs::canonicalize(path).unwrap_or(path.to_path_buf());
+    let dir = match (dir.parent(), dir.file_name()) {
+        (Some(parent), Some(name)) => std::fs::canonicalize(parent)
+            .unwrap_or(parent.to_path_buf())
+            .join(name),
+        _ => dir.to_path_buf(),
+    };
+    return path.starts_with(dir);
+}
+
+/// Remove a replaced file, moving it to the trash where possible.
+fn remove(path: &std::path::Path) -> Result<(), std::io::Error> {
+    match crate::trash::put(path) {
+        Ok(_) => return Ok(()),
+        Err(e) if crate::trash::is_cross_device(&e) => {}
+        Err(e) => return Err(e),
+    }
+
+    match std::fs::symlink_metadata(path)?.is_dir() {
+        true => std::fs::remove_dir_all(path),
+        false => std::fs::remove_file(path),
+    }
+}
+
+/// Find a free name like `name (2).ext` next to a path.
+pub(crate) fn unique_name(path: &std::path::Path) -> std::path::PathBuf {
+    let parent = path.parent().unwrap_or(std::path::Path::new("/"));
+    let is_dir = path.is_dir();
+
+    let name = path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let (stem, ext) = match (is_dir, name.rfind('.')) {
+        (false, Some(idx)) if idx > 0 => (name[..idx].to_string(), name[idx..].to_string()),
+        _ => (name.clone(), String::new()),
+    };
+
+    let mut idx = 2;
+    loop {
+        let ret = parent.join(format!("{} ({}){}", stem, idx, ext));
+        if std::fs::symlink_metadata(&ret).is_err() {
+            return ret;
+        }
+        idx += 1;
+    }
+}
+
+#[cfg(unix)]
+fn symlink(link: &std::path::Path, target: &std::path::Path) -> Result<(), std::io::Error> {
+    return std::os::unix::fs::symlink(link, target);
+}
+
+#[cfg(not(unix))]
+fn symlink(link: &std::path::Path, target: &std::path::Path) -> Result<(), std::io::Error> {
+    std::fs::copy(link, target)?;
+    return Ok(());
+}
@@ -0,0 +1,77 @@
+/// Encode bytes as lowercase hex string.
+///
+/// # Arguments
+/// + `data`: The bytes to encode.
+pub fn to_hex(data: &[u8]) -> String {
+    let mut ret = String::with_capacity(data.len() * 2);
+    for v in data {
+        ret.push_str(format!("{:02x}", v).as_str());
+    }
+    return ret;
+}
+
+/// Format a xxh3 digest the same way as `xxhsum -H3`.
+///
+/// # Arguments
+/// + `digest`: The 64-bit xxh3 digest.
+pub fn xxh3_to_hex(digest: u64) -> String {
+    return format!("{:016x}", digest);
+}
+
+/// Compute all supported checksums of a file.
+///
+/// This is blocking and should be called from a blocking thread pool.
+///
+/// # Arguments
+/// + `path`: The path to the file.
+/// + `cancel`: Set to `true` to stop hashing, an [std::io::ErrorKind::Interrupted]
+///   error is returned in that case.
+pub fn checksum_file(
+    path: &str,
+    cancel: &std::sync::atomic::AtomicBool,
+) -> Result<frontend::protocol::ChecksumResponse, std::io::Error> {
+    use sha1::Digest;
+    use std::io::Read;
+
+    let mut f = std::fs::File::open(path)?;
+    let mut buf = vec![0u8; 1024 * 1024];
+    let mut size: u64 = 0;
+
+    let mut crc32 = crc32fast::Hasher::new();
+    let mut md5 = md5::Md5::new();
+    let mut sha1 = sha1::Sha1::new();
+    let mut sha256 = sha2::Sha256::new();
+    let mut xxh3 = xxhash_rust::xxh3::Xxh3::new();
+
+    loop {
+        if cancel.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Interrupted,
+                "checksum cancelled",
+            ));
+        }
+
+        let n = f.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+
+        let chunk = &buf[..n];
+        crc32.update(chunk);
+        md5.update(chunk);
+        sha1.update(chunk);
+        sha256.update(chunk);
+        xxh3.update(chunk);
+        size += n as u64;
+    }
+
+    return Ok(frontend::protocol::ChecksumResponse {
+        path: path.to_string(),
+        size: size,
+        crc32: format!("{:08x}", crc32.finalize()),
+        md5: to_hex(&md5.finalize()),
+        sha1: to_hex(&sha1.finalize()),
+        sha256: to_hex(&sha256.finalize()),
+        xxh3: xxh3_to_hex(xxh3.digest()),
+    });
+}
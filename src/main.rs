@@ -2,6 +2,16 @@ use tracing::level_filters::LevelFilter;
 
 mod api;
 mod assets;
+mod backup;
+mod bookmarks;
+mod dat;
+mod du;
+mod fileops;
+mod hash;
+mod romcheck;
+mod steam;
+mod trash;
+mod volumes;
 mod webserver;
 
 #[derive(Debug, Default, Clone, clap::Parser)]
@@ -14,6 +24,25 @@ struct BackendConfig {
 
     #[arg(long, default_value = "false", help = "Don't start the gui.")]
     no_gui: bool,
+
+    #[arg(
+        long,
+        help = "The directory of DAT files for ROM verification. [default: <config dir>/steam_deck_file_manager/dat]"
+    )]
+    dat_dir: Option<String>,
+
+    #[arg(
+        long,
+        help = "The directory to store save backups in. [default: <data dir>/steam_deck_file_manager/backups]"
+    )]
+    backup_dir: Option<String>,
+
+    #[arg(
+        long,
+        default_value = "10",
+        help = "The number of backups kept per folder."
+    )]
+    backup_retention: usize,
 }
 
 fn main() {
@@ -42,9 +71,35 @@ fn main() {
         .unwrap();
 
     // Start the webserver.
+    let dat_dir = match &config.dat_dir {
+        Some(v) => v.clone(),
+        None => dirs::config_dir()
+            .unwrap_or_default()
+            .join("steam_deck_file_manager")
+            .join("dat")
+            .to_string_lossy()
+            .to_string(),
+    };
+    let backup_dir = match &config.backup_dir {
+        Some(v) => std::path::PathBuf::from(v),
+        None => dirs::data_dir()
+            .unwrap_or_default()
+            .join("steam_deck_file_manager")
+            .join("backups"),
+    };
+    let bookmarks_file = dirs::config_dir()
+        .unwrap_or_default()
+        .join("steam_deck_file_manager")
+        .join("bookmarks.json");
     let web_config = webserver::Config {
         ip: config.ip.clone(),
         port: config.port,
+        dat_dir: dat_dir,
+        backup: backup::Config {
+            dir: backup_dir,
+            retention: config.backup_retention,
+        },
+        bookmarks_file: bookmarks_file,
     };
     rt.spawn(crate::webserver::new(web_config).unwrap());
 
@@ -0,0 +1,362 @@
+/// The location of a ROM in the loaded DAT files: `(dat, game, rom)` indexes.
+type RomRef = (usize, usize, usize);
+
+/// Verify all files in a ROM directory against DAT files.
+///
+/// Loose files are matched by SHA-1 (or size and CRC32 if the DAT has no
+/// SHA-1). Files inside ZIP archives are matched by the size and CRC32 stored
+/// in the archive.
+///
+/// This is blocking and should be called from a blocking thread pool.
+///
+/// # Arguments
+/// + `dats`: The DAT files to verify against.
+/// + `path`: The ROM directory, scanned recursively.
+pub fn check(
+    dats: &[crate::dat::Datafile],
+    path: &str,
+) -> Result<frontend::protocol::RomCheckResponse, std::io::Error> {
+    let index = Index::new(dats);
+
+    let mut files = Vec::new();
+    collect_files(std::path::Path::new(path), &mut files)?;
+
+    let mut entries = Vec::new();
+    let mut found = std::collections::HashSet::<RomRef>::new();
+
+    for file in files {
+        let is_zip = file
+            .extension()
+            .map(|v| v.to_string_lossy().to_lowercase() == "zip")
+            .unwrap_or(false);
+
+        let ret = if is_zip {
+            check_zip(dats, &index, &file, &mut found)
+        } else {
+            check_file(dats, &index, &file, &mut found).map(|v| vec![v])
+        };
+
+        match ret {
+            Ok(mut v) => entries.append(&mut v),
+            Err(e) => tracing::warn!("check {} failed: {}", file.display(), e),
+        }
+    }
+
+    // Only report missing ROMs of DATs that match anything, otherwise every
+    // unrelated DAT in the folder would be reported as missing.
+    let mut missing = Vec::new();
+    for (dat_idx, dat) in dats.iter().enumerate() {
+        if found.iter().any(|v| v.0 == dat_idx) == false {
+            continue;
+        }
+
+        for (game_idx, game) in dat.games.iter().enumerate() {
+            for (rom_idx, rom) in game.roms.iter().enumerate() {
+                if found.contains(&(dat_idx, game_idx, rom_idx)) == false {
+                    missing.push(frontend::protocol::RomCheckMissing {
+                        dat: dat.name.clone(),
+                        game: game.name.clone(),
+                        rom: rom.name.clone(),
+                    });
+                }
+            }
+        }
+    }
+
+    return Ok(frontend::protocol::RomCheckResponse {
+        dats: dats.iter().map(|v| v.name.clone()).collect(),
+        entries: entries,
+        missing: missing,
+    });
+}
+
+/// Rename files to their DAT names.
+///
+/// # Arguments
+/// + `renames`: The files to rename, see [frontend::protocol::RomRename].
+pub fn fix(renames: &[frontend::protocol::RomRename]) -> frontend::protocol::RomFixResponse {
+    let mut ret = frontend::protocol::RomFixResponse {
+        renamed: 0,
+        errors: Vec::new(),
+    };
+
+    for rename in renames {
+        let from = std::path::Path::new(&rename.path);
+
+        if is_file_name(rename.name.as_str()) == false {
+            ret.errors
+                .push(format!("{}: invalid name {}", rename.path, rename.name));
+            continue;
+        }
+
+        let to = match from.parent() {
+            Some(v) => v.join(&rename.name),
+            None => {
+                ret.errors.push(format!("{}: no parent", rename.path));
+                continue;
+            }
+        };
+
+        if to.exists() {
+            ret.errors
+                .push(format!("{}: {} already exists", rename.path, to.display()));
+            continue;
+        }
+
+        match std::fs::rename(from, &to) {
+            Ok(_) => {
+                tracing::info!("rename {} to {}", from.display(), to.display());
+                ret.renamed += 1;
+            }
+            Err(e) => ret.errors.push(format!("{}: {}", rename.path, e)),
+        }
+    }
+
+    return ret;
+}
+
+/// Lookup tables from checksums to ROMs.
+struct Index {
+    by_sha1: std::collections::HashMap<String, Vec<RomRef>>,
+    by_crc: std::collections::HashMap<(u64, String), Vec<RomRef>>,
+}
+
+impl Index {
+    fn new(dats: &[crate::dat::Datafile]) -> Self {
+        let mut ret = Index {
+            by_sha1: std::collections::HashMap::new(),
+            by_crc: std::collections::HashMap::new(),
+        };
+
+        for (dat_idx, dat) in dats.iter().enumerate() {
+            for (game_idx, game) in dat.games.iter().enumerate() {
+                for (rom_idx, rom) in game.roms.iter().enumerate() {
+                    let r = (dat_idx, game_idx, rom_idx);
+                    if let Some(sha1) = &rom.sha1 {
+                        ret.by_sha1.entry(sha1.clone()).or_default().push(r);
+                    }
+                    if let Some(crc) = &rom.crc {
+                        ret.by_crc
+                            .entry((rom.size, crc.clone()))
+                            .or_default()
+                            .push(r);
+                    }
+                }
+            }
+        }
+
+        return ret;
+    }
+
+    /// Find ROMs by checksums. SHA-1 is preferred if known.
+    fn find(&self, size: u64, crc: &str, sha1: Option<&str>) -> &[RomRef] {
+        if let Some(sha1) = sha1 {
+            if let Some(v) = self.by_sha1.get(sha1) {
+                return v.as_slice();
+            }
+        }
+
+        match self.by_crc.get(&(size, crc.to_string())) {
+            Some(v) => v.as_slice(),
+            None => &[],
+        }
+    }
+}
+
+/// Collect files recursively. Only an unreadable `path` is an error, other
+/// unreadable entries are logged and skipped.
+fn collect_files(
+    path: &std::path::Path,
+    ret: &mut Vec<std::path::PathBuf>,
+) -> Result<(), std::io::Error> {
+    for entry in std::fs::read_dir(path)? {
+        let entry = match entry {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("read {} failed: {}", path.display(), e);
+                continue;
+            }
+        };
+        let file_type = match entry.file_type() {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::warn!("stat {} failed: {}", entry.path().display(), e);
+                continue;
+            }
+        };
+
+        if file_type.is_dir() {
+            if let Err(e) = collect_files(&entry.path(), ret) {
+                tracing::warn!("read {} failed: {}", entry.path().display(), e);
+            }
+        } else if file_type.is_file() {
+            ret.push(entry.path());
+        }
+    }
+
+    return Ok(());
+}
+
+fn check_file(
+    dats: &[crate::dat::Datafile],
+    index: &Index,
+    path: &std::path::Path,
+    found: &mut std::collections::HashSet<RomRef>,
+) -> Result<frontend::protocol::RomCheckEntry, std::io::Error> {
+    use sha1::Digest;
+    use std::io::Read;
+
+    let mut f = std::fs::File::open(path)?;
+    let mut buf = vec![0u8; 1024 * 1024];
+    let mut size: u64 = 0;
+    let mut crc32 = crc32fast::Hasher::new();
+    let mut sha1 = sha1::Sha1::new();
+
+    loop {
+        let n = f.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        crc32.update(&buf[..n]);
+        sha1.update(&buf[..n]);
+        size += n as u64;
+    }
+
+    let crc = format!("{:08x}", crc32.finalize());
+    let sha1 = crate::hash::to_hex(&sha1.finalize());
+    let name = file_name(path);
+
+    let mut ret = unknown_entry(path, None);
+    let candidates = index.find(size, crc.as_str(), Some(sha1.as_str()));
+
+    // Prefer a ROM with the same name, to not report renames for ROMs that
+    // appear in several games.
+    let matched = candidates
+        .iter()
+        .find(|v| dats[v.0].games[v.1].roms[v.2].name == name)
+        .or(candidates.first());
+
+    if let Some(&r) = matched {
+        found.insert(r);
+        let rom = &dats[r.0].games[r.1].roms[r.2];
+        fill_entry(&mut ret, dats, r);
+
+        if rom.name != name {
+            ret.status = frontend::protocol::RomCheckStatus::Rename;
+            ret.fix_name = Some(rom.name.clone());
+        }
+    }
+
+    return Ok(ret);
+}
+
+fn check_zip(
+    dats: &[crate::dat::Datafile],
+    index: &Index,
+    path: &std::path::Path,
+    found: &mut std::collections::HashSet<RomRef>,
+) -> Result<Vec<frontend::protocol::RomCheckEntry>, std::io::Error> {
+    let f = std::fs::File::open(path)?;
+    let mut archive = match zip::ZipArchive::new(f) {
+        Ok(v) => v,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+    };
+
+    let stem = path
+        .file_stem()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    let mut ret = Vec::new();
+    let mut rename_reported = false;
+    for idx in 0..archive.len() {
+        let file = match archive.by_index_raw(idx) {
+            Ok(v) => v,
+            Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, e)),
+        };
+        if file.is_dir() {
+            continue;
+        }
+
+        let name = file.name().to_string();
+        let crc = format!("{:08x}", file.crc32());
+        let candidates = index.find(file.size(), crc.as_str(), None);
+
+        let matched = candidates
+            .iter()
+            .find(|v| dats[v.0].games[v.1].name == stem)
+            .or(candidates.first());
+
+        let mut entry = unknown_entry(path, Some(name.clone()));
+        if let Some(&r) = matched {
+            found.insert(r);
+            let game = &dats[r.0].games[r.1];
+            let rom = &game.roms[r.2];
+            fill_entry(&mut entry, dats, r);
+
+            // Only the archive can be renamed, entries inside need a repack.
+            // The rename is reported once, with the first matched entry.
+            if game.name != stem && rename_reported == false {
+                entry.status = frontend::protocol::RomCheckStatus::Rename;
+                entry.fix_name = Some(format!("{}.zip", game.name));
+                rename_reported = true;
+            } else if rom.name != name {
+                entry.status = frontend::protocol::RomCheckStatus::Repack;
+            }
+        }
+
+        ret.push(entry);
+    }
+
+    return Ok(ret);
+}
+
+fn unknown_entry(
+    path: &std::path::Path,
+    entry: Option<String>,
+) -> frontend::protocol::RomCheckEntry {
+    return frontend::protocol::RomCheckEntry {
+        path: path.to_string_lossy().to_string(),
+        entry: entry,
+        status: frontend::protocol::RomCheckStatus::Unknown,
+        dat: None,
+        game: None,
+        rom: None,
+        fix_name: None,
+    };
+}
+
+fn fill_entry(
+    entry: &mut frontend::protocol::RomCheckEntry,
+    dats: &[crate::dat::Datafile],
+    r: RomRef,
+) {
+    let dat = &dats[r.0];
+    let game = &dat.games[r.1];
+
+    entry.status = frontend::protocol::RomCheckStatus::Matched;
+    entry.dat = Some(dat.name.clone());
+    entry.game = Some(game.name.clone());
+    entry.rom = Some(game.roms[r.2].name.clone());
+}
+
+/// Whether a name is a single file name, without separators of any platform
+/// and not `.` or `..`.
+fn is_file_name(name: &str) -> bool {
+    if name.contains(['/', '\\']) {
+        return false;
+    }
+
+    let mut components = std::path::Path::new(name).components();
+    return matches!(
+        (components.next(), components.next()),
+        (Some(std::path::Component::Normal(_)), None)
+    );
+}
+
+fn file_name(path: &std::path::Path) -> String {
+    return path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or_default();
+}
@@ -0,0 +1,356 @@
+/// A value of Valve's binary KeyValues format, as used by `shortcuts.vdf`.
+///
+/// Keys and strings keep their raw bytes, since Steam does not enforce an
+/// encoding, and a file must be written back unchanged.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A nested map. Keys may repeat, so the order is kept.
+    Map(Vec<(Vec<u8>, Value)>),
+
+    /// A string value.
+    String(Vec<u8>),
+
+    /// A 32-bit integer value.
+    Int32(i32),
+
+    /// A 32-bit float value.
+    Float32(f32),
+
+    /// A 32-bit pointer value, meaningless outside the process that wrote it.
+    Pointer(i32),
+
+    /// A UTF-16 string value.
+    WString(Vec<u16>),
+
+    /// A 32-bit RGBA color value.
+    Color(i32),
+
+    /// A 64-bit unsigned integer value.
+    Uint64(u64),
+
+    /// A 64-bit integer value.
+    Int64(i64),
+}
+
+const TYPE_MAP: u8 = 0x00;
+const TYPE_STRING: u8 = 0x01;
+const TYPE_INT32: u8 = 0x02;
+const TYPE_FLOAT32: u8 = 0x03;
+const TYPE_POINTER: u8 = 0x04;
+const TYPE_WSTRING: u8 = 0x05;
+const TYPE_COLOR: u8 = 0x06;
+const TYPE_UINT64: u8 = 0x07;
+const TYPE_END: u8 = 0x08;
+const TYPE_INT64: u8 = 0x0a;
+
+impl Value {
+    /// Get a child value. Keys are case-insensitive, as in Steam.
+    ///
+    /// # Arguments
+    /// + `key`: The key to look up.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Map(v) => v
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key.as_bytes()))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    /// Get a child string value, with invalid UTF-8 replaced for display.
+    ///
+    /// # Arguments
+    /// + `key`: The key to look up.
+    pub fn get_str(&self, key: &str) -> Option<std::borrow::Cow<'_, str>> {
+        match self.get(key) {
+            Some(Value::String(v)) => Some(String::from_utf8_lossy(v)),
+            _ => None,
+        }
+    }
+
+    /// Get a child 32-bit integer value.
+    ///
+    /// # Arguments
+    /// + `key`: The key to look up.
+    pub fn get_i32(&self, key: &str) -> Option<i32> {
+        match self.get(key) {
+            Some(Value::Int32(v)) => Some(*v),
+            _ => None,
+        }
+    }
+
+    /// Get the entries if this is a map value, or an empty slice.
+    pub fn entries(&self) -> &[(Vec<u8>, Value)] {
+        match self {
+            Value::Map(v) => v.as_slice(),
+            _ => &[],
+        }
+    }
+}
+
+/// Parse a binary KeyValues document.
+///
+/// # Arguments
+/// + `data`: The content of the file.
+///
+/// # Returns
+/// + A map holding the top level keys, usually only one.
+pub fn parse(data: &[u8]) -> Result<Value, String> {
+    let mut reader = Reader { data: data, pos: 0 };
+    let ret = reader.map(true)?;
+    return Ok(Value::Map(ret));
+}
+
+/// Serialize a binary KeyValues document.
+///
+/// # Arguments
+/// + `doc`: A map holding the top level keys, as returned by [parse].
+pub fn write(doc: &Value) -> Vec<u8> {
+    let mut ret = Vec::new();
+    write_map(&mut ret, doc.entries());
+    return ret;
+}
+
+fn write_map(out: &mut Vec<u8>, entries: &[(Vec<u8>, Value)]) {
+    for (key, value) in entries {
+        let t = match value {
+            Value::Map(_) => TYPE_MAP,
+            Value::String(_) => TYPE_STRING,
+            Value::Int32(_) => TYPE_INT32,
+            Value::Float32(_) => TYPE_FLOAT32,
+            Value::Pointer(_) => TYPE_POINTER,
+            Value::WString(_) => TYPE_WSTRING,
+            Value::Color(_) => TYPE_COLOR,
+            Value::Uint64(_) => TYPE_UINT64,
+            Value::Int64(_) => TYPE_INT64,
+        };
+        out.push(t);
+        write_cstr(out, key);
+
+        match value {
+            Value::Map(v) => write_map(out, v.as_slice()),
+            Value::String(v) => write_cstr(out, v),
+            Value::Int32(v) | Value::Pointer(v) | Value::Color(v) => {
+                out.extend_from_slice(&v.to_le_bytes())
+            }
+            Value::Float32(v) => out.extend_from_slice(&v.to_le_bytes()),
+            Value::WString(v) => write_wstr(out, v),
+            Value::Uint64(v) => out.extend_from_slice(&v.to_le_bytes()),
+            Value::Int64(v) => out.extend_from_slice(&v.to_le_bytes()),
+        }
+    }
+
+    out.push(TYPE_END);
+}
+
+fn write_cstr(out: &mut Vec<u8>, s: &[u8]) {
+    out.extend_from_slice(s);
+    out.push(0);
+}
+
+fn write_wstr(out: &mut Vec<u8>, s: &[u16]) {
+    for c in s {
+        out.extend_from_slice(&c.to_le_bytes());
+    }
+    out.extend_from_slice(&[0, 0]);
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn map(&mut self, top_level: bool) -> Result<Vec<(Vec<u8>, Value)>, String> {
+        let mut ret = Vec::new();
+
+        loop {
+            // Some writers omit the final end marker.
+            if top_level && self.pos == self.data.len() {
+                return Ok(ret);
+            }
+
+            let t = self.u8()?;
+            if t == TYPE_END {
+                return Ok(ret);
+            }
+
+            let key = self.cstr()?;
+            let value = match t {
+                TYPE_MAP => Value::Map(self.map(false)?),
+                TYPE_STRING => Value::String(self.cstr()?),
+                TYPE_INT32 => Value::Int32(i32::from_le_bytes(self.bytes()?)),
+                TYPE_FLOAT32 => Value::Float32(f32::from_le_bytes(self.bytes()?)),
+                TYPE_POINTER => Value::Pointer(i32::from_le_bytes(self.bytes()?)),
+                TYPE_WSTRING => Value::WString(self.wstr()?),
+                TYPE_COLOR => Value::Color(i32::from_le_bytes(self.bytes()?)),
+                TYPE_UINT64 => Value::Uint64(u64::from_le_bytes(self.bytes()?)),
+                TYPE_INT64 => Value::Int64(i64::from_le_bytes(self.bytes()?)),
+                t => {
+                    return Err(format!(
+                        "offset {}: unsupported type {:#04x} of {}",
+                        self.pos,
+                        t,
+                        String::from_utf8_lossy(&key)
+                    ))
+                }
+            };
+
+            ret.push((key, value));
+        }
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        let [v] = self.bytes::<1>()?;
+        return Ok(v);
+    }
+
+    fn bytes<const N: usize>(&mut self) -> Result<[u8; N], String> {
+        if self.pos + N > self.data.len() {
+            return Err(format!("offset {}: unexpected end of file", self.pos));
+        }
+
+        let mut ret = [0u8; N];
+        ret.copy_from_slice(&self.data[self.pos..self.pos + N]);
+        self.pos += N;
+        return Ok(ret);
+    }
+
+    fn cstr(&mut self) -> Result<Vec<u8>, String> {
+        let len = match self.data[self.pos..].iter().position(|v| *v == 0) {
+            Some(v) => v,
+            None => return Err(format!("offset {}: unterminated string", self.pos)),
+        };
+
+        let ret = self.data[self.pos..self.pos + len].to_vec();
+        self.pos += len + 1;
+        return Ok(ret);
+    }
+
+    fn wstr(&mut self) -> Result<Vec<u16>, String> {
+        let start = self.pos;
+        let mut units = Vec::new();
+        loop {
+            let v = match self.bytes::<2>() {
+                Ok(v) => u16::from_le_bytes(v),
+                Err(_) => return Err(format!("offset {}: unterminated string", start)),
+            };
+            if v == 0 {
+                break;
+            }
+            units.push(v);
+        }
+
+        return Ok(units);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `shortcuts.vdf` as written by Steam, with one shortcut whose name
+    /// is not valid UTF-8.
+    const SHORTCUTS: &[u8] = b"\x00shortcuts\x00\
+        \x000\x00\
+        \x02appid\x00\x67\xcc\xdc\xd3\
+        \x01AppName\x00Caf\xe9\x00\
+        \x01Exe\x00\"/home/deck/Games/Celeste/Celeste\"\x00\
+        \x01StartDir\x00\"/home/deck/Games/Celeste/\"\x00\
+        \x01icon\x00\x00\
+        \x01ShortcutPath\x00\x00\
+        \x01LaunchOptions\x00\x00\
+        \x02IsHidden\x00\x00\x00\x00\x00\
+        \x02AllowDesktopConfig\x00\x01\x00\x00\x00\
+        \x02AllowOverlay\x00\x01\x00\x00\x00\
+        \x02OpenVR\x00\x00\x00\x00\x00\
+        \x02Devkit\x00\x00\x00\x00\x00\
+        \x01DevkitGameID\x00\x00\
+        \x02DevkitOverrideAppID\x00\x00\x00\x00\x00\
+        \x02LastPlayTime\x00\x9a\x5b\x27\x65\
+        \x01FlatpakAppID\x00\x00\
+        \x00tags\x00\
+        \x010\x00favorite\x00\
+        \x08\
+        \x08\
+        \x08\
+        \x08";
+
+    #[test]
+    fn round_trips_shortcuts() {
+        let doc = parse(SHORTCUTS).unwrap();
+        assert_eq!(write(&doc), SHORTCUTS);
+    }
+
+    #[test]
+    fn keeps_invalid_utf8() {
+        let doc = parse(SHORTCUTS).unwrap();
+        let shortcut = doc.get("shortcuts").unwrap().get("0").unwrap();
+
+        assert_eq!(
+            shortcut.get("AppName"),
+            Some(&Value::String(b"Caf\xe9".to_vec()))
+        );
+        assert_eq!(shortcut.get_str("AppName").unwrap(), "Caf\u{fffd}");
+    }
+
+    #[test]
+    fn reads_nested_maps() {
+        let doc = parse(SHORTCUTS).unwrap();
+        let shortcuts = doc.get("SHORTCUTS").unwrap();
+        assert_eq!(shortcuts.entries().len(), 1);
+
+        let tags = shortcuts.get("0").unwrap().get("tags").unwrap();
+        assert_eq!(tags.get_str("0").unwrap(), "favorite");
+        assert_eq!(tags.get("1"), None);
+    }
+
+    #[test]
+    fn reads_int32_values() {
+        let doc = parse(SHORTCUTS).unwrap();
+        let shortcut = doc.get("shortcuts").unwrap().get("0").unwrap();
+
+        assert_eq!(shortcut.get_i32("appid"), Some(0xd3dccc67u32 as i32));
+        assert_eq!(shortcut.get_i32("AllowOverlay"), Some(1));
+        assert_eq!(shortcut.get_i32("LastPlayTime"), Some(1697078170));
+        assert_eq!(shortcut.get_i32("AppName"), None);
+    }
+
+    #[test]
+    fn writes_all_types() {
+        let doc = Value::Map(vec![(
+            b"root".to_vec(),
+            Value::Map(vec![
+                (b"s".to_vec(), Value::String(b"text".to_vec())),
+                (b"i".to_vec(), Value::Int32(-2)),
+                (b"f".to_vec(), Value::Float32(1.5)),
+                (b"p".to_vec(), Value::Pointer(16)),
+                (b"w".to_vec(), Value::WString(vec![0x48, 0xd800])),
+                (b"c".to_vec(), Value::Color(0x11223344)),
+                (b"u".to_vec(), Value::Uint64(u64::MAX)),
+                (b"l".to_vec(), Value::Int64(i64::MIN)),
+                (b"m".to_vec(), Value::Map(Vec::new())),
+            ]),
+        )]);
+
+        let data = write(&doc);
+        assert_eq!(parse(&data).unwrap(), doc);
+    }
+
+    #[test]
+    fn accepts_missing_final_end() {
+        let doc = parse(&SHORTCUTS[..SHORTCUTS.len() - 1]).unwrap();
+        assert_eq!(doc, parse(SHORTCUTS).unwrap());
+    }
+
+    #[test]
+    fn rejects_truncated_input() {
+        // Cut inside a nested map, an integer, and a string.
+        for len in [SHORTCUTS.len() - 2, 23, 36] {
+            assert!(parse(&SHORTCUTS[..len]).is_err(), "length {}", len);
+        }
+        assert!(parse(b"\x05w\x00H\x00").is_err());
+        assert!(parse(b"\x09key\x00").is_err());
+    }
+}
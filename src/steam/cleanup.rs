@@ -0,0 +1,83 @@
+/// Folders of `steamapps` holding per-game data named by app id.
+const KINDS: &[&str] = &["compatdata", "shadercache"];
+
+/// Find `compatdata` and `shadercache` folders of games that are neither
+/// installed nor added as non-Steam games.
+///
+/// This is blocking, as the size of each folder is computed.
+///
+/// # Arguments
+/// + `libraries`: The Steam library folders.
+/// + `known`: The app ids of installed apps and non-Steam games.
+pub fn orphans(
+    libraries: &[std::path::PathBuf],
+    known: &std::collections::HashSet<u32>,
+) -> Vec<frontend::protocol::SteamOrphan> {
+    let mut ret = Vec::new();
+
+    for library in libraries {
+        for kind in KINDS {
+            let entries = match std::fs::read_dir(library.join("steamapps").join(kind)) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            for entry in entries.flatten() {
+                // `0` holds data shared by all games.
+                let appid = match entry.file_name().to_string_lossy().parse::<u32>() {
+                    Ok(v) if v != 0 => v,
+                    _ => continue,
+                };
+                if known.contains(&appid) || entry.path().is_dir() == false {
+                    continue;
+                }
+
+                let scanned = std::sync::atomic::AtomicU64::new(0);
+                let size = match crate::du::scan(&entry.path(), false, &scanned) {
+                    Ok(v) => v.disk,
+                    Err(e) => {
+                        tracing::warn!("scan {} failed: {}", entry.path().display(), e);
+                        0
+                    }
+                };
+
+                ret.push(frontend::protocol::SteamOrphan {
+                    kind: kind.to_string(),
+                    appid: appid,
+                    path: entry.path().to_string_lossy().to_string(),
+                    size: size,
+                });
+            }
+        }
+    }
+
+    ret.sort_by_key(|v| std::cmp::Reverse(v.size));
+    return ret;
+}
+
+/// Whether a folder is one of [orphans], without computing any size.
+///
+/// # Arguments
+/// + `libraries`: The Steam library folders.
+/// + `known`: The app ids of installed apps and non-Steam games.
+/// + `path`: The folder to check.
+pub fn is_orphan(
+    libraries: &[std::path::PathBuf],
+    known: &std::collections::HashSet<u32>,
+    path: &std::path::Path,
+) -> bool {
+    let appid = match path.file_name().map(|v| v.to_string_lossy().parse::<u32>()) {
+        Some(Ok(v)) if v != 0 => v,
+        _ => return false,
+    };
+    if known.contains(&appid) || path.is_dir() == false {
+        return false;
+    }
+
+    let parent = path.parent();
+    return libraries.iter().any(|library| {
+        KINDS
+            .iter()
+            .any(|kind| parent == Some(library.join("steamapps").join(kind).as_path()))
+    });
+}
@@ -0,0 +1,642 @@
+pub mod binary_vdf;
+pub mod cleanup;
+pub mod proton;
+pub mod screenshots;
+pub mod shortcuts;
+pub mod vdf;
+
+/// Find the Steam installation directory.
+///
+/// Native and Flatpak installations are checked, in this order.
+pub fn find_root() -> Option<std::path::PathBuf> {
+    let home = dirs::home_dir()?;
+
+    let candidates = [
+        home.join(".local/share/Steam"),
+        home.join(".steam/steam"),
+        home.join(".steam/root"),
+        home.join(".var/app/com.valvesoftware.Steam/.local/share/Steam"),
+    ];
+
+    for candidate in candidates {
+        if candidate.join("steamapps").is_dir() {
+            // `~/.steam/steam` is usually a symlink.
+            return Some(std::fs::canonicalize(&candidate).unwrap_or(candidate));
+        }
+    }
+
+    return None;
+}
+
+/// List Steam library folders from `steamapps/libraryfolders.vdf`.
+///
+/// The Steam root is always included, as the first entry.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+pub fn library_folders(root: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut ret = vec![root.to_path_buf()];
+
+    let path = root.join("steamapps").join("libraryfolders.vdf");
+    let text = match std::fs::read_to_string(&path) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::debug!("read {} failed: {}", path.display(), e);
+            return ret;
+        }
+    };
+
+    let doc = match vdf::parse(text.as_str()) {
+        Ok(v) => v,
+        Err(e) => {
+            tracing::warn!("parse {} failed: {}", path.display(), e);
+            return ret;
+        }
+    };
+
+    let folders = match doc.get("libraryfolders") {
+        Some(v) => v,
+        None => return ret,
+    };
+
+    for (_, folder) in folders.entries() {
+        // Old versions store the path as value, new ones in a `path` key.
+        let path = match folder {
+            vdf::Value::String(v) => v.as_str(),
+            vdf::Value::Object(_) => match folder.get_str("path") {
+                Some(v) => v,
+                None => continue,
+            },
+        };
+
+        let path = std::path::PathBuf::from(path);
+        if path.join("steamapps").is_dir() == false {
+            continue;
+        }
+
+        let path = std::fs::canonicalize(&path).unwrap_or(path);
+        if ret.contains(&path) == false {
+            ret.push(path);
+        }
+    }
+
+    return ret;
+}
+
+/// Parse an `appmanifest_<appid>.acf` file.
+///
+/// # Arguments
+/// + `library`: The library folder containing the manifest.
+/// + `path`: The path to the manifest.
+pub fn read_appmanifest(
+    library: &std::path::Path,
+    path: &std::path::Path,
+) -> Result<frontend::protocol::SteamApp, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let doc = vdf::parse(text.as_str())?;
+
+    let state = match doc.get("AppState") {
+        Some(v) => v,
+        None => return Err("missing AppState".to_string()),
+    };
+
+    let appid = match state.get_str("appid").and_then(|v| v.parse().ok()) {
+        Some(v) => v,
+        None => return Err("missing appid".to_string()),
+    };
+    let installdir = state.get_str("installdir").unwrap_or_default();
+
+    return Ok(frontend::protocol::SteamApp {
+        appid: appid,
+        name: state.get_str("name").unwrap_or_default().to_string(),
+        install_dir: library
+            .join("steamapps")
+            .join("common")
+            .join(installdir)
+            .to_string_lossy()
+            .to_string(),
+        library: library.to_string_lossy().to_string(),
+        size_on_disk: state
+            .get_str("SizeOnDisk")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+        last_updated: state
+            .get_str("LastUpdated")
+            .and_then(|v| v.parse().ok())
+            .unwrap_or_default(),
+    });
+}
+
+/// List installed apps of all Steam libraries.
+///
+/// # Returns
+/// + The Steam root, or `None` if Steam is not installed.
+/// + The installed apps.
+pub fn installed_apps() -> (
+    Option<std::path::PathBuf>,
+    Vec<frontend::protocol::SteamApp>,
+) {
+    let root = match find_root() {
+        Some(v) => v,
+        None => return (None, Vec::new()),
+    };
+
+    let mut ret = Vec::new();
+    for library in library_folders(&root) {
+        let entries = match std::fs::read_dir(library.join("steamapps")) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("appmanifest_") == false || name.ends_with(".acf") == false {
+                continue;
+            }
+
+            match read_appmanifest(&library, &entry.path()) {
+                Ok(v) => ret.push(v),
+                Err(e) => tracing::warn!("parse {} failed: {}", entry.path().display(), e),
+            }
+        }
+    }
+
+    ret.sort_by_key(|v| v.name.to_lowercase());
+    return (Some(root), ret);
+}
+
+/// Map app ids to names, for installed Steam apps and non-Steam games.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+/// + `apps`: The installed apps, see [installed_apps].
+pub fn app_names(
+    root: &std::path::Path,
+    apps: &[frontend::protocol::SteamApp],
+) -> std::collections::HashMap<u32, String> {
+    let mut ret = std::collections::HashMap::new();
+
+    for app in apps {
+        ret.insert(app.appid, app.name.clone());
+    }
+
+    for path in shortcuts::files(root) {
+        match shortcuts::read(&path) {
+            Ok(v) => {
+                for shortcut in v {
+                    ret.insert(shortcut.appid, shortcut.name);
+                }
+            }
+            Err(e) => tracing::warn!("parse {} failed: {}", path.display(), e),
+        }
+    }
+
+    return ret;
+}
+
+/// Installed apps and game names, read again only when Steam changed its
+/// library or shortcut files.
+#[derive(Default)]
+pub struct AppCache {
+    inner: std::sync::Mutex<Option<CachedApps>>,
+}
+
+struct CachedApps {
+    /// Modification times of the files the apps were read from, see [stamp].
+    stamp: Vec<Option<std::time::SystemTime>>,
+    root: Option<std::path::PathBuf>,
+    apps: std::sync::Arc<Vec<frontend::protocol::SteamApp>>,
+    names: std::sync::Arc<std::collections::HashMap<u32, String>>,
+}
+
+impl AppCache {
+    /// Get installed apps and game names by app id, see [installed_apps] and
+    /// [app_names].
+    ///
+    /// This is blocking and should be called from a blocking thread pool.
+    pub fn get(
+        &self,
+    ) -> (
+        std::sync::Arc<Vec<frontend::protocol::SteamApp>>,
+        std::sync::Arc<std::collections::HashMap<u32, String>>,
+    ) {
+        let root = find_root();
+        let stamp = stamp(root.as_deref());
+
+        let mut inner = self.inner.lock().unwrap();
+        if let Some(v) = inner.as_ref() {
+            if v.root == root && v.stamp == stamp {
+                return (v.apps.clone(), v.names.clone());
+            }
+        }
+
+        let (root, apps) = installed_apps();
+        let names = match &root {
+            Some(v) => app_names(v, &apps),
+            None => std::collections::HashMap::new(),
+        };
+
+        let ret = (std::sync::Arc::new(apps), std::sync::Arc::new(names));
+        *inner = Some(CachedApps {
+            stamp: stamp,
+            root: root,
+            apps: ret.0.clone(),
+            names: ret.1.clone(),
+        });
+        return ret;
+    }
+}
+
+/// Modification times of `libraryfolders.vdf`, the `steamapps` folder of
+/// each library and each `shortcuts.vdf`. Steam replaces app manifests by
+/// renaming, which changes the time of their folder.
+fn stamp(root: Option<&std::path::Path>) -> Vec<Option<std::time::SystemTime>> {
+    let root = match root {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+    let modified = |path: &std::path::Path| std::fs::metadata(path).and_then(|v| v.modified()).ok();
+
+    let mut ret = vec![modified(&root.join("steamapps").join("libraryfolders.vdf"))];
+    for library in library_folders(root) {
+        ret.push(modified(&library.join("steamapps")));
+    }
+    for path in shortcuts::files(root) {
+        ret.push(modified(&path));
+    }
+
+    return ret;
+}
+
+/// The icon of annotated entries of a directory, `None` if the directory is
+/// not `steamapps/common`, `steamapps/compatdata` or `steamapps/shadercache`.
+fn annotation_icon(path: &std::path::Path) -> Option<&'static str> {
+    let is_steamapps = path
+        .parent()
+        .and_then(|v| v.file_name())
+        .map(|v| v == "steamapps")
+        .unwrap_or(false);
+    if is_steamapps == false {
+        return None;
+    }
+
+    return match path.file_name().and_then(|v| v.to_str()) {
+        Some("common") => Some("🎮"),
+        Some("compatdata") => Some("🍷"),
+        Some("shadercache") => Some("🎨"),
+        _ => None,
+    };
+}
+
+/// Whether [annotate] adds anything to entries of a directory.
+pub fn is_annotated(path: &std::path::Path) -> bool {
+    return annotation_icon(path).is_some();
+}
+
+/// Add game names to entries of `steamapps/common`, `steamapps/compatdata`
+/// and `steamapps/shadercache`. Other directories are left untouched.
+///
+/// # Arguments
+/// + `cache`: The cached apps and game names.
+/// + `path`: The listed directory.
+/// + `entries`: The entries of the directory.
+pub fn annotate(
+    cache: &AppCache,
+    path: &std::path::Path,
+    entries: &mut [frontend::protocol::ReaddirResponseItem],
+) {
+    let icon = match annotation_icon(path) {
+        Some(v) => v,
+        None => return,
+    };
+
+    let (apps, names) = cache.get();
+
+    if path.ends_with("common") {
+        // Library paths are canonical, the listed path may not be.
+        let path = std::fs::canonicalize(path).unwrap_or(path.to_path_buf());
+        for entry in entries.iter_mut() {
+            let app = apps.iter().find(|v| {
+                let install_dir = std::path::Path::new(&v.install_dir);
+                install_dir.parent() == Some(path.as_path())
+                    && install_dir.file_name() == Some(std::ffi::OsStr::new(&entry.f_name))
+            });
+            if let Some(app) = app {
+                entry.f_label = Some(app.name.clone());
+                entry.f_icon = Some(icon.to_string());
+            }
+        }
+        return;
+    }
+
+    for entry in entries.iter_mut() {
+        let name = entry.f_name.parse::<u32>().ok().and_then(|v| names.get(&v));
+        if let Some(name) = name {
+            entry.f_label = Some(name.clone());
+            entry.f_icon = Some(icon.to_string());
+        }
+    }
+}
+
+/// List Proton prefixes of all libraries, named after their games.
+///
+/// # Returns
+/// + The prefixes, sorted by name. Unknown app ids come last.
+pub fn prefixes() -> Vec<frontend::protocol::SteamPrefix> {
+    let (root, apps) = installed_apps();
+    let root = match root {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+
+    let names = app_names(&root, &apps);
+    let mut ret: Vec<frontend::protocol::SteamPrefix> = proton::prefixes(&library_folders(&root))
+        .into_iter()
+        .map(|(appid, path)| frontend::protocol::SteamPrefix {
+            appid: appid,
+            name: names.get(&appid).cloned(),
+            path: path.to_string_lossy().to_string(),
+        })
+        .collect();
+
+    ret.sort_by_key(|v| {
+        (
+            v.name.is_none(),
+            v.name.clone().unwrap_or_default().to_lowercase(),
+            v.appid,
+        )
+    });
+    return ret;
+}
+
+/// List non-Steam games of all Steam users.
+///
+/// # Returns
+/// + The ids of Steam users.
+/// + The non-Steam games, sorted by name.
+pub fn shortcuts() -> (Vec<String>, Vec<frontend::protocol::SteamShortcut>) {
+    let root = match find_root() {
+        Some(v) => v,
+        None => return (Vec::new(), Vec::new()),
+    };
+
+    let users = shortcuts::users(&root);
+    let mut ret = Vec::new();
+    for user in users.iter() {
+        let path = shortcuts::file(&root, user);
+        if path.is_file() == false {
+            continue;
+        }
+
+        match shortcuts::read(&path) {
+            Ok(v) => {
+                for shortcut in v {
+                    ret.push(to_protocol_shortcut(user, shortcut));
+                }
+            }
+            Err(e) => tracing::warn!("parse {} failed: {}", path.display(), e),
+        }
+    }
+
+    ret.sort_by_key(|v| v.name.to_lowercase());
+    return (users, ret);
+}
+
+/// Whether the Steam client is running. Steam rewrites `shortcuts.vdf` from
+/// memory when it exits, dropping changes made meanwhile.
+pub fn is_running() -> bool {
+    let dir = match std::fs::read_dir("/proc") {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+
+    for entry in dir.flatten() {
+        let comm = match std::fs::read_to_string(entry.path().join("comm")) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if comm.trim_end() == "steam" {
+            return true;
+        }
+    }
+
+    return false;
+}
+
+/// Add a non-Steam game.
+///
+/// # Arguments
+/// + `req`: The shortcut to add. Without a user, the last logged in user is
+///   used.
+pub fn add_shortcut(
+    req: &frontend::protocol::SteamShortcutAddRequest,
+) -> Result<frontend::protocol::SteamShortcut, String> {
+    let root = match find_root() {
+        Some(v) => v,
+        None => return Err("Steam is not installed".to_string()),
+    };
+
+    let user = match &req.user {
+        Some(v) => v.clone(),
+        None => match shortcuts::last_user(&root) {
+            Some(v) => v,
+            None => return Err("no Steam user found".to_string()),
+        },
+    };
+    if shortcuts::users(&root).contains(&user) == false {
+        return Err(format!("unknown Steam user {}", user));
+    }
+
+    let name = match req.name.trim().is_empty() {
+        true => std::path::Path::new(&req.exe)
+            .file_stem()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        false => req.name.trim().to_string(),
+    };
+
+    let path = shortcuts::file(&root, &user);
+    let ret = shortcuts::add(
+        &path,
+        name.as_str(),
+        req.exe.as_str(),
+        req.start_dir.as_str(),
+        req.launch_options.as_str(),
+    )?;
+
+    tracing::info!("add shortcut {} to {}", req.exe, path.display());
+    return Ok(to_protocol_shortcut(&user, ret));
+}
+
+/// Remove a non-Steam game.
+///
+/// # Arguments
+/// + `user`: The id of the Steam user.
+/// + `appid`: The app id of the shortcut.
+pub fn remove_shortcut(user: &str, appid: u32) -> Result<(), String> {
+    let root = match find_root() {
+        Some(v) => v,
+        None => return Err("Steam is not installed".to_string()),
+    };
+
+    if shortcuts::users(&root).iter().any(|v| v == user) == false {
+        return Err(format!("unknown Steam user {}", user));
+    }
+
+    let path = shortcuts::file(&root, user);
+    shortcuts::remove(&path, appid)?;
+
+    tracing::info!("remove shortcut {} from {}", appid, path.display());
+    return Ok(());
+}
+
+fn to_protocol_shortcut(
+    user: &str,
+    shortcut: shortcuts::Shortcut,
+) -> frontend::protocol::SteamShortcut {
+    return frontend::protocol::SteamShortcut {
+        user: user.to_string(),
+        appid: shortcut.appid,
+        name: shortcut.name,
+        exe: shortcut.exe,
+        start_dir: shortcut.start_dir,
+        launch_options: shortcut.launch_options,
+    };
+}
+
+/// List screenshots of all Steam users, grouped by game.
+pub fn screenshots() -> Vec<frontend::protocol::ScreenshotGame> {
+    let (root, apps) = installed_apps();
+    let root = match root {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+
+    let names = app_names(&root, &apps);
+    return screenshots::list(&root, &names);
+}
+
+/// Read a screenshot or a thumbnail. Other files are refused.
+///
+/// # Arguments
+/// + `path`: The path to the image.
+pub fn read_screenshot(path: &str) -> Result<Vec<u8>, std::io::Error> {
+    let path = std::path::Path::new(path);
+    let allowed = match find_root() {
+        Some(root) => screenshots::is_screenshot(&root, path),
+        None => false,
+    };
+    if allowed == false {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{} is not a screenshot", path.display()),
+        ));
+    }
+
+    return std::fs::read(path);
+}
+
+/// Pack screenshots into a zip archive, in one folder per game.
+///
+/// The archive is written to an unnamed temporary file, so it is never held
+/// in memory and disappears once closed.
+///
+/// # Arguments
+/// + `paths`: The screenshots to pack.
+///
+/// # Returns
+/// + The archive, positioned at its start.
+pub fn zip_screenshots(paths: &[String]) -> Result<std::fs::File, std::io::Error> {
+    use std::io::Seek;
+
+    let (root, apps) = installed_apps();
+    let root = match root {
+        Some(v) => v,
+        None => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "Steam is not installed",
+            ))
+        }
+    };
+
+    let names = app_names(&root, &apps);
+
+    let temp_path = std::env::temp_dir().join(format!(
+        "screenshots-{}-{}.zip",
+        std::process::id(),
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos()
+    ));
+    let f = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create_new(true)
+        .open(&temp_path)?;
+    // The open file stays readable after its name is gone.
+    std::fs::remove_file(&temp_path)?;
+
+    let mut f = screenshots::zip(&root, &names, paths, f)?;
+    f.rewind()?;
+    return Ok(f);
+}
+
+/// Find `compatdata` and `shadercache` folders of uninstalled games.
+pub fn orphans() -> Vec<frontend::protocol::SteamOrphan> {
+    let (root, apps) = installed_apps();
+    let root = match root {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+
+    let known = app_names(&root, &apps).into_keys().collect();
+    return cleanup::orphans(&library_folders(&root), &known);
+}
+
+/// Remove orphaned folders by moving them to the trash.
+///
+/// Paths that are not orphans anymore, see [orphans], are refused. Folders
+/// that can not be moved to a trash, like bind mounts crossing filesystems,
+/// are kept and reported as errors.
+///
+/// # Arguments
+/// + `paths`: The folders to remove.
+pub fn remove_orphans(paths: &[String]) -> frontend::protocol::SteamCleanupRemoveResponse {
+    let mut ret = frontend::protocol::SteamCleanupRemoveResponse {
+        trashed: 0,
+        errors: Vec::new(),
+    };
+
+    let (root, apps) = installed_apps();
+    let root = match root {
+        Some(v) => v,
+        None => {
+            ret.errors.push("Steam is not installed".to_string());
+            return ret;
+        }
+    };
+
+    let libraries = library_folders(&root);
+    let known = app_names(&root, &apps).into_keys().collect();
+    for path in paths {
+        let path = std::path::Path::new(path);
+        if cleanup::is_orphan(&libraries, &known, path) == false {
+            ret.errors
+                .push(format!("{} is not an orphan", path.display()));
+            continue;
+        }
+
+        match crate::trash::put(path) {
+            Ok(_) => ret.trashed += 1,
+            Err(e) if crate::trash::is_cross_device(&e) => ret.errors.push(format!(
+                "{}: can not be moved to the trash, delete it from the file list",
+                path.display()
+            )),
+            Err(e) => ret.errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+
+    return ret;
+}
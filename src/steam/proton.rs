@@ -0,0 +1,76 @@
+/// Well-known locations inside a Proton prefix, relative to
+/// `pfx/drive_c/users/steamuser`.
+const LOCATIONS: &[(&str, &str)] = &[
+    ("AppData/Roaming", "AppData/Roaming"),
+    ("AppData/Local", "AppData/Local"),
+    ("AppData/LocalLow", "AppData/LocalLow"),
+    ("Documents", "Documents"),
+    ("Documents", "My Documents"),
+    ("My Games", "Documents/My Games"),
+    ("Saved Games", "Saved Games"),
+];
+
+/// List Proton prefixes `steamapps/compatdata/<appid>` of all libraries.
+///
+/// # Arguments
+/// + `libraries`: The Steam library folders.
+pub fn prefixes(libraries: &[std::path::PathBuf]) -> Vec<(u32, std::path::PathBuf)> {
+    let mut ret = Vec::new();
+
+    for library in libraries {
+        let entries = match std::fs::read_dir(library.join("steamapps").join("compatdata")) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let appid = match entry.file_name().to_string_lossy().parse::<u32>() {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let path = entry.path();
+            if path.join("pfx").is_dir() {
+                ret.push((appid, path));
+            }
+        }
+    }
+
+    return ret;
+}
+
+/// List existing well-known locations of a Proton prefix.
+///
+/// # Arguments
+/// + `prefix`: The path to `steamapps/compatdata/<appid>`.
+pub fn locations(prefix: &std::path::Path) -> Vec<frontend::protocol::PrefixLocation> {
+    let user = prefix
+        .join("pfx")
+        .join("drive_c")
+        .join("users")
+        .join("steamuser");
+
+    let mut ret = Vec::<frontend::protocol::PrefixLocation>::new();
+    for (name, relative) in LOCATIONS {
+        let path = user.join(relative);
+        if path.is_dir() == false || ret.iter().any(|v| v.name == *name) {
+            continue;
+        }
+
+        ret.push(frontend::protocol::PrefixLocation {
+            name: name.to_string(),
+            path: path.to_string_lossy().to_string(),
+        });
+    }
+
+    ret.push(frontend::protocol::PrefixLocation {
+        name: "drive_c".to_string(),
+        path: prefix
+            .join("pfx")
+            .join("drive_c")
+            .to_string_lossy()
+            .to_string(),
+    });
+
+    return ret;
+}
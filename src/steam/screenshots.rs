@@ -0,0 +1,279 @@
+/// Bits set in the game id of non-Steam games, with the app id in the high
+/// 32 bits.
+const SHORTCUT_GAMEID_FLAGS: u64 = 0x02000000;
+
+/// List screenshots of all Steam users, grouped by game.
+///
+/// Screenshots are stored as
+/// `userdata/<user>/760/remote/<gameid>/screenshots/<file>.jpg`, with a
+/// thumbnail of the same name in the `thumbnails` subfolder.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+/// + `names`: App ids to game names, see [super::app_names].
+///
+/// # Returns
+/// + The games, sorted by name. Screenshots are sorted newest first.
+pub fn list(
+    root: &std::path::Path,
+    names: &std::collections::HashMap<u32, String>,
+) -> Vec<frontend::protocol::ScreenshotGame> {
+    let mut ret = Vec::<frontend::protocol::ScreenshotGame>::new();
+
+    for remote in remote_dirs(root) {
+        let entries = match std::fs::read_dir(&remote) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let gameid = match entry.file_name().to_string_lossy().parse::<u64>() {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let screenshots = read_screenshots(&entry.path().join("screenshots"));
+            if screenshots.is_empty() {
+                continue;
+            }
+
+            // Several users may have screenshots of the same game.
+            match ret.iter_mut().find(|v| v.gameid == gameid) {
+                Some(v) => v.screenshots.extend(screenshots),
+                None => ret.push(frontend::protocol::ScreenshotGame {
+                    gameid: gameid,
+                    name: names.get(&appid(gameid)).cloned(),
+                    screenshots: screenshots,
+                }),
+            }
+        }
+    }
+
+    for game in ret.iter_mut() {
+        game.screenshots
+            .sort_by_key(|v| std::cmp::Reverse(v.created));
+    }
+    ret.sort_by_key(|v| {
+        (
+            v.name.is_none(),
+            v.name.clone().unwrap_or_default().to_lowercase(),
+            v.gameid,
+        )
+    });
+    return ret;
+}
+
+/// Whether a path is a screenshot or a thumbnail of a Steam user.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+/// + `path`: The path to check.
+pub fn is_screenshot(root: &std::path::Path, path: &std::path::Path) -> bool {
+    let path = match std::fs::canonicalize(path) {
+        Ok(v) => v,
+        Err(_) => return false,
+    };
+
+    let is_image = path
+        .extension()
+        .map(|v| v.eq_ignore_ascii_case("jpg") || v.eq_ignore_ascii_case("png"))
+        .unwrap_or(false);
+    if is_image == false {
+        return false;
+    }
+
+    // Either `<remote>/<gameid>/screenshots/<file>` or
+    // `<remote>/<gameid>/screenshots/thumbnails/<file>`.
+    return remote_dirs(root).iter().any(|remote| {
+        let relative = match path.strip_prefix(remote) {
+            Ok(v) => v,
+            Err(_) => return false,
+        };
+        let parts: Vec<String> = relative
+            .components()
+            .map(|v| v.as_os_str().to_string_lossy().to_string())
+            .collect();
+
+        match parts.len() {
+            3 => parts[1] == "screenshots",
+            4 => parts[1] == "screenshots" && parts[2] == "thumbnails",
+            _ => false,
+        }
+    });
+}
+
+/// Pack screenshots into a zip archive, in one folder per game.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+/// + `names`: App ids to game names, see [super::app_names].
+/// + `paths`: The screenshots to pack.
+/// + `out`: Where to write the archive.
+///
+/// # Returns
+/// + `out`, positioned after the archive.
+pub fn zip<W: std::io::Write + std::io::Seek>(
+    root: &std::path::Path,
+    names: &std::collections::HashMap<u32, String>,
+    paths: &[String],
+    out: W,
+) -> Result<W, std::io::Error> {
+    let mut zip = zip::ZipWriter::new(out);
+
+    // Images are compressed already.
+    let options =
+        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+
+    let mut added = std::collections::HashSet::new();
+    for path in paths {
+        let path = std::path::Path::new(path);
+        if is_screenshot(root, path) == false {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::PermissionDenied,
+                format!("{} is not a screenshot", path.display()),
+            ));
+        }
+
+        // `<gameid>/screenshots/<file>`, or a thumbnail one folder deeper.
+        let file_name = path.file_name().unwrap().to_string_lossy().to_string();
+        let gameid = remote_dirs(root)
+            .iter()
+            .find_map(|remote| path.strip_prefix(remote).ok())
+            .and_then(|v| v.components().next())
+            .and_then(|v| v.as_os_str().to_string_lossy().parse::<u64>().ok())
+            .unwrap_or_default();
+        let folder = match names.get(&appid(gameid)) {
+            Some(v) => sanitize(v.as_str()),
+            None => gameid.to_string(),
+        };
+
+        let name = format!("{}/{}", folder, file_name);
+        if added.insert(name.clone()) == false {
+            continue;
+        }
+
+        zip.start_file(name, options)?;
+        let mut f = std::fs::File::open(path)?;
+        std::io::copy(&mut f, &mut zip)?;
+    }
+
+    let ret = zip.finish()?;
+    return Ok(ret);
+}
+
+/// The app id of a game id. Non-Steam games keep it in the high 32 bits.
+fn appid(gameid: u64) -> u32 {
+    if gameid > u32::MAX as u64 && gameid & SHORTCUT_GAMEID_FLAGS != 0 {
+        return (gameid >> 32) as u32;
+    }
+    return gameid as u32;
+}
+
+/// List canonical `userdata/<user>/760/remote` folders.
+fn remote_dirs(root: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut ret = Vec::new();
+
+    let entries = match std::fs::read_dir(root.join("userdata")) {
+        Ok(v) => v,
+        Err(_) => return ret,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path().join("760").join("remote");
+        if let Ok(v) = std::fs::canonicalize(&path) {
+            ret.push(v);
+        }
+    }
+
+    return ret;
+}
+
+fn read_screenshots(path: &std::path::Path) -> Vec<frontend::protocol::Screenshot> {
+    let mut ret = Vec::new();
+
+    let entries = match std::fs::read_dir(path) {
+        Ok(v) => v,
+        Err(_) => return ret,
+    };
+
+    for entry in entries.flatten() {
+        let metadata = match entry.metadata() {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        if metadata.is_file() == false {
+            continue;
+        }
+
+        let thumbnail = path.join("thumbnails").join(entry.file_name());
+
+        ret.push(frontend::protocol::Screenshot {
+            path: entry.path().to_string_lossy().to_string(),
+            thumbnail: match thumbnail.is_file() {
+                true => Some(thumbnail.to_string_lossy().to_string()),
+                false => None,
+            },
+            // Copied screenshots lose their mtime, but keep their name.
+            created: capture_time(entry.file_name().to_string_lossy().as_ref())
+                .or(metadata
+                    .modified()
+                    .ok()
+                    .and_then(|v| v.duration_since(std::time::UNIX_EPOCH).ok())
+                    .map(|v| v.as_secs()))
+                .unwrap_or_default(),
+            size: metadata.len(),
+        });
+    }
+
+    return ret;
+}
+
+/// The capture time of a screenshot named `YYYYMMDDhhmmss_N.jpg` by Steam, in
+/// local time.
+fn capture_time(name: &str) -> Option<u64> {
+    let (stamp, rest) = name.split_once('_')?;
+    if stamp.len() != 14 || rest.starts_with(|c: char| c.is_ascii_digit()) == false {
+        return None;
+    }
+
+    return chrono::NaiveDateTime::parse_from_str(stamp, "%Y%m%d%H%M%S")
+        .ok()
+        .and_then(|v| v.and_local_timezone(chrono::Local).earliest())
+        .map(|v| v.timestamp() as u64);
+}
+
+/// Replace characters that are not allowed in file names on common systems.
+fn sanitize(name: &str) -> String {
+    return name
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn capture_time_from_name() {
+        let expected = chrono::NaiveDate::from_ymd_opt(2024, 1, 31)
+            .unwrap()
+            .and_hms_opt(12, 5, 9)
+            .unwrap()
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .unwrap()
+            .timestamp() as u64;
+        assert_eq!(capture_time("20240131120509_1.jpg"), Some(expected));
+        assert_eq!(capture_time("20240131120509_12.png"), Some(expected));
+
+        assert_eq!(capture_time("screenshot.jpg"), None);
+        assert_eq!(capture_time("20240131120509.jpg"), None);
+        assert_eq!(capture_time("2024013112050_1.jpg"), None);
+        assert_eq!(capture_time("20241331120509_1.jpg"), None);
+        assert_eq!(capture_time("20240131120509_a.jpg"), None);
+    }
+}
@@ -0,0 +1,353 @@
+use super::binary_vdf;
+
+/// How many backups of a `shortcuts.vdf` are kept.
+const KEEP_BACKUPS: usize = 5;
+
+/// A non-Steam game in `userdata/<id>/config/shortcuts.vdf`.
+#[derive(Debug, Clone)]
+pub struct Shortcut {
+    /// The app id, also the name of its `compatdata` folder.
+    pub appid: u32,
+
+    /// The display name.
+    pub name: String,
+
+    /// The executable, without quotes.
+    pub exe: String,
+
+    /// The working directory, without quotes.
+    pub start_dir: String,
+
+    /// The launch options.
+    pub launch_options: String,
+}
+
+/// List ids of Steam users with a `userdata/<id>/config` folder.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+pub fn users(root: &std::path::Path) -> Vec<String> {
+    let mut ret = Vec::new();
+
+    let entries = match std::fs::read_dir(root.join("userdata")) {
+        Ok(v) => v,
+        Err(_) => return ret,
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+
+        // `0` is used while nobody is logged in.
+        if name == "0" || name.parse::<u32>().is_err() {
+            continue;
+        }
+        if entry.path().join("config").is_dir() {
+            ret.push(name);
+        }
+    }
+
+    ret.sort();
+    return ret;
+}
+
+/// The user who logged in last, guessed from `config/localconfig.vdf`.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+pub fn last_user(root: &std::path::Path) -> Option<String> {
+    return users(root).into_iter().max_by_key(|v| {
+        let path = root
+            .join("userdata")
+            .join(v)
+            .join("config")
+            .join("localconfig.vdf");
+        std::fs::metadata(path)
+            .and_then(|v| v.modified())
+            .unwrap_or(std::time::UNIX_EPOCH)
+    });
+}
+
+/// The `shortcuts.vdf` of a user, which may not exist yet.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+/// + `user`: The id of the Steam user.
+pub fn file(root: &std::path::Path, user: &str) -> std::path::PathBuf {
+    return root
+        .join("userdata")
+        .join(user)
+        .join("config")
+        .join("shortcuts.vdf");
+}
+
+/// List `userdata/<id>/config/shortcuts.vdf` of all Steam users.
+///
+/// # Arguments
+/// + `root`: The Steam installation directory.
+pub fn files(root: &std::path::Path) -> Vec<std::path::PathBuf> {
+    let mut ret = Vec::new();
+
+    let entries = match std::fs::read_dir(root.join("userdata")) {
+        Ok(v) => v,
+        Err(_) => return ret,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path().join("config").join("shortcuts.vdf");
+        if path.is_file() {
+            ret.push(path);
+        }
+    }
+
+    return ret;
+}
+
+/// Read non-Steam games from a `shortcuts.vdf`.
+///
+/// # Arguments
+/// + `path`: The path to `shortcuts.vdf`.
+pub fn read(path: &std::path::Path) -> Result<Vec<Shortcut>, String> {
+    let data = std::fs::read(path).map_err(|e| e.to_string())?;
+    let doc = binary_vdf::parse(&data)?;
+
+    let mut ret = Vec::new();
+    let shortcuts = match doc.get("shortcuts") {
+        Some(v) => v,
+        None => return Ok(ret),
+    };
+
+    for (_, shortcut) in shortcuts.entries() {
+        let appid = match shortcut.get_i32("appid") {
+            Some(v) => v as u32,
+            None => continue,
+        };
+
+        ret.push(Shortcut {
+            appid: appid,
+            name: shortcut.get_str("AppName").unwrap_or_default().to_string(),
+            exe: unquote(&shortcut.get_str("Exe").unwrap_or_default()),
+            start_dir: unquote(&shortcut.get_str("StartDir").unwrap_or_default()),
+            launch_options: shortcut
+                .get_str("LaunchOptions")
+                .unwrap_or_default()
+                .to_string(),
+        });
+    }
+
+    return Ok(ret);
+}
+
+/// Add a non-Steam game to a `shortcuts.vdf`, which is created if missing.
+///
+/// The file is backed up first. Steam only picks up the change after a
+/// restart.
+///
+/// # Arguments
+/// + `path`: The path to `shortcuts.vdf`.
+/// + `name`: The display name.
+/// + `exe`: The executable.
+/// + `start_dir`: The working directory, the folder of `exe` if empty.
+/// + `launch_options`: The launch options.
+pub fn add(
+    path: &std::path::Path,
+    name: &str,
+    exe: &str,
+    start_dir: &str,
+    launch_options: &str,
+) -> Result<Shortcut, String> {
+    let mut shortcuts = read_entries(path)?;
+
+    let start_dir = match start_dir.is_empty() {
+        true => std::path::Path::new(exe)
+            .parent()
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or_default(),
+        false => start_dir.to_string(),
+    };
+
+    // Steam quotes paths, and derives the id of the shortcut from them.
+    let quoted_exe = format!("\"{}\"", exe);
+    let appid = shortcut_appid(quoted_exe.as_str(), name);
+    if shortcuts
+        .iter()
+        .any(|(_, v)| v.get_i32("appid") == Some(appid as i32))
+    {
+        return Err(format!("{} is already in Steam", name));
+    }
+
+    let string = |v: &str| binary_vdf::Value::String(v.as_bytes().to_vec());
+    let entry = binary_vdf::Value::Map(vec![
+        ("appid".into(), binary_vdf::Value::Int32(appid as i32)),
+        ("AppName".into(), string(name)),
+        ("Exe".into(), string(quoted_exe.as_str())),
+        (
+            "StartDir".into(),
+            string(format!("\"{}\"", start_dir).as_str()),
+        ),
+        ("icon".into(), string("")),
+        ("ShortcutPath".into(), string("")),
+        ("LaunchOptions".into(), string(launch_options)),
+        ("IsHidden".into(), binary_vdf::Value::Int32(0)),
+        ("AllowDesktopConfig".into(), binary_vdf::Value::Int32(1)),
+        ("AllowOverlay".into(), binary_vdf::Value::Int32(1)),
+        ("OpenVR".into(), binary_vdf::Value::Int32(0)),
+        ("Devkit".into(), binary_vdf::Value::Int32(0)),
+        ("DevkitGameID".into(), string("")),
+        ("DevkitOverrideAppID".into(), binary_vdf::Value::Int32(0)),
+        ("LastPlayTime".into(), binary_vdf::Value::Int32(0)),
+        ("FlatpakAppID".into(), string("")),
+        ("tags".into(), binary_vdf::Value::Map(Vec::new())),
+    ]);
+    shortcuts.push((Vec::new(), entry));
+
+    write_entries(path, shortcuts)?;
+
+    return Ok(Shortcut {
+        appid: appid,
+        name: name.to_string(),
+        exe: exe.to_string(),
+        start_dir: start_dir,
+        launch_options: launch_options.to_string(),
+    });
+}
+
+/// Remove a non-Steam game from a `shortcuts.vdf`.
+///
+/// The file is backed up first.
+///
+/// # Arguments
+/// + `path`: The path to `shortcuts.vdf`.
+/// + `appid`: The app id of the shortcut.
+pub fn remove(path: &std::path::Path, appid: u32) -> Result<(), String> {
+    let mut shortcuts = read_entries(path)?;
+
+    let len = shortcuts.len();
+    shortcuts.retain(|(_, v)| v.get_i32("appid") != Some(appid as i32));
+    if shortcuts.len() == len {
+        return Err(format!("no shortcut {} in {}", appid, path.display()));
+    }
+
+    return write_entries(path, shortcuts);
+}
+
+/// The app id Steam assigns to a shortcut.
+///
+/// # Arguments
+/// + `exe`: The quoted executable.
+/// + `name`: The display name.
+fn shortcut_appid(exe: &str, name: &str) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(exe.as_bytes());
+    hasher.update(name.as_bytes());
+    return hasher.finalize() | 0x80000000;
+}
+
+/// Read the entries of the `shortcuts` map, or none if the file is missing.
+fn read_entries(path: &std::path::Path) -> Result<Vec<(Vec<u8>, binary_vdf::Value)>, String> {
+    let data = match std::fs::read(path) {
+        Ok(v) => v,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.to_string()),
+    };
+
+    let doc = binary_vdf::parse(&data)?;
+    let ret = match doc.get("shortcuts") {
+        Some(v) => v.entries().to_vec(),
+        None => Vec::new(),
+    };
+    return Ok(ret);
+}
+
+/// Back up and replace the `shortcuts` map. Entries are renumbered, as Steam
+/// expects keys `0`, `1`, ...
+fn write_entries(
+    path: &std::path::Path,
+    shortcuts: Vec<(Vec<u8>, binary_vdf::Value)>,
+) -> Result<(), String> {
+    if path.exists() {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let mut backup = path.with_extension(format!("vdf.{}.bak", now));
+        if std::fs::symlink_metadata(&backup).is_ok() {
+            backup = crate::fileops::unique_name(&backup);
+        }
+        std::fs::copy(path, &backup).map_err(|e| e.to_string())?;
+        tracing::info!("backup {} to {}", path.display(), backup.display());
+        prune_backups(path);
+    }
+
+    let shortcuts = shortcuts
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (_, v))| (idx.to_string().into_bytes(), v))
+        .collect();
+    let doc = binary_vdf::Value::Map(vec![(
+        "shortcuts".into(),
+        binary_vdf::Value::Map(shortcuts),
+    )]);
+
+    // Write to a temporary file, so Steam never reads a partial file.
+    let temp_path = path.with_extension("vdf.tmp");
+    std::fs::write(&temp_path, binary_vdf::write(&doc)).map_err(|e| e.to_string())?;
+    std::fs::rename(&temp_path, path).map_err(|e| e.to_string())?;
+
+    return Ok(());
+}
+
+/// Remove all but the last [KEEP_BACKUPS] backups of a `shortcuts.vdf`.
+fn prune_backups(path: &std::path::Path) {
+    let parent = path.parent().unwrap_or(std::path::Path::new("/"));
+    let prefix = format!(
+        "{}.",
+        path.file_name().unwrap_or_default().to_string_lossy()
+    );
+
+    let entries = match std::fs::read_dir(parent) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    let mut backups = Vec::new();
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name.starts_with(prefix.as_str()) == false || name.ends_with(".bak") == false {
+            continue;
+        }
+        let modified = entry
+            .metadata()
+            .and_then(|v| v.modified())
+            .unwrap_or(std::time::UNIX_EPOCH);
+        backups.push((modified, name, entry.path()));
+    }
+
+    // Newest first.
+    backups.sort();
+    backups.reverse();
+    for (_, _, backup) in backups.into_iter().skip(KEEP_BACKUPS) {
+        match std::fs::remove_file(&backup) {
+            Ok(_) => tracing::info!("remove old backup {}", backup.display()),
+            Err(e) => tracing::warn!("failed to remove {}: {}", backup.display(), e),
+        }
+    }
+}
+
+fn unquote(s: &str) -> String {
+    return s.trim_matches('"').to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn appid_of_shortcut() {
+        let appid = shortcut_appid("\"/home/deck/Games/Celeste/Celeste\"", "Celeste");
+        assert_eq!(appid, 0xd3dccc67);
+
+        // The high bit is always set, as Steam does.
+        let appid = shortcut_appid("\"/usr/bin/true\"", "");
+        assert_eq!(appid & 0x80000000, 0x80000000);
+    }
+}
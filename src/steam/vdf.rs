@@ -0,0 +1,276 @@
+/// A value of Valve's KeyValues text format, as used by `libraryfolders.vdf`
+/// and `appmanifest_*.acf`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    /// A string value.
+    String(String),
+
+    /// A nested object. Keys may repeat, so the order is kept.
+    Object(Vec<(String, Value)>),
+}
+
+impl Value {
+    /// Get a child value. Keys are case-insensitive, as in Steam.
+    ///
+    /// # Arguments
+    /// + `key`: The key to look up.
+    pub fn get(&self, key: &str) -> Option<&Value> {
+        match self {
+            Value::Object(v) => v
+                .iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .map(|(_, v)| v),
+            Value::String(_) => None,
+        }
+    }
+
+    /// Get a child string value.
+    ///
+    /// # Arguments
+    /// + `key`: The key to look up.
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        return self.get(key).and_then(|v| v.as_str());
+    }
+
+    /// Get the string if this is a string value.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(v) => Some(v.as_str()),
+            Value::Object(_) => None,
+        }
+    }
+
+    /// Get the entries if this is an object value, or an empty slice.
+    pub fn entries(&self) -> &[(String, Value)] {
+        match self {
+            Value::Object(v) => v.as_slice(),
+            Value::String(_) => &[],
+        }
+    }
+}
+
+/// Parse a KeyValues text document.
+///
+/// # Arguments
+/// + `text`: The content of the file.
+///
+/// # Returns
+/// + An object holding the top level keys, usually only one.
+pub fn parse(text: &str) -> Result<Value, String> {
+    let mut tokenizer = Tokenizer {
+        chars: text.chars().peekable(),
+        line: 1,
+    };
+
+    let ret = parse_object(&mut tokenizer, false)?;
+    return Ok(Value::Object(ret));
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    String(String),
+    Open,
+    Close,
+}
+
+struct Tokenizer<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Tokenizer<'a> {
+    fn next(&mut self) -> Result<Option<Token>, String> {
+        loop {
+            let c = match self.chars.next() {
+                Some(v) => v,
+                None => return Ok(None),
+            };
+
+            match c {
+                '\n' => self.line += 1,
+                c if c.is_whitespace() => (),
+                '{' => return Ok(Some(Token::Open)),
+                '}' => return Ok(Some(Token::Close)),
+                '/' if self.chars.peek() == Some(&'/') => {
+                    // Comment until end of line.
+                    for c in self.chars.by_ref() {
+                        if c == '\n' {
+                            self.line += 1;
+                            break;
+                        }
+                    }
+                }
+                '[' => {
+                    // Platform conditionals like `[$WIN32]` are ignored.
+                    for c in self.chars.by_ref() {
+                        if c == ']' {
+                            break;
+                        }
+                    }
+                }
+                '"' => return self.quoted().map(|v| Some(Token::String(v))),
+                c => {
+                    let mut ret = String::from(c);
+                    while let Some(&c) = self.chars.peek() {
+                        if c.is_whitespace() || c == '{' || c == '}' || c == '"' {
+                            break;
+                        }
+                        ret.push(c);
+                        self.chars.next();
+                    }
+                    return Ok(Some(Token::String(ret)));
+                }
+            }
+        }
+    }
+
+    fn quoted(&mut self) -> Result<String, String> {
+        let mut ret = String::new();
+
+        loop {
+            let c = match self.chars.next() {
+                Some(v) => v,
+                None => return Err(format!("line {}: unterminated string", self.line)),
+            };
+
+            match c {
+                '"' => return Ok(ret),
+                '\\' => match self.chars.next() {
+                    Some('n') => ret.push('\n'),
+                    Some('t') => ret.push('\t'),
+                    Some(c) => ret.push(c),
+                    None => return Err(format!("line {}: unterminated string", self.line)),
+                },
+                c => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    ret.push(c);
+                }
+            }
+        }
+    }
+}
+
+fn parse_object(tokenizer: &mut Tokenizer, nested: bool) -> Result<Vec<(String, Value)>, String> {
+    let mut ret = Vec::new();
+
+    loop {
+        let key = match tokenizer.next()? {
+            Some(Token::String(v)) => v,
+            Some(Token::Close) if nested => return Ok(ret),
+            None if nested == false => return Ok(ret),
+            Some(v) => return Err(format!("line {}: unexpected {:?}", tokenizer.line, v)),
+            None => return Err(format!("line {}: unexpected end of file", tokenizer.line)),
+        };
+
+        let value = match tokenizer.next()? {
+            Some(Token::String(v)) => Value::String(v),
+            Some(Token::Open) => Value::Object(parse_object(tokenizer, true)?),
+            Some(v) => return Err(format!("line {}: unexpected {:?}", tokenizer.line, v)),
+            None => return Err(format!("line {}: unexpected end of file", tokenizer.line)),
+        };
+
+        ret.push((key, value));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `libraryfolders.vdf` as written by Steam on a Steam Deck.
+    const LIBRARY_FOLDERS: &str = r#""libraryfolders"
+{
+	"0"
+	{
+		"path"		"/home/deck/.local/share/Steam"
+		"label"		""
+		"contentid"		"5791389305483040934"
+		"totalsize"		"0"
+		"update_clean_bytes_tally"		"2829516"
+		"time_last_update_corruption"		"0"
+		"apps"
+		{
+			"228980"		"568805316"
+			"1245620"		"51382130982"
+		}
+	}
+	"1"
+	{
+		"path"		"/run/media/mmcblk0p1"
+		"label"		""
+		"contentid"		"1290347598234759234"
+		"totalsize"		"511900942336"
+		"apps"
+		{
+			"1145360"		"9875493120"
+		}
+	}
+}
+"#;
+
+    #[test]
+    fn parses_library_folders() {
+        let doc = parse(LIBRARY_FOLDERS).unwrap();
+        let folders = doc.get("LibraryFolders").unwrap();
+        assert_eq!(folders.entries().len(), 2);
+
+        let first = folders.get("0").unwrap();
+        assert_eq!(first.get_str("path"), Some("/home/deck/.local/share/Steam"));
+        assert_eq!(first.get_str("label"), Some(""));
+        assert_eq!(
+            first.get("apps").unwrap().get_str("1245620"),
+            Some("51382130982")
+        );
+
+        let second = folders.get("1").unwrap();
+        assert_eq!(second.get_str("path"), Some("/run/media/mmcblk0p1"));
+        assert_eq!(second.get("path").unwrap().entries(), &[]);
+        assert_eq!(second.get_str("apps"), None);
+    }
+
+    #[test]
+    fn unescapes_quoted_strings() {
+        let text = r#""AppState" { "name" "Say \"Hi\"\tnow\n" "installdir" "C:\\Games\\Hi" }"#;
+        let doc = parse(text).unwrap();
+        let app = doc.get("AppState").unwrap();
+        assert_eq!(app.get_str("name"), Some("Say \"Hi\"\tnow\n"));
+        assert_eq!(app.get_str("installdir"), Some("C:\\Games\\Hi"));
+    }
+
+    #[test]
+    fn parses_unquoted_tokens_comments_and_conditionals() {
+        let text = "// generated\nAppState\n{\n\tappid 1245620 // Elden Ring\n\tStateFlags \"4\" [$LINUX]\n\tmulti\t\"line\nvalue\"\n}\n";
+        let doc = parse(text).unwrap();
+        let app = doc.get("appstate").unwrap();
+        assert_eq!(app.get_str("appid"), Some("1245620"));
+        assert_eq!(app.get_str("StateFlags"), Some("4"));
+        assert_eq!(app.get_str("multi"), Some("line\nvalue"));
+    }
+
+    #[test]
+    fn keeps_repeated_keys_in_order() {
+        let doc = parse(r#""a" "1" "b" "2" "A" "3""#).unwrap();
+        let keys: Vec<&str> = doc.entries().iter().map(|v| v.0.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b", "A"]);
+        // The first one wins.
+        assert_eq!(doc.get_str("a"), Some("1"));
+    }
+
+    #[test]
+    fn rejects_malformed_documents() {
+        let cases = [
+            (r#""a" { "b" "c""#, "line 1: unexpected end of file"),
+            ("\"a\"\n\"b", "line 2: unterminated string"),
+            (r#""a" "b\"#, "line 1: unterminated string"),
+            (r#""a""#, "line 1: unexpected end of file"),
+            (r#""a" }"#, "line 1: unexpected Close"),
+            (r#"{ "a" "b" }"#, "line 1: unexpected Open"),
+            ("\"a\" {\n}\n}", "line 3: unexpected Close"),
+        ];
+        for (text, error) in cases {
+            assert_eq!(parse(text), Err(error.to_string()), "{}", text);
+        }
+    }
+}
@@ -0,0 +1,579 @@
+/// A trash directory, see the freedesktop.org Trash specification.
+struct TrashDir {
+    /// The trash directory, holding `files` and `info`.
+    path: std::path::PathBuf,
+
+    /// The top directory of the volume, `None` for the home trash. Paths in
+    /// `.trashinfo` files of volume trashes are relative to it.
+    topdir: Option<std::path::PathBuf>,
+}
+
+/// Move a file or directory to the trash, following the freedesktop.org
+/// Trash specification.
+///
+/// Files on the same filesystem as the home directory go to
+/// `$XDG_DATA_HOME/Trash`. Other files go to the trash of their volume,
+/// `$topdir/.Trash/$uid` if the administrator created `.Trash`, or
+/// `$topdir/.Trash-$uid` otherwise.
+///
+/// Mount points are refused, as they can not be moved into a trash on their
+/// own volume.
+///
+/// # Arguments
+/// + `path`: The absolute path to move.
+pub fn put(path: &std::path::Path) -> Result<(), std::io::Error> {
+    let is_mount_point = match path.parent() {
+        Some(parent) => device(parent) != device(path),
+        None => true,
+    };
+    if is_mount_point {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("{} is a mount point", path.display()),
+        ));
+    }
+
+    let trash = trash_for(path)?;
+    let files = trash.path.join("files");
+    let info = trash.path.join("info");
+    std::fs::create_dir_all(&files)?;
+    std::fs::create_dir_all(&info)?;
+
+    let name = path
+        .file_name()
+        .map(|v| v.to_string_lossy().to_string())
+        .unwrap_or("unnamed".to_string());
+
+    // Reserve a unique name by creating its info file first, as the
+    // specification requires.
+    let mut idx = 1;
+    let (trash_name, info_path) = loop {
+        let trash_name = match idx {
+            1 => name.clone(),
+            _ => format!("{}.{}", name, idx),
+        };
+        let info_path = info.join(format!("{}.trashinfo", trash_name));
+
+        let ret = std::fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(&info_path);
+        match ret {
+            Ok(_) if files.join(&trash_name).exists() == false => break (trash_name, info_path),
+            Ok(_) => std::fs::remove_file(&info_path)?,
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+        idx += 1;
+    };
+
+    let original = match &trash.topdir {
+        Some(topdir) => path.strip_prefix(topdir).unwrap_or(path),
+        None => path,
+    };
+    let content = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        encode_path(original),
+        chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+    std::fs::write(&info_path, content)?;
+
+    if let Err(e) = std::fs::rename(path, files.join(&trash_name)) {
+        std::fs::remove_file(&info_path).ok();
+        return Err(e);
+    }
+
+    tracing::info!("trash {} to {}", path.display(), trash.path.display());
+    return Ok(());
+}
+
+/// Whether an error means the file is on another filesystem than its trash.
+pub fn is_cross_device(e: &std::io::Error) -> bool {
+    return e.raw_os_error() == Some(libc::EXDEV);
+}
+
+/// List trashed files of the home trash and of all mounted volumes.
+///
+/// Sizes of directories are left out, see [size].
+///
+/// # Returns
+/// + The trashed files, most recently deleted first.
+pub fn list() -> Vec<frontend::protocol::TrashItem> {
+    let mut ret = Vec::new();
+
+    for trash in trash_dirs() {
+        let entries = match std::fs::read_dir(trash.path.join("info")) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+
+        for entry in entries.flatten() {
+            let info_name = entry.file_name().to_string_lossy().to_string();
+            let name = match info_name.strip_suffix(".trashinfo") {
+                Some(v) => v.to_string(),
+                None => continue,
+            };
+
+            let file = trash.path.join("files").join(&name);
+            let metadata = match std::fs::symlink_metadata(&file) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let (original, deleted) = match read_info(&trash, &entry.path()) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::warn!("read {} failed: {}", entry.path().display(), e);
+                    continue;
+                }
+            };
+
+            let size = match metadata.is_dir() {
+                true => None,
+                false => Some(metadata.len()),
+            };
+
+            ret.push(frontend::protocol::TrashItem {
+                path: file.to_string_lossy().to_string(),
+                name: original
+                    .file_name()
+                    .map(|v| v.to_string_lossy().to_string())
+                    .unwrap_or(name),
+                original_path: original.to_string_lossy().to_string(),
+                deleted: deleted,
+                is_dir: metadata.is_dir(),
+                size: size,
+            });
+        }
+    }
+
+    ret.sort_by_key(|v| std::cmp::Reverse(v.deleted));
+    return ret;
+}
+
+/// The size of a trashed file, of all files for a directory.
+///
+/// # Arguments
+/// + `path`: The path inside a trash, see [frontend::protocol::TrashItem::path].
+pub fn size(path: &str) -> Result<u64, std::io::Error> {
+    let (trash, name) = find_trashed(path)?;
+    let file = trash.path.join("files").join(name);
+
+    let scanned = std::sync::atomic::AtomicU64::new(0);
+    let ret = crate::du::scan(&file, false, &scanned)?;
+    return Ok(ret.apparent);
+}
+
+/// Move a trashed file back to where it was deleted from.
+///
+/// Fails if a file exists at the original location.
+///
+/// # Arguments
+/// + `path`: The path inside a trash, see [frontend::protocol::TrashItem::path].
+///
+/// # Returns
+/// + The original location.
+pub fn restore(path: &str) -> Result<String, std::io::Error> {
+    let (trash, name) = find_trashed(path)?;
+    let info_path = trash.path.join("info").join(format!("{}.trashinfo", name));
+    let (original, _) = read_info(&trash, &info_path)?;
+
+    if std::fs::symlink_metadata(&original).is_ok() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::AlreadyExists,
+            format!("{} already exists", original.display()),
+        ));
+    }
+
+    if let Some(parent) = original.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(trash.path.join("files").join(&name), &original)?;
+    std::fs::remove_file(&info_path)?;
+
+    tracing::info!("restore {} from trash", original.display());
+    return Ok(original.to_string_lossy().to_string());
+}
+
+/// Delete trashed files permanently.
+///
+/// # Arguments
+/// + `paths`: Paths inside a trash, see [frontend::protocol::TrashItem::path],
+///   or `None` to empty all trashes.
+///
+/// # Returns
+/// + The number of deleted files.
+/// + The reasons of failed deletions.
+pub fn empty(paths: Option<Vec<String>>) -> (u64, Vec<String>) {
+    let mut deleted = 0;
+    let mut errors = Vec::new();
+
+    let paths = match paths {
+        Some(v) => v,
+        None => {
+            // Everything in `files` goes, whether its info file is readable
+            // or not.
+            for trash in trash_dirs() {
+                let entries = match std::fs::read_dir(trash.path.join("files")) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    match delete(&trash, name.as_str()) {
+                        Ok(_) => deleted += 1,
+                        Err(e) => errors.push(format!("{}: {}", entry.path().display(), e)),
+                    }
+                }
+
+                remove_orphaned_info(&trash, &mut errors);
+            }
+
+            tracing::info!("deleted {} files from trash", deleted);
+            return (deleted, errors);
+        }
+    };
+
+    for path in paths {
+        let ret = find_trashed(path.as_str()).and_then(|(trash, name)| delete(&trash, &name));
+        match ret {
+            Ok(_) => deleted += 1,
+            Err(e) => errors.push(format!("{}: {}", path, e)),
+        }
+    }
+
+    tracing::info!("deleted {} files from trash", deleted);
+    return (deleted, errors);
+}
+
+/// Delete a trashed file and its info file.
+fn delete(trash: &TrashDir, name: &str) -> Result<(), std::io::Error> {
+    let file = trash.path.join("files").join(name);
+    match std::fs::symlink_metadata(&file)?.is_dir() {
+        true => std::fs::remove_dir_all(&file)?,
+        false => std::fs::remove_file(&file)?,
+    }
+
+    // The info file goes last, so a failure leaves the file listed.
+    let info = trash.path.join("info").join(format!("{}.trashinfo", name));
+    match std::fs::remove_file(info) {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Remove `.trashinfo` files whose trashed file is gone, left behind by
+/// interrupted deletions or other file managers.
+fn remove_orphaned_info(trash: &TrashDir, errors: &mut Vec<String>) {
+    let entries = match std::fs::read_dir(trash.path.join("info")) {
+        Ok(v) => v,
+        Err(_) => return,
+    };
+
+    for entry in entries.flatten() {
+        let info_name = entry.file_name().to_string_lossy().to_string();
+        let name = match info_name.strip_suffix(".trashinfo") {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let file = trash.path.join("files").join(name);
+        if std::fs::symlink_metadata(file).is_ok() {
+            continue;
+        }
+        if let Err(e) = std::fs::remove_file(entry.path()) {
+            errors.push(format!("{}: {}", entry.path().display(), e));
+        }
+    }
+}
+
+/// Find the trash a path was listed from, and its name inside `files`.
+fn find_trashed(path: &str) -> Result<(TrashDir, String), std::io::Error> {
+    let path = std::path::Path::new(path);
+
+    let name = path.file_name().map(|v| v.to_string_lossy().to_string());
+    let files = path.parent();
+    if let (Some(name), Some(files)) = (name, files) {
+        for trash in trash_dirs() {
+            if trash.path.join("files") == files {
+                return Ok((trash, name));
+            }
+        }
+    }
+
+    return Err(std::io::Error::new(
+        std::io::ErrorKind::NotFound,
+        format!("{} is not in a trash", path.display()),
+    ));
+}
+
+/// Read a `.trashinfo` file, see [parse_info].
+fn read_info(
+    trash: &TrashDir,
+    path: &std::path::Path,
+) -> Result<(std::path::PathBuf, u64), std::io::Error> {
+    let text = std::fs::read_to_string(path)?;
+    return parse_info(trash, text.as_str());
+}
+
+/// Parse the content of a `.trashinfo` file.
+///
+/// # Returns
+/// + The absolute original path.
+/// + The deletion time as unix timestamp, `0` if missing or invalid.
+fn parse_info(trash: &TrashDir, text: &str) -> Result<(std::path::PathBuf, u64), std::io::Error> {
+    let mut original = None;
+    let mut deleted = 0;
+    for line in text.lines() {
+        if let Some(v) = line.strip_prefix("Path=") {
+            original = urlencoding::decode(v).ok().map(|v| v.to_string());
+        } else if let Some(v) = line.strip_prefix("DeletionDate=") {
+            deleted = chrono::NaiveDateTime::parse_from_str(v.trim(), "%Y-%m-%dT%H:%M:%S")
+                .ok()
+                .and_then(|v| v.and_local_timezone(chrono::Local).earliest())
+                .map(|v| v.timestamp() as u64)
+                .unwrap_or_default();
+        }
+    }
+
+    let original = match original {
+        Some(v) => std::path::PathBuf::from(v),
+        None => {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "missing Path",
+            ))
+        }
+    };
+
+    let original = match (&trash.topdir, original.is_absolute()) {
+        (Some(topdir), false) => topdir.join(original),
+        _ => original,
+    };
+    return Ok((original, deleted));
+}
+
+/// The trash for a path, on the same filesystem.
+fn trash_for(path: &std::path::Path) -> Result<TrashDir, std::io::Error> {
+    let home = home_trash()?;
+
+    // The home trash may not exist yet, compare with its closest ancestor.
+    let home_device = home.ancestors().find_map(device);
+    let path_device = device(path);
+    if path_device.is_none() || path_device == home_device {
+        return Ok(TrashDir {
+            path: home,
+            topdir: None,
+        });
+    }
+
+    // The top directory is the last ancestor on the same filesystem.
+    let mut topdir = path;
+    while let Some(parent) = topdir.parent() {
+        if device(parent) != path_device {
+            break;
+        }
+        topdir = parent;
+    }
+
+    return Ok(volume_trash(topdir, true).unwrap());
+}
+
+/// List the home trash, and the trashes of mounted volumes that exist.
+fn trash_dirs() -> Vec<TrashDir> {
+    let mut ret = Vec::new();
+
+    if let Ok(v) = home_trash() {
+        ret.push(TrashDir {
+            path: v,
+            topdir: None,
+        });
+    }
+
+    let volumes = crate::volumes::list().unwrap_or_default();
+    for volume in volumes {
+        let topdir = std::path::Path::new(&volume.mount_point);
+        if let Some(v) = volume_trash(topdir, false) {
+            // The home trash may live on a volume too.
+            if ret.iter().any(|t| t.path == v.path) == false {
+                ret.push(v);
+            }
+        }
+    }
+
+    return ret;
+}
+
+/// The trash of a volume.
+///
+/// # Arguments
+/// + `topdir`: The mount point of the volume.
+/// + `create`: Return the trash even if it does not exist yet.
+fn volume_trash(topdir: &std::path::Path, create: bool) -> Option<TrashDir> {
+    let uid = uid();
+
+    // `$topdir/.Trash` is only used if it is a sticky directory, not a
+    // symlink, as required by the specification.
+    let shared = topdir.join(".Trash");
+    if is_sticky_dir(&shared) {
+        let path = shared.join(uid.to_string());
+        if create || path.is_dir() {
+            return Some(TrashDir {
+                path: path,
+                topdir: Some(topdir.to_path_buf()),
+            });
+        }
+    }
+
+    let path = topdir.join(format!(".Trash-{}", uid));
+    if create || path.is_dir() {
+        return Some(TrashDir {
+            path: path,
+            topdir: Some(topdir.to_path_buf()),
+        });
+    }
+
+    return None;
+}
+
+fn home_trash() -> Result<std::path::PathBuf, std::io::Error> {
+    match dirs::data_dir() {
+        Some(v) => Ok(v.join("Trash")),
+        None => Err(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "no data directory",
+        )),
+    }
+}
+
+#[cfg(unix)]
+fn device(path: &std::path::Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    return std::fs::symlink_metadata(path).ok().map(|v| v.dev());
+}
+
+#[cfg(not(unix))]
+fn device(_path: &std::path::Path) -> Option<u64> {
+    return None;
+}
+
+#[cfg(unix)]
+fn uid() -> u32 {
+    return unsafe { libc::getuid() };
+}
+
+#[cfg(not(unix))]
+fn uid() -> u32 {
+    return 0;
+}
+
+#[cfg(unix)]
+fn is_sticky_dir(path: &std::path::Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    return match std::fs::symlink_metadata(path) {
+        Ok(v) => v.is_dir() && v.permissions().mode() & 0o1000 != 0,
+        Err(_) => false,
+    };
+}
+
+#[cfg(not(unix))]
+fn is_sticky_dir(_path: &std::path::Path) -> bool {
+    return false;
+}
+
+/// Percent-encode a path for the `Path` key, keeping `/`.
+fn encode_path(path: &std::path::Path) -> String {
+    return path
+        .to_string_lossy()
+        .split('/')
+        .map(|v| urlencoding::encode(v).to_string())
+        .collect::<Vec<String>>()
+        .join("/");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn home() -> TrashDir {
+        return TrashDir {
+            path: std::path::PathBuf::from("/home/deck/.local/share/Trash"),
+            topdir: None,
+        };
+    }
+
+    fn volume() -> TrashDir {
+        return TrashDir {
+            path: std::path::PathBuf::from("/run/media/deck/SD/.Trash-1000"),
+            topdir: Some(std::path::PathBuf::from("/run/media/deck/SD")),
+        };
+    }
+
+    fn timestamp(text: &str) -> u64 {
+        return chrono::NaiveDateTime::parse_from_str(text, "%Y-%m-%dT%H:%M:%S")
+            .unwrap()
+            .and_local_timezone(chrono::Local)
+            .earliest()
+            .unwrap()
+            .timestamp() as u64;
+    }
+
+    #[test]
+    fn parses_home_trash_info() {
+        let text = "[Trash Info]\nPath=/home/deck/My%20Games/caf%C3%A9.sav\nDeletionDate=2024-01-31T12:05:09\n";
+        let (original, deleted) = parse_info(&home(), text).unwrap();
+        assert_eq!(
+            original,
+            std::path::PathBuf::from("/home/deck/My Games/café.sav")
+        );
+        assert_eq!(deleted, timestamp("2024-01-31T12:05:09"));
+    }
+
+    #[test]
+    fn parses_volume_trash_info() {
+        // Relative paths are relative to the top directory of the volume.
+        let text = "[Trash Info]\r\nPath=roms/a%2Bb.zip\r\nDeletionDate=2024-01-31T12:05:09\r\n";
+        let (original, _) = parse_info(&volume(), text).unwrap();
+        assert_eq!(
+            original,
+            std::path::PathBuf::from("/run/media/deck/SD/roms/a+b.zip")
+        );
+
+        // Absolute paths are kept.
+        let text = "[Trash Info]\nPath=/run/media/deck/SD/x\n";
+        let (original, _) = parse_info(&volume(), text).unwrap();
+        assert_eq!(original, std::path::PathBuf::from("/run/media/deck/SD/x"));
+    }
+
+    #[test]
+    fn tolerates_bad_deletion_dates() {
+        for date in [
+            "",
+            "yesterday",
+            "2024-01-31 12:05:09",
+            "2024-13-31T12:05:09",
+        ] {
+            let text = format!("[Trash Info]\nPath=/a\nDeletionDate={}\n", date);
+            assert_eq!(parse_info(&home(), text.as_str()).unwrap().1, 0, "{}", date);
+        }
+    }
+
+    #[test]
+    fn rejects_info_without_path() {
+        assert!(parse_info(&home(), "").is_err());
+        assert!(parse_info(&home(), "[Trash Info]\nDeletionDate=2024-01-31T12:05:09\n").is_err());
+        // Not valid UTF-8 once decoded.
+        assert!(parse_info(&home(), "[Trash Info]\nPath=/a%FF\n").is_err());
+    }
+
+    #[test]
+    fn encodes_paths() {
+        let path = std::path::Path::new("/home/deck/My Games/100% café?.sav");
+        let encoded = encode_path(path);
+        assert_eq!(encoded, "/home/deck/My%20Games/100%25%20caf%C3%A9%3F.sav");
+
+        let text = format!("[Trash Info]\nPath={}\n", encoded);
+        assert_eq!(parse_info(&home(), text.as_str()).unwrap().0, path);
+    }
+}
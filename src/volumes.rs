@@ -0,0 +1,306 @@
+/// Filesystem types that never hold user files.
+#[cfg(target_os = "linux")]
+const PSEUDO_FS_TYPES: &[&str] = &["squashfs", "overlay", "autofs", "efivarfs"];
+
+/// List mounted filesystems backed by a block device.
+///
+/// Linux only, an empty list is returned on other systems.
+#[cfg(target_os = "linux")]
+pub fn list() -> Result<Vec<frontend::protocol::Volume>, std::io::Error> {
+    let mountinfo = std::fs::read_to_string("/proc/self/mountinfo")?;
+    let labels = read_labels();
+
+    let mut ret = Vec::<frontend::protocol::Volume>::new();
+    // The `major:minor` device number of each volume in `ret`, and whether it
+    // is mounted from the root of its device.
+    let mut devices = Vec::<(String, bool)>::new();
+    for line in mountinfo.lines() {
+        let mount = match parse_mountinfo_line(line) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        if mount.source.starts_with("/dev/") == false
+            || PSEUDO_FS_TYPES.contains(&mount.fs_type.as_str())
+        {
+            continue;
+        }
+
+        // The same device may be mounted several times, e.g. bind mounts or
+        // btrfs subvolumes, also through different device paths. List it
+        // once, preferring the mount of the device root, or else the first one.
+        let duplicate = devices.iter().position(|v| v.0 == mount.device_id);
+        if let Some(idx) = duplicate {
+            if devices[idx].1 || mount.root != "/" {
+                continue;
+            }
+        }
+
+        let stat = match statvfs(mount.mount_point.as_str()) {
+            Ok(v) => v,
+            Err(e) => {
+                tracing::debug!("statvfs {} failed: {}", mount.mount_point, e);
+                continue;
+            }
+        };
+
+        let device = std::fs::canonicalize(&mount.source)
+            .map(|v| v.to_string_lossy().to_string())
+            .unwrap_or(mount.source.clone());
+
+        let label = match labels.get(&device) {
+            Some(v) => v.clone(),
+            None if mount.mount_point == "/" => "Root".to_string(),
+            None => std::path::Path::new(&mount.mount_point)
+                .file_name()
+                .map(|v| v.to_string_lossy().to_string())
+                .unwrap_or(mount.mount_point.clone()),
+        };
+
+        let volume = frontend::protocol::Volume {
+            label: label,
+            removable: is_removable(mount.mount_point.as_str(), device.as_str()),
+            read_only: mount.options.split(',').any(|v| v == "ro"),
+            mount_point: mount.mount_point,
+            device: mount.source,
+            fs_type: mount.fs_type,
+            total: stat.0,
+            free: stat.1,
+            used: stat.2,
+        };
+        match duplicate {
+            Some(idx) => {
+                ret[idx] = volume;
+                devices[idx].1 = true;
+            }
+            None => {
+                devices.push((mount.device_id, mount.root == "/"));
+                ret.push(volume);
+            }
+        }
+    }
+
+    return Ok(ret);
+}
+
+/// List mounted filesystems backed by a block device.
+///
+/// Linux only, an empty list is returned on other systems.
+#[cfg(not(target_os = "linux"))]
+pub fn list() -> Result<Vec<frontend::protocol::Volume>, std::io::Error> {
+    return Ok(Vec::new());
+}
+
+/// A line of `/proc/self/mountinfo`.
+#[cfg(target_os = "linux")]
+struct MountInfo {
+    device_id: String,
+    root: String,
+    mount_point: String,
+    options: String,
+    fs_type: String,
+    source: String,
+}
+
+/// Parse a line of `/proc/self/mountinfo`, see `proc(5)`:
+///
+/// ```text
+/// 36 35 98:0 /mnt1 /mnt2 rw,noatime master:1 - ext3 /dev/root rw,errors=continue
+/// ```
+#[cfg(target_os = "linux")]
+fn parse_mountinfo_line(line: &str) -> Option<MountInfo> {
+    let fields: Vec<&str> = line.split(' ').collect();
+
+    // Optional fields end with a single `-`.
+    let sep = fields.iter().position(|v| *v == "-")?;
+    if sep < 6 || fields.len() < sep + 3 {
+        return None;
+    }
+
+    return Some(MountInfo {
+        device_id: fields[2].to_string(),
+        root: unescape(fields[3]),
+        mount_point: unescape(fields[4]),
+        options: fields[5].to_string(),
+        fs_type: fields[sep + 1].to_string(),
+        source: unescape(fields[sep + 2]),
+    });
+}
+
+/// Decode octal escapes like `\040` used for spaces in mountinfo.
+#[cfg(target_os = "linux")]
+fn unescape(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut ret = Vec::with_capacity(bytes.len());
+
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'\\' && idx + 4 <= bytes.len() {
+            let code = std::str::from_utf8(&bytes[idx + 1..idx + 4])
+                .ok()
+                .and_then(|v| u8::from_str_radix(v, 8).ok());
+            if let Some(v) = code {
+                ret.push(v);
+                idx += 4;
+                continue;
+            }
+        }
+        ret.push(bytes[idx]);
+        idx += 1;
+    }
+
+    return String::from_utf8_lossy(&ret).to_string();
+}
+
+/// Decode hex escapes like `\x20` that udev uses in `/dev/disk/by-label`
+/// names for spaces, slashes and other unsafe bytes.
+#[cfg(target_os = "linux")]
+fn unescape_udev(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut ret = Vec::with_capacity(bytes.len());
+
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'\\' && bytes.get(idx + 1) == Some(&b'x') && idx + 4 <= bytes.len() {
+            let code = std::str::from_utf8(&bytes[idx + 2..idx + 4])
+                .ok()
+                .and_then(|v| u8::from_str_radix(v, 16).ok());
+            if let Some(v) = code {
+                ret.push(v);
+                idx += 4;
+                continue;
+            }
+        }
+        ret.push(bytes[idx]);
+        idx += 1;
+    }
+
+    return String::from_utf8_lossy(&ret).to_string();
+}
+
+/// Map canonical device paths to filesystem labels.
+#[cfg(target_os = "linux")]
+fn read_labels() -> std::collections::HashMap<String, String> {
+    let mut ret = std::collections::HashMap::new();
+
+    let entries = match std::fs::read_dir("/dev/disk/by-label") {
+        Ok(v) => v,
+        Err(_) => return ret,
+    };
+
+    for entry in entries.flatten() {
+        if let Ok(device) = std::fs::canonicalize(entry.path()) {
+            let label = unescape_udev(entry.file_name().to_string_lossy().as_ref());
+            ret.insert(device.to_string_lossy().to_string(), label);
+        }
+    }
+
+    return ret;
+}
+
+/// Whether a filesystem is on removable media, such as the microSD card.
+#[cfg(target_os = "linux")]
+fn is_removable(mount_point: &str, device: &str) -> bool {
+    if mount_point.starts_with("/run/media/") || mount_point.starts_with("/media/") {
+        return true;
+    }
+
+    let name = device.trim_start_matches("/dev/");
+    if name.starts_with("mmcblk") {
+        return true;
+    }
+
+    // `/sys/class/block/<partition>/..` is the whole disk.
+    let path = format!("/sys/class/block/{}/../removable", name);
+    if let Ok(v) = std::fs::read_to_string(path) {
+        return v.trim() == "1";
+    }
+
+    return false;
+}
+
+/// Get `(total, free, used)` bytes of a mounted filesystem.
+#[cfg(target_os = "linux")]
+fn statvfs(path: &str) -> Result<(u64, u64, u64), std::io::Error> {
+    let c_path = match std::ffi::CString::new(path) {
+        Ok(v) => v,
+        Err(e) => return Err(std::io::Error::new(std::io::ErrorKind::InvalidInput, e)),
+    };
+
+    let mut st: libc::statvfs = unsafe { std::mem::zeroed() };
+    let ret = unsafe { libc::statvfs(c_path.as_ptr(), &mut st) };
+    if ret != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    let frsize = st.f_frsize as u64;
+    let total = st.f_blocks as u64 * frsize;
+    let free = st.f_bavail as u64 * frsize;
+    let used = (st.f_blocks as u64 - st.f_bfree as u64) * frsize;
+
+    return Ok((total, free, used));
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_mountinfo_lines() {
+        let line = "29 1 259:2 / / rw,relatime shared:1 - ext4 /dev/nvme0n1p2 rw";
+        let mount = parse_mountinfo_line(line).unwrap();
+        assert_eq!(mount.device_id, "259:2");
+        assert_eq!(mount.root, "/");
+        assert_eq!(mount.mount_point, "/");
+        assert_eq!(mount.options, "rw,relatime");
+        assert_eq!(mount.fs_type, "ext4");
+        assert_eq!(mount.source, "/dev/nvme0n1p2");
+
+        // No optional fields, and a btrfs subvolume.
+        let line = "61 29 0:45 /@home /home rw,noatime - btrfs /dev/sda1 rw,ssd";
+        let mount = parse_mountinfo_line(line).unwrap();
+        assert_eq!(mount.root, "/@home");
+        assert_eq!(mount.fs_type, "btrfs");
+
+        // Several optional fields.
+        let line =
+            "84 29 179:1 / /run/media/mmcblk0p1 rw shared:45 master:2 - ext4 /dev/mmcblk0p1 rw";
+        let mount = parse_mountinfo_line(line).unwrap();
+        assert_eq!(mount.mount_point, "/run/media/mmcblk0p1");
+        assert_eq!(mount.source, "/dev/mmcblk0p1");
+    }
+
+    #[test]
+    fn unescapes_mountinfo_paths() {
+        let line = "90 29 8:17 / /run/media/deck/SD\\040Card rw - exfat /dev/sdb1 rw";
+        let mount = parse_mountinfo_line(line).unwrap();
+        assert_eq!(mount.mount_point, "/run/media/deck/SD Card");
+
+        assert_eq!(unescape("a\\011b\\134c"), "a\tb\\c");
+        // Not an octal escape, or cut short.
+        assert_eq!(unescape("a\\09b"), "a\\09b");
+        assert_eq!(unescape("a\\04"), "a\\04");
+    }
+
+    #[test]
+    fn rejects_malformed_mountinfo_lines() {
+        assert!(parse_mountinfo_line("").is_none());
+        // No separator.
+        assert!(parse_mountinfo_line("29 1 259:2 / / rw ext4 /dev/nvme0n1p2 rw").is_none());
+        // Missing fields before the separator.
+        assert!(parse_mountinfo_line("29 1 259:2 / - ext4 /dev/nvme0n1p2 rw").is_none());
+        // Missing source after the separator.
+        assert!(parse_mountinfo_line("29 1 259:2 / / rw - ext4").is_none());
+    }
+
+    #[test]
+    fn unescapes_udev_labels() {
+        assert_eq!(unescape_udev("SD\\x20Card"), "SD Card");
+        assert_eq!(unescape_udev("a\\x2fb"), "a/b");
+        assert_eq!(unescape_udev("caf\\xc3\\xa9"), "café");
+        // Not a hex escape, or cut short.
+        assert_eq!(unescape_udev("a\\xzzb"), "a\\xzzb");
+        assert_eq!(unescape_udev("a\\x2"), "a\\x2");
+        assert_eq!(unescape_udev("a\\040b"), "a\\040b");
+    }
+}
@@ -2,6 +2,15 @@
 pub struct Config {
     pub ip: String,
     pub port: u16,
+
+    /// The directory of Logiqx XML DAT files for ROM verification.
+    pub dat_dir: String,
+
+    /// Where and how many save backups are kept.
+    pub backup: crate::backup::Config,
+
+    /// The json file of bookmarks, shared by all devices using this server.
+    pub bookmarks_file: std::path::PathBuf,
 }
 
 pub struct BackendData {
@@ -23,25 +32,90 @@ impl BackendData {
     }
 }
 
+/// State shared by all workers of the webserver.
+#[derive(Default)]
+pub struct SharedData {
+    /// Progress of in-flight uploads, keyed by upload id.
+    pub uploads: std::sync::Mutex<
+        std::collections::HashMap<String, frontend::protocol::UploadProgressResponse>,
+    >,
+
+    /// Cancel flags of in-flight checksum requests, keyed by request id.
+    pub checksums: std::sync::Mutex<
+        std::collections::HashMap<String, std::sync::Arc<std::sync::atomic::AtomicBool>>,
+    >,
+
+    /// Running and finished disk usage scans.
+    pub du_jobs: std::sync::Mutex<Vec<crate::api::du::DuJob>>,
+
+    /// Installed Steam apps and game names for labeling app id folders.
+    pub steam_apps: crate::steam::AppCache,
+
+    /// Held while the bookmarks file is read or written.
+    pub bookmarks: std::sync::Mutex<()>,
+
+    /// Held while a `shortcuts.vdf` file is read and rewritten.
+    pub shortcuts: std::sync::Mutex<()>,
+}
+
 /// Create a new webserver.
 ///
 /// # Arguments
 ///
-/// + `ip`: The ip to bind to.
-/// + `port`: The port to bind to.
+/// + `config`: The webserver configuration, also available to handlers as
+///   `actix_web::web::Data<Config>`.
 pub fn new(config: Config) -> Result<actix_web::dev::Server, std::io::Error> {
     let addr = format!("{}:{}", config.ip, config.port);
+    let shared_data = actix_web::web::Data::new(SharedData::default());
+    let config = actix_web::web::Data::new(config);
 
     let srv = actix_web::HttpServer::new(move || {
         let ext_data = actix_web::web::Data::new(BackendData::new());
 
         return actix_web::App::new()
             .app_data(ext_data)
+            .app_data(shared_data.clone())
+            .app_data(config.clone())
             .service(crate::api::assets::get)
+            .service(crate::api::backup_create::post)
+            .service(crate::api::backup_delete::post)
+            .service(crate::api::backup_list::post)
+            .service(crate::api::backup_restore::post)
+            .service(crate::api::bookmark_add::post)
+            .service(crate::api::bookmark_list::post)
+            .service(crate::api::bookmark_move::post)
+            .service(crate::api::bookmark_remove::post)
+            .service(crate::api::checksum::post)
+            .service(crate::api::checksum_cancel::post)
+            .service(crate::api::complete::post)
             .service(crate::api::dirs::post)
+            .service(crate::api::download::get)
+            .service(crate::api::du::post)
             .service(crate::api::index::get)
+            .service(crate::api::paste::post)
             .service(crate::api::readdir::post)
-            .service(crate::api::upload::post);
+            .service(crate::api::rename::post)
+            .service(crate::api::romcheck::post)
+            .service(crate::api::romcheck_fix::post)
+            .service(crate::api::steam_apps::post)
+            .service(crate::api::steam_cleanup::post)
+            .service(crate::api::steam_cleanup_remove::post)
+            .service(crate::api::steam_prefix::post)
+            .service(crate::api::steam_prefixes::post)
+            .service(crate::api::steam_screenshot::get)
+            .service(crate::api::steam_screenshots::post)
+            .service(crate::api::steam_screenshots_zip::post)
+            .service(crate::api::steam_shortcuts::post)
+            .service(crate::api::steam_shortcuts_add::post)
+            .service(crate::api::steam_shortcuts_remove::post)
+            .service(crate::api::trash_empty::post)
+            .service(crate::api::trash_list::post)
+            .service(crate::api::trash_put::post)
+            .service(crate::api::trash_restore::post)
+            .service(crate::api::trash_size::post)
+            .service(crate::api::upload::post)
+            .service(crate::api::upload_progress::post)
+            .service(crate::api::volumes::post);
     })
     .bind(addr);
 